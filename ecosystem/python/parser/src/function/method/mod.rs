@@ -16,8 +16,9 @@ impl Parser<WithSource<StmtFunctionDef>> for PythonParser {
         let identifier = function.identifier;
         let inputs = function.inputs;
         let output = function.output;
+        let overloads = Default::default();
         let mutability = Mutability::Mutable;
-        Ok(Self::Output { attributes, visibility, synchrony, mutability, identifier, inputs, output })
+        Ok(Self::Output { attributes, visibility, synchrony, mutability, identifier, inputs, output, overloads })
     }
 }
 
@@ -31,7 +32,8 @@ impl Parser<WithSource<StmtAsyncFunctionDef>> for PythonParser {
         let identifier = function.identifier;
         let inputs = function.inputs;
         let output = function.output;
+        let overloads = Default::default();
         let mutability = Mutability::Mutable;
-        Ok(Self::Output { attributes, visibility, synchrony, mutability, identifier, inputs, output })
+        Ok(Self::Output { attributes, visibility, synchrony, mutability, identifier, inputs, output, overloads })
     }
 }
@@ -39,7 +39,8 @@ impl Parser<WithSource<StmtFunctionDef>> for FunctionParser {
             let synchrony = Synchrony::Synchronous;
             let inputs = self.parse_inputs(*input.ast.args, config)?;
             let output = self.parse_output(input.ast.returns, config)?;
-            Ok(Function { attributes, visibility, synchrony, identifier, inputs, output })    
+            let overloads = Default::default();
+            Ok(Function { attributes, visibility, synchrony, identifier, inputs, output, overloads })    
         }
     }
 }
@@ -56,7 +57,8 @@ impl Parser<WithSource<StmtAsyncFunctionDef>> for FunctionParser {
             let synchrony = Synchrony::Asynchronous;
             let inputs = self.parse_inputs(*input.ast.args, config)?;
             let output = self.parse_output(input.ast.returns, config)?;    
-            Ok(Function { attributes, visibility, synchrony, identifier, inputs, output })
+            let overloads = Default::default();
+            Ok(Function { attributes, visibility, synchrony, identifier, inputs, output, overloads })
         }
     }
 }
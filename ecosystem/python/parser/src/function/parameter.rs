@@ -5,19 +5,29 @@ use crate::identifier::IdentifierParser;
 use crate::literal::LiteralParser;
 use crate::prelude::*;
 use crate::types::type_::TypeParser;
+use crate::types::inference::TypeInference;
 
 #[derive(Default)]
 pub struct ParameterParser {
     literal_parser: LiteralParser,
+    type_inference: TypeInference,
 }
 
 impl Parser<ArgWithDefault> for ParameterParser {
     type Output = Parameter;
     fn parse(&self, input: ArgWithDefault, config: &ParserConfig) -> Result<Self::Output> {
-        let attributes = Default::default();
+        let mut attributes = Default::default();
         let identifier = IdentifierParser::new().parse(input.def.arg.as_str(), config)?;
+        let inferred_type = if input.def.annotation.is_none() {
+            input.default.as_ref().and_then(|value| self.type_inference.infer(value, &[], config))
+        } else {
+            None
+        };
         let type_ = if let Some(value) = input.def.annotation.and_then(|annotation| annotation.name_expr()) {
             TypeParser::default().parse(&value, config)?
+        } else if let Some(type_) = inferred_type {
+            self.type_inference.mark(&mut attributes);
+            type_
         } else {
             Default::default()
         };
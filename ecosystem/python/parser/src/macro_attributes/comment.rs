@@ -0,0 +1,64 @@
+//! Directives written as `# ligen: ...` comments, for statements that can't be decorated.
+
+use ligen::ir::{Attributes, macro_attributes::Group};
+use ligen::parser::ParserConfig;
+use rustpython_parser::ast::Ranged;
+use crate::literal::LiteralParser;
+use crate::prelude::*;
+
+type DirectivesParser = ligen::parser::universal::attributes::AttributesParser<LiteralParser>;
+
+/// Parses the `# ligen: ...` comment at the end of the statement's line, e.g.
+/// `name: str  # ligen: readonly, rename = "title"`, as a `ligen(...)` group.
+#[derive(Default)]
+pub struct CommentParser {
+    directives_parser: DirectivesParser
+}
+
+impl CommentParser {
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
+
+impl<T: Ranged> Parser<WithSource<&T>> for CommentParser {
+    type Output = Attributes;
+    fn parse(&self, input: WithSource<&T>, config: &ParserConfig) -> Result<Self::Output> {
+        let end = input.ast.end().to_usize();
+        let line_end = input.source[end..]
+            .find('\n')
+            .map_or(input.source.len(), |index| end + index);
+        let directives = input.source[end..line_end]
+            .trim()
+            .strip_prefix('#')
+            .and_then(|comment| comment.trim().strip_prefix("ligen:"));
+        match directives {
+            Some(directives) => {
+                let directives = self.directives_parser.parse(directives.trim(), config)?;
+                Ok(Group::new("ligen", directives).into())
+            },
+            None => Ok(Default::default())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::module::ModuleParser;
+    use ligen::parser::Directive;
+
+    fn directives(source: &str) -> Result<Vec<Directive>> {
+        let module = ModuleParser.parse(source, &Default::default())?;
+        let attributes = CommentParser::new().parse(module.sub(&module.ast.body[0]), &Default::default())?;
+        Ok(Directive::from_attributes(&attributes))
+    }
+
+    #[test]
+    fn comment_directives() -> Result<()> {
+        assert_eq!(directives("name: str  # ligen: readonly, rename = \"title\"\n")?, vec![Directive::Readonly(None), Directive::Rename("title".into())]);
+        assert_eq!(directives("name: str  # A name.\n")?, vec![]);
+        assert_eq!(directives("name: str")?, vec![]);
+        Ok(())
+    }
+}
@@ -1 +1,2 @@
-pub mod attributes;
\ No newline at end of file
+pub mod attributes;
+pub mod comment;
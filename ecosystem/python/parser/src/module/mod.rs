@@ -1,7 +1,9 @@
 use crate::prelude::*;
-use ligen::{ir::Module, parser::ParserConfig};
+use ligen::{ir::{Module, Path}, parser::{parallel, Filtered, ParserConfig, PathFilter}};
 use rustpython_parser::ast::ModModule;
-use crate::parser::PythonParser;
+use crate::parser::{PythonParser, PythonParserConfig};
+use std::collections::BTreeSet;
+use std::path::PathBuf;
 
 #[derive(Default)]
 pub struct ModuleParser;
@@ -30,16 +32,148 @@ impl Parser<WithSource<ModModule>> for PythonParser {
     }
 }
 
-pub(crate) struct Directory<'a>(pub &'a std::path::Path);
+/// A package directory and its module path.
+pub(crate) struct Directory<'a>(pub &'a std::path::Path, pub &'a Path);
 pub(crate) struct File<'a>(pub &'a std::path::Path);
 pub(crate) struct SubPath<'a>(pub &'a std::path::Path);
+/// Portions of a namespace package (PEP 420), possibly split across several directories, and its
+/// module path.
+pub(crate) struct Namespace<'a>(pub &'a [PathBuf], pub &'a Path);
+
+/// What a module name resolves to, following the Python import system rules.
+enum Entry {
+    /// A directory containing `__init__.py` or `__init__.pyi`.
+    Package(PathBuf),
+    /// A `.pyi` stub or, if there is none, a `.py` source file.
+    Module(PathBuf),
+    /// Directories without `__init__`, one for each portion.
+    Namespace(Vec<PathBuf>)
+}
+
+impl Entry {
+    /// Resolves `name` in the given portions. Regular packages and modules are looked up in
+    /// order and the first one wins, otherwise every matching directory is a namespace portion.
+    fn resolve(portions: &[PathBuf], name: &str) -> Option<Self> {
+        let mut namespace = Vec::new();
+        for portion in portions {
+            let path = portion.join(name);
+            if path.is_dir() && Self::init_file(&path).is_some() {
+                return Some(Self::Package(path));
+            }
+            if let Some(file) = Self::source_file(&path) {
+                return Some(Self::Module(file));
+            }
+            if path.is_dir() {
+                namespace.push(path);
+            }
+        }
+        if namespace.is_empty() {
+            None
+        } else {
+            Some(Self::Namespace(namespace))
+        }
+    }
+
+    fn source_file(path: &std::path::Path) -> Option<PathBuf> {
+        ["pyi", "py"]
+            .into_iter()
+            .map(|extension| path.with_extension(extension))
+            .find(|file| file.is_file())
+    }
+
+    fn init_file(directory: &std::path::Path) -> Option<PathBuf> {
+        Self::source_file(&directory.join("__init__"))
+    }
+}
+
+fn is_python_identifier(name: &str) -> bool {
+    let mut characters = name.chars();
+    characters
+        .next()
+        .map(|first| first == '_' || first.is_alphabetic())
+        .unwrap_or(false)
+        && characters.all(|character| character == '_' || character.is_alphanumeric())
+}
+
+impl PythonParser {
+    fn is_ignored_directory(&self, name: &str, config: &ParserConfig) -> bool {
+        PythonParserConfig::from(config)
+            .get_ignored_directories()
+            .iter()
+            .any(|ignored| match ignored.strip_prefix('*') {
+                Some(suffix) => name.ends_with(suffix),
+                None => name == ignored
+            })
+    }
+
+    /// Names of the modules and packages importable from the given portions.
+    fn module_names(&self, portions: &[PathBuf], config: &ParserConfig) -> Result<BTreeSet<String>> {
+        let mut names = BTreeSet::new();
+        for portion in portions {
+            for entry in portion.read_dir()? {
+                let path = entry?.path();
+                let name = if path.is_dir() {
+                    path
+                        .file_name()
+                        .and_then(|name| name.to_str())
+                        .filter(|name| !self.is_ignored_directory(name, config))
+                } else {
+                    match path.extension().and_then(|extension| extension.to_str()) {
+                        Some("py") | Some("pyi") => path.file_stem().and_then(|name| name.to_str()),
+                        _ => None
+                    }
+                };
+                if let Some(name) = name.filter(|name| *name != "__init__" && is_python_identifier(name)) {
+                    names.insert(name.to_string());
+                }
+            }
+        }
+        Ok(names)
+    }
+
+    fn parse_entry(&self, entry: Entry, module: &Path, config: &ParserConfig) -> Result<Option<Module>> {
+        match entry {
+            Entry::Package(path) => self.parse(Directory(path.as_path(), module), config).map(Some),
+            Entry::Module(path) => self.parse(File(path.as_path()), config).map(Some),
+            // Namespace packages without any Python code are just data directories.
+            Entry::Namespace(portions) => self
+                .parse(Namespace(portions.as_slice(), module), config)
+                .map(|module| Some(module).filter(|module| !module.is_empty()))
+        }
+    }
+
+    fn parse_sub_modules(&self, portions: &[PathBuf], parent: &Path, config: &ParserConfig) -> Result<Vec<Module>> {
+        let filter = PathFilter::from_config(config);
+        let mut entries = Vec::new();
+        for name in self.module_names(portions, config)? {
+            // Excluded modules are never read.
+            let path = parent.clone().join(name.as_str());
+            if let Some(reason) = filter.check_module(&path) {
+                self.filtered.push(Filtered { path, reason });
+                continue;
+            }
+            if let Some(entry) = Entry::resolve(portions, &name) {
+                entries.push((entry, path));
+            }
+        }
+        // Entries are sorted by name, and so are the modules.
+        let modules = parallel::map(entries, |(entry, path)| self.parse_entry(entry, &path, config))
+            .into_iter()
+            .filter_map(|module| module.ok().flatten())
+            .collect();
+        Ok(modules)
+    }
+}
 
 impl Parser<File<'_>> for PythonParser {
     type Output = Module;
     fn parse(&self, File(input): File<'_>, config: &ParserConfig) -> Result<Self::Output> {
         let content = std::fs::read_to_string(input)?;
-        let module = ModuleParser.parse(content.as_str(), config)?;
-        let mut module = self.parse(module, config)?;
+        let parse = || self.parse(ModuleParser.parse(content.as_str(), config)?, config);
+        let mut module = match &self.cache {
+            Some(cache) => cache.get_or_parse(&cache.key(&[content.as_bytes()], config), parse)?,
+            None => parse()?
+        };
         module.identifier = self.identifier_parser.parse(input, config)?;
         Ok(module)
     }
@@ -47,61 +181,143 @@ impl Parser<File<'_>> for PythonParser {
 
 impl Parser<Directory<'_>> for PythonParser {
     type Output = Module;
-    fn parse(&self, Directory(input): Directory<'_>, config: &ParserConfig) -> Result<Self::Output> {
-        let identifier = self.identifier_parser.parse(input, config)?;
-        let mut module = Module { identifier, .. Default::default() };
-        let mut modules: Vec<Module> = Vec::new();
-        for entry in input.read_dir()? {
-            let entry = entry?;
-            let path = entry.path();
-            let extension = path
-                .extension()
-                .and_then(|extension| extension.to_str())
-                .map(String::from)
-                .unwrap_or_default();
-            if extension == "py" || extension == "pyi" || path.is_dir() {
-                if let Ok(module) = self.parse(SubPath(path.as_path()), config) {
-                    if let Some(existing) = modules
-                        .iter_mut()
-                        .find(|existing| existing.identifier == module.identifier)
-                    {
-                        existing.join(module)
-                    } else {
-                        modules.push(module);
-                    }
-                }
-            }
-        }
-        if let Some((index, _)) = modules
-            .iter()
-            .enumerate()
-            .find(|(_, sub_module)| sub_module.identifier.name == "__init__")
-        {
-            let identifier = module.identifier;
-            module = modules.remove(index);
-            module.identifier = identifier;
+    fn parse(&self, Directory(input, path): Directory<'_>, config: &ParserConfig) -> Result<Self::Output> {
+        match Entry::init_file(input) {
+            Some(init) => {
+                let mut module = self.parse(File(init.as_path()), config)?;
+                module.identifier = self.identifier_parser.parse(input, config)?;
+                module.modules = self.parse_sub_modules(&[input.to_path_buf()], path, config)?;
+                Ok(module)
+            },
+            None => self.parse(Namespace(&[input.to_path_buf()], path), config)
         }
-        module.modules = modules;
-        Ok(module)
+    }
+}
+
+impl Parser<Namespace<'_>> for PythonParser {
+    type Output = Module;
+    fn parse(&self, Namespace(portions, path): Namespace<'_>, config: &ParserConfig) -> Result<Self::Output> {
+        let first = portions
+            .first()
+            .ok_or(Error::Message("Namespace package without portions.".into()))?;
+        let identifier = self.identifier_parser.parse(first.as_path(), config)?;
+        let modules = self.parse_sub_modules(portions, path, config)?;
+        Ok(Module { identifier, modules, .. Default::default() })
     }
 }
 
 impl Parser<SubPath<'_>> for PythonParser {
     type Output = Module;
     fn parse(&self, SubPath(input): SubPath<'_>, config: &ParserConfig) -> Result<Self::Output> {
-        let input = if input.with_extension("py").exists() {
-            input.with_extension("py")
+        let entry = if input.is_file() {
+            Some(Entry::Module(input.to_path_buf()))
         } else {
-            input.to_path_buf()
+            let name = input
+                .file_name()
+                .and_then(|name| name.to_str())
+                .ok_or(Error::Message(format!("Invalid module path {}.", input.display())))?;
+            let mut portions = vec![input.parent().unwrap_or(std::path::Path::new("")).to_path_buf()];
+            portions.extend(PythonParserConfig::from(config).get_search_paths());
+            Entry::resolve(&portions, name)
         };
+        match entry {
+            Some(Entry::Module(path)) => self.parse(File(path.as_path()), config)
+                .map_err(|error| Error::Message(format!("Failed to read {}. Cause: {:?}", path.display(), error))),
+            Some(entry) => self
+                .parse_entry(entry, &Path::from(self.identifier_parser.parse(input, config)?), config)?
+                .ok_or(Error::Message(format!("No Python code found in {}.", input.display()))),
+            None => Err(Error::Message(format!("Failed to find module {}.", input.display())))
+        }
+    }
+}
 
-        let input = input.as_path();
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
 
-        if input.is_dir() {
-            self.parse(Directory(input), config)
-        } else {
-            self.parse(File(input), config)
-                .map_err(|error| Error::Message(format!("Failed to read {}. Cause: {:?}", input.display(), error)))
-        }        
+    fn workspace(name: &str) -> Result<PathBuf> {
+        let root = std::env::temp_dir().join("ligen-python-parser").join(name);
+        if root.exists() {
+            fs::remove_dir_all(&root)?;
+        }
+        fs::create_dir_all(&root)?;
+        Ok(root)
+    }
+
+    fn write(path: PathBuf, content: &str) -> Result<()> {
+        fs::create_dir_all(path.parent().unwrap())?;
+        fs::write(path, content)?;
+        Ok(())
+    }
+
+    fn sub_module<'a>(module: &'a Module, name: &str) -> Option<&'a Module> {
+        module.modules.iter().find(|module| module.identifier.name == name)
+    }
+
+    #[test]
+    fn regular_package_with_stub() -> Result<()> {
+        let root = workspace("regular_package_with_stub")?;
+        write(root.join("package").join("__init__.py"), "def source(): pass")?;
+        write(root.join("package").join("__init__.pyi"), "def stub() -> int: ...")?;
+        write(root.join("package").join("__pycache__").join("cached.py"), "def cached(): pass")?;
+        write(root.join("package").join("tests").join("test_package.py"), "def test(): pass")?;
+        write(root.join("package").join("package-1.0.dist-info").join("metadata.py"), "")?;
+        let module = PythonParser::default().parse(SubPath(root.join("package").as_path()), &Default::default())?;
+        assert_eq!(module.identifier.name, "package");
+        assert_eq!(module.functions.len(), 1);
+        assert_eq!(module.functions[0].identifier.name, "stub");
+        assert!(module.modules.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn excluded_modules() -> Result<()> {
+        let root = workspace("excluded_modules")?;
+        write(root.join("package").join("__init__.py"), "def package(): pass")?;
+        write(root.join("package").join("api.py"), "def api(): pass")?;
+        write(root.join("package").join("_vendor").join("__init__.py"), "def vendored(): pass")?;
+        let mut config = ParserConfig::default();
+        config.set_exclude(vec!["_vendor"]);
+        let parser = PythonParser::default();
+        let module = parser.parse(SubPath(root.join("package").as_path()), &config)?;
+        assert_eq!(module.modules.len(), 1);
+        assert_eq!(module.modules[0].identifier.name, "api");
+        assert_eq!(parser.filtered.to_vec().iter().map(|filtered| filtered.to_string()).collect::<Vec<_>>(), vec!["package::_vendor: excluded by `_vendor`"]);
+        Ok(())
+    }
+
+    #[test]
+    fn cached_modules() -> Result<()> {
+        let root = workspace("cached_modules")?;
+        write(root.join("package").join("__init__.py"), "def package(): pass")?;
+        write(root.join("package").join("api.py"), "def api(): pass")?;
+        let parser = PythonParser::default().with_cache(ligen::parser::ParseCache::new(root.join("cache")));
+        let config = ParserConfig::default();
+        let module = parser.parse(SubPath(root.join("package").as_path()), &config)?;
+        assert_eq!(fs::read_dir(root.join("cache").join("python"))?.count(), 2);
+        assert_eq!(parser.parse(SubPath(root.join("package").as_path()), &config)?, module);
+        write(root.join("package").join("api.py"), "def api(): pass\ndef other(): pass")?;
+        let module = parser.parse(SubPath(root.join("package").as_path()), &config)?;
+        assert_eq!(sub_module(&module, "api").map(|module| module.functions.len()), Some(2));
+        assert_eq!(fs::read_dir(root.join("cache").join("python"))?.count(), 3);
+        Ok(())
+    }
+
+    #[test]
+    fn namespace_package() -> Result<()> {
+        let root = workspace("namespace_package")?;
+        write(root.join("first").join("namespace").join("module.py"), "def module(): pass")?;
+        write(root.join("first").join("namespace").join("data").join("file.txt"), "")?;
+        write(root.join("second").join("namespace").join("other").join("__init__.py"), "def other(): pass")?;
+        let mut config = PythonParserConfig::default();
+        config.set_search_paths(vec![root.join("second").display().to_string()]);
+        let config = ParserConfig::from(config);
+        let module = PythonParser::default().parse(SubPath(root.join("first").join("namespace").as_path()), &config)?;
+        assert_eq!(module.identifier.name, "namespace");
+        assert_eq!(module.modules.len(), 2);
+        assert_eq!(sub_module(&module, "module").map(|module| module.functions.len()), Some(1));
+        assert_eq!(sub_module(&module, "other").map(|module| module.functions.len()), Some(1));
+        Ok(())
     }
 }
@@ -42,7 +42,8 @@ impl Parser<&Expr> for ObjectParser {
             let mutability = identifier_parser.get_mutability(&identifier);
             let type_ = Default::default();
             let literal = Default::default();
-            Ok(Object { identifier, mutability, literal, type_ })
+            let attributes = Default::default();
+            Ok(Object { attributes, identifier, mutability, literal, type_ })
         }
     }
 }
@@ -1,4 +1,5 @@
-use ligen::parser::{ParserConfigSet, ParserConfigGet, ParserConfig};
+use ligen::ir::{Literal, Path, Type};
+use ligen::parser::{ConfigOption, ConfigSchema, OptionKind, ParserConfigSet, ParserConfigGet, ParserConfig};
 
 use crate::prelude::*;
 
@@ -19,6 +20,11 @@ impl Default for PythonParserConfig<ParserConfig> {
         let config = Default::default();
         let mut config = Self { config };
         config.set_class_variables_as_properties(false);
+        config.set_ignored_directories(Self::default_ignored_directories());
+        config.set_search_paths(Vec::<String>::new());
+        config.set_type_checking(true);
+        config.set_target_version("3.12");
+        config.set_target_platform(Self::default_target_platform());
         config
     }
 }
@@ -33,9 +39,73 @@ impl PythonParserConfig<ParserConfig> {
     pub fn new() -> PythonParserConfig<ParserConfig> {
         Default::default()
     }
+
+    /// Options of the Python parser.
+    pub fn schema() -> ConfigSchema {
+        ConfigSchema::common()
+            .with_option(
+                ConfigOption::new("ligen::python::class_variables_as_properties", OptionKind::Boolean, "Parses class variables as properties instead of constants.")
+                    .with_default(false)
+            )
+            .with_option(
+                ConfigOption::new("ligen::python::ignored_directories", OptionKind::Strings, "Directory names skipped while walking a package. A leading `*` matches any prefix.")
+                    .with_default(Literal::Vector(Self::default_ignored_directories().into_iter().map(Literal::String).collect()))
+            )
+            .with_option(
+                ConfigOption::new("ligen::python::search_paths", OptionKind::Strings, "Additional directories searched for portions of namespace packages (PEP 420).")
+                    .with_default(Literal::Vector(Default::default()))
+            )
+            .with_option(
+                ConfigOption::new("ligen::python::type_checking", OptionKind::Boolean, "Value assumed for `typing.TYPE_CHECKING` when choosing conditional branches.")
+                    .with_default(true)
+            )
+            .with_option(
+                ConfigOption::new("ligen::python::target_version", OptionKind::String, "Python version compared against `sys.version_info`, e.g. \"3.12\".")
+                    .with_default("3.12")
+            )
+            .with_option(
+                ConfigOption::new("ligen::python::target_platform", OptionKind::String, "Platform compared against `sys.platform`, e.g. \"linux\", \"win32\" or \"darwin\".")
+                    .with_default(Self::default_target_platform())
+            )
+            .with_option(ConfigOption::new("ligen::python::types::*", OptionKind::String, "IR type the Python type is mapped to, e.g. `I64` for `int`."))
+    }
 }
 
 impl<T> PythonParserConfig<T> {
+    /// Directories that are never parsed as packages. A leading `*` matches any prefix.
+    pub fn default_ignored_directories() -> Vec<String> {
+        ["__pycache__", "tests", "test", "*.dist-info", "*.egg-info"]
+            .into_iter()
+            .map(String::from)
+            .collect()
+    }
+
+    /// The `sys.platform` value of the host.
+    pub fn default_target_platform() -> &'static str {
+        match std::env::consts::OS {
+            "windows" => "win32",
+            "macos" => "darwin",
+            os => os
+        }
+    }
+
+    fn get_strings(&self, path: &str) -> Option<Vec<String>>
+    where T: ParserConfigGet
+    {
+        self.config
+            .get(path)
+            .and_then(|literal| match literal {
+                Literal::Vector(values) | Literal::Tuple(values) => Some(values),
+                _ => None
+            })
+            .map(|values| {
+                values
+                    .iter()
+                    .filter_map(|value| value.as_string().cloned())
+                    .collect()
+            })
+    }
+
     pub fn set_class_variables_as_properties(&mut self, value: bool)
     where T: ParserConfigSet
     {
@@ -51,4 +121,117 @@ impl<T> PythonParserConfig<T> {
             .cloned()
             .unwrap_or(false)
     }
+
+    pub fn set_ignored_directories<S: Into<String>>(&mut self, directories: Vec<S>)
+    where T: ParserConfigSet
+    {
+        let directories = directories.into_iter().map(|directory| Literal::String(directory.into())).collect();
+        self.config.set("ligen::python::ignored_directories", Literal::Vector(directories));
+    }
+
+    /// Directory names skipped while walking a package.
+    pub fn get_ignored_directories(&self) -> Vec<String>
+    where T: ParserConfigGet
+    {
+        self.get_strings("ligen::python::ignored_directories")
+            .unwrap_or_else(Self::default_ignored_directories)
+    }
+
+    pub fn set_search_paths<S: Into<String>>(&mut self, paths: Vec<S>)
+    where T: ParserConfigSet
+    {
+        let paths = paths.into_iter().map(|path| Literal::String(path.into())).collect();
+        self.config.set("ligen::python::search_paths", Literal::Vector(paths));
+    }
+
+    /// Additional directories searched for portions of namespace packages (PEP 420).
+    pub fn get_search_paths(&self) -> Vec<std::path::PathBuf>
+    where T: ParserConfigGet
+    {
+        self.get_strings("ligen::python::search_paths")
+            .unwrap_or_default()
+            .into_iter()
+            .map(std::path::PathBuf::from)
+            .collect()
+    }
+
+    pub fn set_type_checking(&mut self, value: bool)
+    where T: ParserConfigSet
+    {
+        self.config.set("ligen::python::type_checking", value);
+    }
+
+    /// Value assumed for `typing.TYPE_CHECKING` when choosing conditional branches.
+    pub fn get_type_checking(&self) -> bool
+    where T: ParserConfigGet
+    {
+        self.config
+            .get("ligen::python::type_checking")
+            .and_then(|literal| literal.as_boolean())
+            .cloned()
+            .unwrap_or(true)
+    }
+
+    pub fn set_target_version<S: Into<String>>(&mut self, version: S)
+    where T: ParserConfigSet
+    {
+        self.config.set("ligen::python::target_version", version.into());
+    }
+
+    /// Python version compared against `sys.version_info`, e.g. "3.12".
+    pub fn get_target_version(&self) -> String
+    where T: ParserConfigGet
+    {
+        self.config
+            .get("ligen::python::target_version")
+            .and_then(|literal| literal.as_string())
+            .cloned()
+            .unwrap_or_else(|| "3.12".into())
+    }
+
+    pub fn set_target_platform<S: Into<String>>(&mut self, platform: S)
+    where T: ParserConfigSet
+    {
+        self.config.set("ligen::python::target_platform", platform.into());
+    }
+
+    /// Platform compared against `sys.platform`, e.g. "linux", "win32" or "darwin".
+    pub fn get_target_platform(&self) -> String
+    where T: ParserConfigGet
+    {
+        self.config
+            .get("ligen::python::target_platform")
+            .and_then(|literal| literal.as_string())
+            .cloned()
+            .unwrap_or_else(|| Self::default_target_platform().into())
+    }
+
+    /// Maps the Python type `python` to the IR type `ligen`, e.g. `int` to `I64`.
+    pub fn set_type_mapping<S: Into<String>>(&mut self, python: &str, ligen: S)
+    where T: ParserConfigSet
+    {
+        self.config.set(Path::from("ligen::python::types").join(python), ligen.into());
+    }
+
+    /// The IR type configured for the Python type `python`, if any.
+    pub fn get_type_mapping(&self, python: &str) -> Option<Type>
+    where T: ParserConfigGet
+    {
+        self.config
+            .get(Path::from("ligen::python::types").join(python))
+            .and_then(|literal| literal.as_string())
+            .map(|ligen| Path::from(ligen.as_str()).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn schema() {
+        let config = PythonParserConfig::default();
+        assert_eq!(PythonParserConfig::schema().validate(&config), vec![]);
+        assert_eq!(PythonParserConfig::schema().defaults().iter().count(), config.iter().count());
+    }
 }
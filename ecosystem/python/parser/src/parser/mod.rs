@@ -4,17 +4,28 @@ use crate::object::ObjectParser;
 use crate::function::FunctionParser;
 use crate::identifier::IdentifierParser;
 use crate::types::type_definition::TypeDefinitionParser;
+use crate::types::inference::TypeInference;
 
 pub mod metadata;
 pub mod config;
-pub mod validator;
 
 pub use config::*;
 pub use metadata::*;
-pub use validator::*;
 
 use ligen::ir::{Registry, Library};
-use ligen::parser::ParserConfig;
+use ligen::parser::{parallel, ConfigSchema, DirectiveApplier, FilterReport, Filtered, ParseCache, ParserConfig, ParserPlugin, PathFilter};
+
+/// The parser's plugin.
+pub fn plugin() -> ParserPlugin {
+    ParserPlugin::new("Python", "Python packages and their dependencies.", PythonParser::default)
+        .with_input("Package directory")
+}
+
+/// The parser's plugin, caching the modules parsed from each file in `cache`.
+pub fn cached_plugin(cache: ParseCache) -> ParserPlugin {
+    ParserPlugin::new("Python", "Python packages and their dependencies.", move || PythonParser::default().with_cache(cache.clone()))
+        .with_input("Package directory")
+}
 
 #[derive(Default)]
 pub struct PythonParser {
@@ -23,13 +34,22 @@ pub struct PythonParser {
     pub type_definition_parser: TypeDefinitionParser,
     pub metadata_parser: MetadataParser,
     pub object_parser: ObjectParser,
-    pub validator: LibraryValidator
+    pub type_inference: TypeInference,
+    pub filtered: FilterReport,
+    /// Cache of the modules parsed from each file.
+    pub cache: Option<ParseCache>
 }
 
 impl PythonParser {
     pub fn new() -> Self {
         Default::default()
     }
+
+    /// Caches the modules parsed from each file in `cache`.
+    pub fn with_cache(mut self, cache: ParseCache) -> Self {
+        self.cache = Some(cache.for_parser("Python", env!("CARGO_PKG_VERSION")));
+        self
+    }
 }
 
 impl PythonParser {
@@ -41,7 +61,8 @@ impl PythonParser {
         let metadata = self.metadata_parser.parse(input, config)?;
         let root_module = self.parse(SubPath(input), config)?;
         let mut library = Library { identifier, metadata, root_module };
-        self.validator.validate(&mut library, config)?;
+        DirectiveApplier::new().apply_library(&mut library);
+        self.filtered.extend(PathFilter::from_config(config).apply_library(&mut library));
         Ok(library)
     }
 }
@@ -49,15 +70,22 @@ impl PythonParser {
 impl Parser<&std::path::Path> for PythonParser {
     type Output = Registry;
     fn parse(&self, input: &std::path::Path, config: &ParserConfig) -> Result<Self::Output> {
-        let mut registry = Registry::new();
-        let library = self.parse_library(input, config)?;
-        for dependency in library.metadata.dependencies.iter().filter(|dependency| dependency.feature.is_none()) { // TODO: We need to support features.
-            let dependency_path = input.parent().unwrap().join(dependency.identifier.to_string());
-            let mut dependency_library = self.parse(dependency_path.as_path(), config)?;
-            registry.libraries.append(&mut dependency_library.libraries);
-        }
-        registry.libraries.push(library);
-        Ok(registry)
+        parallel::with_workers(config, || {
+            let mut registry = Registry::new();
+            let library = self.parse_library(input, config)?;
+            let dependencies = library
+                .metadata
+                .dependencies
+                .iter()
+                .filter(|dependency| dependency.feature.is_none()) // TODO: We need to support features.
+                .map(|dependency| input.parent().unwrap().join(dependency.identifier.to_string()))
+                .collect();
+            for dependency_registry in parallel::map(dependencies, |dependency_path| self.parse(dependency_path.as_path(), config)) {
+                registry.libraries.extend(dependency_registry?.libraries);
+            }
+            registry.libraries.push(library);
+            Ok(registry)
+        })?
     }
     fn name(&self) -> &str {
         "Python"
@@ -65,4 +93,10 @@ impl Parser<&std::path::Path> for PythonParser {
     fn config(&self) -> ParserConfig {
         PythonParserConfig::default().into()
     }
+    fn schema(&self) -> ConfigSchema {
+        PythonParserConfig::schema()
+    }
+    fn filtered(&self) -> Vec<Filtered> {
+        self.filtered.to_vec()
+    }
 }
\ No newline at end of file
@@ -0,0 +1,176 @@
+use std::cmp::Ordering;
+use rustpython_parser::ast::{BoolOp, CmpOp, Constant, Expr, UnaryOp};
+use ligen::parser::ParserConfig;
+use crate::parser::PythonParserConfig;
+
+/// Statically evaluates the well-known conditions guarding Python definitions.
+/// `None` means the condition can't be known at parse time.
+pub struct ConditionEvaluator {
+    type_checking: bool,
+    version: Vec<i64>,
+    platform: String
+}
+
+impl ConditionEvaluator {
+    pub fn new(config: &ParserConfig) -> Self {
+        let config = PythonParserConfig::from(config);
+        let type_checking = config.get_type_checking();
+        let version = config
+            .get_target_version()
+            .split('.')
+            .filter_map(|number| number.parse().ok())
+            .collect();
+        let platform = config.get_target_platform();
+        Self { type_checking, version, platform }
+    }
+
+    pub fn evaluate(&self, expr: &Expr) -> Option<bool> {
+        match expr {
+            Expr::Constant(constant) => match &constant.value {
+                Constant::Bool(value) => Some(*value),
+                _ => None
+            },
+            Expr::Name(_) | Expr::Attribute(_) if self.is_type_checking(expr) => Some(self.type_checking),
+            Expr::UnaryOp(unary) if matches!(unary.op, UnaryOp::Not) => self.evaluate(&unary.operand).map(|value| !value),
+            Expr::BoolOp(bool_op) => {
+                let values = bool_op.values.iter().map(|value| self.evaluate(value));
+                match bool_op.op {
+                    BoolOp::And => values.fold(Some(true), |all, value| match (all, value) {
+                        (Some(false), _) | (_, Some(false)) => Some(false),
+                        (Some(true), Some(true)) => Some(true),
+                        _ => None
+                    }),
+                    BoolOp::Or => values.fold(Some(false), |any, value| match (any, value) {
+                        (Some(true), _) | (_, Some(true)) => Some(true),
+                        (Some(false), Some(false)) => Some(false),
+                        _ => None
+                    })
+                }
+            },
+            Expr::Compare(compare) if compare.ops.len() == 1 && compare.comparators.len() == 1 => {
+                let operator = &compare.ops[0];
+                let right = &compare.comparators[0];
+                if self.is_sys_attribute(&compare.left, "version_info") {
+                    self.version_ordering(right).and_then(|ordering| Self::compare(operator, ordering))
+                } else if self.is_sys_attribute(&compare.left, "platform") {
+                    self.string(right).and_then(|platform| Self::compare(operator, self.platform.as_str().cmp(platform)))
+                } else {
+                    None
+                }
+            },
+            Expr::Call(call) if call.args.len() == 1 => match call.func.as_ref() {
+                Expr::Attribute(attribute) if attribute.attr.as_str() == "startswith" && self.is_sys_attribute(&attribute.value, "platform") => {
+                    self.string(&call.args[0]).map(|prefix| self.platform.starts_with(prefix))
+                },
+                _ => None
+            },
+            _ => None
+        }
+    }
+
+    fn compare(operator: &CmpOp, ordering: Ordering) -> Option<bool> {
+        match operator {
+            CmpOp::Eq => Some(ordering == Ordering::Equal),
+            CmpOp::NotEq => Some(ordering != Ordering::Equal),
+            CmpOp::Lt => Some(ordering == Ordering::Less),
+            CmpOp::LtE => Some(ordering != Ordering::Greater),
+            CmpOp::Gt => Some(ordering == Ordering::Greater),
+            CmpOp::GtE => Some(ordering != Ordering::Less),
+            _ => None
+        }
+    }
+
+    /// Compares the target version with a tuple such as `(3, 11)`, only up to the tuple's length.
+    fn version_ordering(&self, expr: &Expr) -> Option<Ordering> {
+        let numbers = match expr {
+            Expr::Tuple(tuple) => tuple
+                .elts
+                .iter()
+                .map(|element| match element {
+                    Expr::Constant(constant) => match &constant.value {
+                        Constant::Int(integer) => i64::try_from(integer).ok(),
+                        _ => None
+                    },
+                    _ => None
+                })
+                .collect::<Option<Vec<i64>>>()?,
+            _ => return None
+        };
+        let length = numbers.len().min(self.version.len());
+        Some(self.version[..length].cmp(&numbers[..length]))
+    }
+
+    fn string<'a>(&self, expr: &'a Expr) -> Option<&'a str> {
+        match expr {
+            Expr::Constant(constant) => match &constant.value {
+                Constant::Str(string) => Some(string.as_str()),
+                _ => None
+            },
+            _ => None
+        }
+    }
+
+    fn is_type_checking(&self, expr: &Expr) -> bool {
+        match expr {
+            Expr::Name(name) => name.id.as_str() == "TYPE_CHECKING",
+            Expr::Attribute(attribute) => attribute.attr.as_str() == "TYPE_CHECKING"
+                && matches!(attribute.value.as_ref(), Expr::Name(name) if name.id.as_str() == "typing"),
+            _ => false
+        }
+    }
+
+    /// Checks for `sys.<name>`, also accepting slices such as `sys.version_info[:2]`.
+    fn is_sys_attribute(&self, expr: &Expr, name: &str) -> bool {
+        match expr {
+            Expr::Attribute(attribute) => attribute.attr.as_str() == name
+                && matches!(attribute.value.as_ref(), Expr::Name(module) if module.id.as_str() == "sys"),
+            Expr::Subscript(subscript) => self.is_sys_attribute(&subscript.value, name),
+            _ => false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rustpython_parser::ast::Expr;
+    use rustpython_parser::Parse;
+
+    fn evaluate(condition: &str) -> Option<bool> {
+        let mut config = PythonParserConfig::default();
+        config.set_target_version("3.11");
+        config.set_target_platform("linux");
+        let expr = Expr::parse(condition, "<embedded>").expect("Failed to parse condition.");
+        ConditionEvaluator::new(&config.into()).evaluate(&expr)
+    }
+
+    #[test]
+    fn type_checking() {
+        assert_eq!(evaluate("TYPE_CHECKING"), Some(true));
+        assert_eq!(evaluate("typing.TYPE_CHECKING"), Some(true));
+        assert_eq!(evaluate("not TYPE_CHECKING"), Some(false));
+    }
+
+    #[test]
+    fn version_info() {
+        assert_eq!(evaluate("sys.version_info >= (3, 11)"), Some(true));
+        assert_eq!(evaluate("sys.version_info >= (3, 12)"), Some(false));
+        assert_eq!(evaluate("sys.version_info[:2] < (3, 8)"), Some(false));
+        assert_eq!(evaluate("sys.version_info >= (3,)"), Some(true));
+    }
+
+    #[test]
+    fn platform() {
+        assert_eq!(evaluate("sys.platform == 'linux'"), Some(true));
+        assert_eq!(evaluate("sys.platform == 'win32'"), Some(false));
+        assert_eq!(evaluate("sys.platform.startswith('lin')"), Some(true));
+        assert_eq!(evaluate("sys.platform != 'darwin' and sys.version_info >= (3, 9)"), Some(true));
+    }
+
+    #[test]
+    fn unknown() {
+        assert_eq!(evaluate("HAS_NUMPY"), None);
+        assert_eq!(evaluate("HAS_NUMPY and TYPE_CHECKING"), None);
+        assert_eq!(evaluate("HAS_NUMPY or TYPE_CHECKING"), Some(true));
+    }
+}
@@ -1,13 +1,15 @@
 mod import_parser;
 mod scope_type;
+mod condition;
 
-use rustpython_parser::ast::{Arguments, Expr, Stmt};
-use ligen::{ir::{Interface, Object, Function, Method, Import, TypeDefinition}, parser::ParserConfig};
-use crate::{prelude::*, parser::PythonParserConfig};
+use rustpython_parser::ast::{Arguments, Expr, Ranged, Stmt};
+use ligen::{ir::{Attribute, Attributes, Identifier, Interface, Object, Function, Method, Import, TypeDefinition, macro_attributes::{Group, Named}}, parser::ParserConfig};
+use crate::{prelude::*, parser::PythonParserConfig, literal::LiteralParser, macro_attributes::comment::CommentParser};
 
 // TODO: REMOVE THIS.
 // pub use import_parser::*;
 pub use scope_type::*;
+pub use condition::*;
 use crate::parser::PythonParser;
 
 impl Parser<WithSource<&[Stmt]>> for PythonParser {
@@ -21,7 +23,9 @@ impl Parser<WithSource<&[Stmt]>> for PythonParser {
         let methods = self.parse_methods(&input, config)?;
         let scope = Scope { imports, objects, types, functions, methods, interfaces };
         let sub_scopes = self.parse_sub_scopes(&input, config)?;
-        let scope = self.join_scopes(scope, sub_scopes);
+        let mut scope = self.join_scopes(scope, sub_scopes);
+        scope.functions = self.collect_overloads(scope.functions, |function| &function.identifier, |function| &function.attributes, |function| &mut function.overloads);
+        scope.methods = self.collect_overloads(scope.methods, |method| &method.identifier, |method| &method.attributes, |method| &mut method.overloads);
         Ok(scope)
     }
 }
@@ -35,10 +39,76 @@ impl PythonParser {
         scope
     }
 
+    /// Collects the `@overload` signatures sharing an identifier into the first one and drops the
+    /// implementation signature. Definitions without overloads are kept as they are.
+    fn collect_overloads<T>(
+        &self,
+        definitions: Vec<T>,
+        identifier: impl Fn(&T) -> &Identifier,
+        attributes: impl Fn(&T) -> &Attributes,
+        overloads: impl Fn(&mut T) -> &mut Vec<T>
+    ) -> Vec<T> {
+        let is_overload = |definition: &T| attributes(definition).get_group("overload").is_some();
+        let mut groups: Vec<Vec<T>> = Vec::new();
+        for definition in definitions {
+            match groups.iter_mut().find(|group| identifier(&group[0]) == identifier(&definition)) {
+                Some(group) => group.push(definition),
+                None => groups.push(vec![definition])
+            }
+        }
+        let mut collected = Vec::new();
+        for group in groups {
+            if group.iter().any(is_overload) {
+                let mut signatures = group.into_iter().filter(is_overload);
+                if let Some(mut primary) = signatures.next() {
+                    for mut signature in signatures {
+                        let nested = std::mem::take(overloads(&mut signature));
+                        overloads(&mut primary).push(signature);
+                        overloads(&mut primary).extend(nested);
+                    }
+                    collected.push(primary);
+                }
+            } else {
+                collected.extend(group);
+            }
+        }
+        collected
+    }
+
+    /// Infers the type and literal of unannotated objects from their assigned `value`.
+    fn infer_objects(&self, mut objects: Vec<Object>, value: &Expr, known_classes: &[Identifier], config: &ParserConfig) -> Vec<Object> {
+        if config.get_only_parse_symbols() {
+            return objects;
+        }
+        let type_ = self.type_inference.infer(value, known_classes, config);
+        let literal = LiteralParser::default().parse(value, config).ok();
+        for object in &mut objects {
+            if let Some(type_) = &type_ {
+                object.type_ = type_.clone();
+                self.type_inference.mark(&mut object.attributes);
+            }
+            if let Some(literal) = &literal {
+                object.literal = literal.clone();
+            }
+        }
+        objects
+    }
+
+    /// Adds the `# ligen: ...` comment directives of `statement` to the objects it defines.
+    fn add_comment_directives(&self, mut objects: Vec<Object>, statement: WithSource<&Stmt>, config: &ParserConfig) -> Vec<Object> {
+        let directives = CommentParser::new().parse(statement, config).unwrap_or_default();
+        for object in &mut objects {
+            object.attributes.attributes.extend(directives.attributes.iter().cloned());
+        }
+        objects
+    }
+
+    /// Keeps the last assignment of each object. Assignments under different conditions are kept.
     fn deduplicate_objects(&self, objects: Vec<Object>) -> Vec<Object> {
+        let condition = |object: &Object| object.attributes.get_literal_from_path("python::condition").cloned();
         let mut deduplicated_objects: Vec<Object> = Vec::new();
         for object in objects.into_iter().rev() {
-            if !deduplicated_objects.iter().any(|deduplicated_object| deduplicated_object.identifier == object.identifier) {
+            if !deduplicated_objects.iter().any(|deduplicated_object| deduplicated_object.identifier == object.identifier && condition(deduplicated_object) == condition(&object)) {
                 deduplicated_objects.push(object)
             }
         }
@@ -49,10 +119,20 @@ impl PythonParser {
         let mut sub_scopes = Vec::new();
         for statement in statements.ast {
             match statement {
-                Stmt::If(ast) => {
-                    sub_scopes.push(self.parse(statements.sub( ast.body.as_slice()), config)?);
-                    sub_scopes.push(self.parse(statements.sub(ast.orelse.as_slice()), config)?);
+                Stmt::If(ast) => match ConditionEvaluator::new(config).evaluate(&ast.test) {
+                    Some(true) => sub_scopes.push(self.parse(statements.sub(ast.body.as_slice()), config)?),
+                    Some(false) => sub_scopes.push(self.parse(statements.sub(ast.orelse.as_slice()), config)?),
+                    None => {
+                        let condition = &statements.source[ast.test.start().to_usize()..ast.test.end().to_usize()];
+                        let mut body = self.parse(statements.sub(ast.body.as_slice()), config)?;
+                        let mut orelse = self.parse(statements.sub(ast.orelse.as_slice()), config)?;
+                        self.add_condition(&mut body, condition.to_string());
+                        self.add_condition(&mut orelse, format!("not ({})", condition));
+                        sub_scopes.push(body);
+                        sub_scopes.push(orelse);
+                    }
                 },
+                // Exception handlers such as `except ImportError:` are fallbacks and aren't parsed.
                 Stmt::Try(ast) => {
                     sub_scopes.push(self.parse(statements.sub(ast.body.as_slice()), config)?);
                     sub_scopes.push(self.parse(statements.sub(ast.orelse.as_slice()), config)?);
@@ -64,6 +144,20 @@ impl PythonParser {
         Ok(sub_scopes)
     }
 
+    /// Records the condition guarding the definitions of a scope that couldn't be statically evaluated.
+    fn add_condition(&self, scope: &mut Scope, condition: String) {
+        let attribute: Attribute = Group::new("python", Named::new("condition", condition)).into();
+        let attributes = scope.imports.iter_mut().map(|import| &mut import.attributes)
+            .chain(scope.objects.iter_mut().map(|object| &mut object.attributes))
+            .chain(scope.types.iter_mut().map(|type_| &mut type_.attributes))
+            .chain(scope.functions.iter_mut().map(|function| &mut function.attributes))
+            .chain(scope.methods.iter_mut().map(|method| &mut method.attributes))
+            .chain(scope.interfaces.iter_mut().map(|interface| &mut interface.attributes));
+        for attributes in attributes {
+            attributes.attributes.push(attribute.clone());
+        }
+    }
+
     fn has_static_decorator(&self, decorator_list: WithSource<&[Expr]>) -> bool {
         decorator_list
             .ast
@@ -189,21 +283,28 @@ impl PythonParser {
         let mut objects = Vec::new();
         let class_variables_as_properties = PythonParserConfig::from(config).get_class_variables_as_properties();
         if !class_variables_as_properties {
+            let known_classes = statements
+                .ast
+                .iter()
+                .filter_map(|statement| statement.as_class_def_stmt())
+                .map(|class| Identifier::from(class.name.as_str()))
+                .collect::<Vec<_>>();
             for statement in statements.ast {
                 match statement {
                     Stmt::Assign(assign) => {
                         if let Ok(more_objects) = self.object_parser.parse(assign, config) {
-                            objects.extend(more_objects)
+                            let more_objects = self.infer_objects(more_objects, &assign.value, &known_classes, config);
+                            objects.extend(self.add_comment_directives(more_objects, statements.sub(statement), config))
                         }
                     },
                     Stmt::AnnAssign(assign) => {
                         if let Ok(object) = self.object_parser.parse(statements.sub(assign), config) {
-                            objects.push(object)
+                            objects.extend(self.add_comment_directives(vec![object], statements.sub(statement), config))
                         }
                     },
                     Stmt::AugAssign(assign) => {
                         if let Ok(object) = self.object_parser.parse(assign, config) {
-                            objects.push(object)
+                            objects.extend(self.add_comment_directives(vec![object], statements.sub(statement), config))
                         }
                     },
                     _ => ()
@@ -212,4 +313,95 @@ impl PythonParser {
         }
         Ok(objects)
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::module::ModuleParser;
+    use ligen::ir::Type;
+
+    fn parse(source: &str) -> Result<Scope> {
+        let module = ModuleParser.parse(source, &Default::default())?;
+        PythonParser::default().parse(module.sub(module.ast.body.as_slice()), &Default::default())
+    }
+
+    #[test]
+    fn conditional_branches() -> Result<()> {
+        let scope = parse(r#"
+if TYPE_CHECKING:
+    def checked(): pass
+else:
+    def unchecked(): pass
+
+if sys.version_info < (3, 0):
+    def legacy(): pass
+
+if HAS_NUMPY:
+    def numpy(): pass
+"#)?;
+        let names = scope.functions.iter().map(|function| function.identifier.name.as_str()).collect::<Vec<_>>();
+        assert_eq!(names, vec!["checked", "numpy"]);
+        let condition = scope.functions[1].attributes.get_literal_from_path("python::condition");
+        assert_eq!(condition, Some(&"HAS_NUMPY".into()));
+        Ok(())
+    }
+
+    #[test]
+    fn conditional_objects() -> Result<()> {
+        let scope = parse(r#"
+if HAS_NUMPY:
+    BACKEND = 1
+else:
+    BACKEND = 2
+"#)?;
+        let conditions = scope
+            .objects
+            .iter()
+            .filter(|object| object.identifier.name == "BACKEND")
+            .map(|object| object.attributes.get_literal_from_path("python::condition").cloned())
+            .collect::<Vec<_>>();
+        assert_eq!(conditions.len(), 2);
+        assert!(conditions.contains(&Some("HAS_NUMPY".into())));
+        assert!(conditions.contains(&Some("not (HAS_NUMPY)".into())));
+        Ok(())
+    }
+
+    #[test]
+    fn overloads() -> Result<()> {
+        let scope = parse(r#"
+@overload
+def convert(value: int) -> str: ...
+@overload
+def convert(value: str) -> int: ...
+def convert(value): pass
+def other(): pass
+"#)?;
+        assert_eq!(scope.functions.len(), 2);
+        let convert = &scope.functions[0];
+        assert_eq!(convert.identifier.name, "convert");
+        assert_eq!(convert.output, Some(Type::string()));
+        assert_eq!(convert.overloads.len(), 1);
+        assert_eq!(convert.overloads[0].output, Some(Type::i32()));
+        assert!(scope.functions[1].overloads.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn inferred_objects() -> Result<()> {
+        let scope = parse(r#"
+class Point: pass
+MAX = 10
+ORIGIN = Point()
+handler = get_handler()
+"#)?;
+        let object = |name: &str| scope.objects.iter().find(|object| object.identifier.name == name).unwrap();
+        assert_eq!(object("MAX").type_, Type::i32());
+        assert_eq!(object("MAX").literal, 10.into());
+        assert!(object("MAX").attributes.has_inferred_attribute());
+        assert_eq!(object("ORIGIN").type_, Type::from(Identifier::from("Point")));
+        assert_eq!(object("handler").type_, Type::default());
+        assert!(!object("handler").attributes.has_inferred_attribute());
+        Ok(())
+    }
+}
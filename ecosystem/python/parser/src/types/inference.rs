@@ -0,0 +1,115 @@
+use rustpython_parser::ast::{Constant, Expr, UnaryOp};
+use ligen::ir::{Attribute, Attributes, Identifier, Type, macro_attributes::Group};
+use ligen::parser::ParserConfig;
+use crate::prelude::*;
+use crate::types::type_::{PythonMapper, TypeParser};
+
+/// Infers the type of unannotated objects and parameters from the values assigned to them.
+#[derive(Default)]
+pub struct TypeInference {
+    type_parser: TypeParser,
+    mapper: PythonMapper
+}
+
+impl TypeInference {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Marks `attributes` as belonging to an inferred type.
+    pub fn mark(&self, attributes: &mut Attributes) {
+        attributes.push(Attribute::Group(Group::new("ligen", Group::from("inferred"))));
+    }
+
+    /// Infers the type of `value`. Constructor calls are only trusted for builtin types and
+    /// `known_classes`, which are usually the classes defined in the same scope.
+    pub fn infer(&self, value: &Expr, known_classes: &[Identifier], config: &ParserConfig) -> Option<Type> {
+        match value {
+            Expr::Constant(constant) => match &constant.value {
+                Constant::None | Constant::Ellipsis => None,
+                constant => self.type_parser.parse(constant, config).ok()
+            },
+            Expr::UnaryOp(unary) if matches!(unary.op, UnaryOp::USub | UnaryOp::UAdd) => {
+                self.infer(&unary.operand, known_classes, config).filter(|type_| type_.is_number())
+            },
+            Expr::List(list) => Some(Type::vector(self.infer_elements(&list.elts, known_classes, config))),
+            Expr::Set(set) => Some(self.container("set", vec![self.infer_elements(&set.elts, known_classes, config)], config)),
+            Expr::Tuple(tuple) => Some(Type::tuple(
+                tuple
+                    .elts
+                    .iter()
+                    .map(|element| self.infer(element, known_classes, config).unwrap_or_else(Type::opaque))
+                    .collect()
+            )),
+            Expr::Dict(dict) => {
+                let keys = dict.keys.iter().flatten().cloned().collect::<Vec<_>>();
+                let key = self.infer_elements(&keys, known_classes, config);
+                let value = self.infer_elements(&dict.values, known_classes, config);
+                Some(self.container("dict", vec![key, value], config))
+            },
+            Expr::Call(call) => {
+                let name = call.func.as_name_expr()?;
+                let identifier = Identifier::from(name.id.as_str());
+                if self.mapper.map(&identifier, config).is_some() {
+                    self.type_parser.parse(name, config).ok()
+                } else if known_classes.contains(&identifier) {
+                    Some(identifier.into())
+                } else {
+                    None
+                }
+            },
+            _ => None
+        }
+    }
+
+    /// The common type of homogeneous elements, or an opaque type.
+    fn infer_elements(&self, elements: &[Expr], known_classes: &[Identifier], config: &ParserConfig) -> Type {
+        let mut types = elements.iter().map(|element| self.infer(element, known_classes, config));
+        if let Some(first) = types.next().flatten() {
+            if types.all(|type_| type_.as_ref() == Some(&first)) {
+                return first;
+            }
+        }
+        Type::opaque()
+    }
+
+    fn container(&self, name: &str, generics: Vec<Type>, config: &ParserConfig) -> Type {
+        let mut type_ = self.mapper.map_or_keep(&name.into(), config);
+        type_.path.last_mut().generics.types = generics;
+        type_
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rustpython_parser::Parse;
+
+    fn infer(value: &str) -> Option<Type> {
+        let expr = Expr::parse(value, "<embedded>").expect("Failed to parse expression.");
+        TypeInference::new().infer(&expr, &["Point".into()], &Default::default())
+    }
+
+    #[test]
+    fn literals() {
+        assert_eq!(infer("10"), Some(Type::i32()));
+        assert_eq!(infer("-30.0"), Some(Type::f64()));
+        assert_eq!(infer("'name'"), Some(Type::string()));
+        assert_eq!(infer("True"), Some(Type::boolean()));
+        assert_eq!(infer("None"), None);
+    }
+
+    #[test]
+    fn containers() {
+        assert_eq!(infer("[1, 2, 3]"), Some(Type::vector(Type::i32())));
+        assert_eq!(infer("[1, 'a']"), Some(Type::vector(Type::opaque())));
+        assert_eq!(infer("(1, 'a')"), Some(Type::tuple(vec![Type::i32(), Type::string()])));
+    }
+
+    #[test]
+    fn constructors() {
+        assert_eq!(infer("float('inf')"), Some(Type::f64()));
+        assert_eq!(infer("Point(1, 2)"), Some(Type::from(Identifier::from("Point"))));
+        assert_eq!(infer("unknown()"), None);
+    }
+}
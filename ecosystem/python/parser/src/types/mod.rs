@@ -1,2 +1,3 @@
 pub mod type_;
-pub mod type_definition;
\ No newline at end of file
+pub mod type_definition;
+pub mod inference;
\ No newline at end of file
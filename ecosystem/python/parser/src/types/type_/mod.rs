@@ -6,28 +6,63 @@ pub use validator::*;
 use rustpython_parser::ast::{ExprName, Expr, ExprSubscript, ExprTuple, Ranged, ExprList, ExprConstant, Constant, ExprAttribute};
 use ligen::{ir::{Path, Type, Identifier}, parser::ParserConfig};
 use crate::prelude::*;
+use crate::parser::PythonParserConfig;
 
+/// Maps Python type names to IR types.
 pub struct PythonMapper {
-    map: HashMap<Identifier, Identifier>
+    map: HashMap<Identifier, Type>
 }
 
 impl Default for PythonMapper {
     fn default() -> Self {
-        let mut map = HashMap::new();
-        map.insert("bool".into(), Identifier::boolean(), );
-        map.insert("int".into(), Identifier::i32());
-        map.insert("float".into(), Identifier::f64());
-        map.insert("str".into(), Identifier::string());
-        map.insert("Optional".into(), Identifier::option());
-        map.insert("Any".into(), Identifier::opaque());
-        map.insert("byte".into(), Identifier::i8());
-        map.insert("datetime".into(), Identifier::date_time());
-        map.insert("List".into(), Identifier::vector());
-        map.insert("list".into(), Identifier::vector());
-        map.insert("dict".into(), Identifier::dictionary());
-        map.insert("Dict".into(), Identifier::dictionary());
-        map.insert("tuple".into(), Identifier::tuple());
-        Self { map }
+        let mut mapper = Self { map: HashMap::new() };
+        // Builtins.
+        mapper.insert("bool", Type::boolean());
+        mapper.insert("int", Type::i32());
+        mapper.insert("float", Type::f64());
+        mapper.insert("complex", Type::opaque());
+        mapper.insert("str", Type::string());
+        mapper.insert("byte", Type::i8());
+        mapper.insert("bytes", Type::vector(Type::u8()));
+        mapper.insert("bytearray", Type::vector(Type::u8()));
+        mapper.insert("memoryview", Type::vector(Type::u8()));
+        mapper.insert("object", Type::opaque());
+        mapper.insert("type", Type::opaque());
+        mapper.insert("list", Identifier::vector());
+        mapper.insert("tuple", Identifier::tuple());
+        mapper.insert("dict", Identifier::dictionary());
+        mapper.insert("set", Identifier::set());
+        mapper.insert("frozenset", Identifier::set());
+        // Standard library.
+        mapper.insert("datetime", Identifier::date_time());
+        mapper.insert("Decimal", Type::opaque());
+        mapper.insert("deque", Identifier::vector());
+        mapper.insert("defaultdict", Identifier::dictionary());
+        // typing special forms.
+        mapper.insert("Any", Identifier::opaque());
+        mapper.insert("Optional", Identifier::option());
+        mapper.insert("Union", Identifier::union());
+        mapper.insert("Text", Type::string());
+        mapper.insert("LiteralString", Type::string());
+        for name in ["Callable", "Literal", "Type", "TypeVar", "Awaitable", "Coroutine", "NoReturn", "Never", "Hashable", "Sized"] {
+            mapper.insert(name, Type::opaque());
+        }
+        // typing aliases and collections.abc.
+        for name in ["List", "Sequence", "MutableSequence", "Iterable", "Iterator", "Collection", "Container", "Reversible",
+            "Generator", "AsyncIterable", "AsyncIterator", "AsyncGenerator", "Deque", "ValuesView"] {
+            mapper.insert(name, Identifier::vector());
+        }
+        for name in ["Dict", "Mapping", "MutableMapping", "DefaultDict", "OrderedDict", "Counter", "ChainMap"] {
+            mapper.insert(name, Identifier::dictionary());
+        }
+        for name in ["Set", "FrozenSet", "AbstractSet", "MutableSet", "KeysView", "ItemsView"] {
+            mapper.insert(name, Identifier::set());
+        }
+        for name in ["ByteString", "Buffer"] {
+            mapper.insert(name, Type::vector(Type::u8()));
+        }
+        mapper.insert("Tuple", Identifier::tuple());
+        mapper
     }
 }
 
@@ -36,9 +71,33 @@ impl PythonMapper {
         Default::default()
     }
 
-    pub fn to_ligen(&self, identifier: &Identifier) -> Option<&Identifier> {
+    /// Registers or overrides the IR type of a Python type name.
+    pub fn insert(&mut self, python: impl Into<Identifier>, ligen: impl Into<Type>) {
+        self.map.insert(python.into(), ligen.into());
+    }
+
+    pub fn to_ligen(&self, identifier: &Identifier) -> Option<&Type> {
         self.map.get(identifier)
     }
+
+    /// Maps a Python type name, giving priority to the mappings in the `PythonParserConfig`.
+    pub fn map(&self, identifier: &Identifier, config: &ParserConfig) -> Option<Type> {
+        PythonParserConfig::from(config)
+            .get_type_mapping(&identifier.name)
+            .or_else(|| self.to_ligen(identifier).cloned())
+    }
+
+    /// Maps a Python type name, keeping it as it is if there is no mapping.
+    pub fn map_or_keep(&self, identifier: &Identifier, config: &ParserConfig) -> Type {
+        let mut type_ = self
+            .map(identifier, config)
+            .unwrap_or_else(|| identifier.clone().into());
+        let last = type_.path.last_mut();
+        if last.identifier == Identifier::vector() && last.generics.types.is_empty() {
+            last.generics.types.push(Type::opaque());
+        }
+        type_
+    }
 }
 
 pub struct TypeParser {
@@ -60,18 +119,8 @@ impl TypeParser {
 
 impl Parser<&ExprName> for TypeParser {
     type Output = Type;
-    fn parse(&self, input: &ExprName, _config: &ParserConfig) -> Result<Self::Output> {
-        let name = input.id.as_str();
-        let identifier = self
-            .mapper
-            .to_ligen(&name.into())
-            .cloned()
-            .unwrap_or(Identifier::from(name));
-        let mut type_ = Type::from(identifier);
-        if type_.path.last().identifier == Identifier::vector() {
-            type_.path.last_mut().generics.types.push(Type::opaque());
-        }
-        Ok(type_)
+    fn parse(&self, input: &ExprName, config: &ParserConfig) -> Result<Self::Output> {
+        Ok(self.mapper.map_or_keep(&input.id.as_str().into(), config))
     }
 }
 
@@ -134,19 +183,19 @@ impl Parser<WithSource<&ExprConstant>> for TypeParser {
 
 impl Parser<&Constant> for TypeParser {
     type Output = Type;
-    fn parse(&self, input: &Constant, _config: &ParserConfig) -> Result<Self::Output> {
+    fn parse(&self, input: &Constant, config: &ParserConfig) -> Result<Self::Output> {
         match &input {
             Constant::Ellipsis => Ok(Type::variadic(Type::opaque())),
-            Constant::Str(_) => Ok(Type::string()),
-            Constant::Bool(_) => Ok(Type::boolean()),
-            Constant::Bytes(_) => Ok(Type::vector(Type::u8())),
-            Constant::Float(_) => Ok(Type::f64()),
-            Constant::Int(_) => Ok(Type::i32()),
+            Constant::Str(_) => Ok(self.mapper.map_or_keep(&"str".into(), config)),
+            Constant::Bool(_) => Ok(self.mapper.map_or_keep(&"bool".into(), config)),
+            Constant::Bytes(_) => Ok(self.mapper.map_or_keep(&"bytes".into(), config)),
+            Constant::Float(_) => Ok(self.mapper.map_or_keep(&"float".into(), config)),
+            Constant::Int(_) => Ok(self.mapper.map_or_keep(&"int".into(), config)),
             Constant::None => Ok(Type::option(Type::opaque())),
             Constant::Tuple(values) => {
                 let types = values
                     .iter()
-                    .map(|expr| self.parse(expr, _config))
+                    .map(|expr| self.parse(expr, config))
                     .collect::<Result<Vec<Type>>>()?;
                 Ok(Type::tuple(types))
             },
@@ -155,16 +204,29 @@ impl Parser<&Constant> for TypeParser {
     }
 }
 
+/// Modules whose names are mapped when qualified, e.g. `typing.List`. Names qualified by other
+/// modules, e.g. `numpy.Set`, are kept as they are.
+const MAPPED_MODULES: [&str; 7] = ["typing", "typing_extensions", "collections", "collections.abc", "builtins", "decimal", "datetime"];
+
+/// Dotted name of a module expression, e.g. `collections.abc`.
+fn qualifier(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Name(name) => Some(name.id.as_str().to_string()),
+        Expr::Attribute(attribute) => qualifier(&attribute.value).map(|value| format!("{}.{}", value, attribute.attr.as_str())),
+        _ => None
+    }
+}
+
 impl Parser<WithSource<&ExprAttribute>> for TypeParser {
     type Output = Type;
     fn parse(&self, input: WithSource<&ExprAttribute>, config: &ParserConfig) -> Result<Self::Output> {
+        let identifier = Identifier::from(input.ast.attr.as_str());
+        let is_mapped_module = qualifier(&input.ast.value).map_or(false, |qualifier| MAPPED_MODULES.contains(&qualifier.as_str()));
+        if is_mapped_module && self.mapper.map(&identifier, config).is_some() {
+            // Well-known names such as `typing.List` or `collections.abc.Mapping`.
+            return Ok(self.mapper.map_or_keep(&identifier, config));
+        }
         let mut type_ = self.parse(input.sub(&*input.ast.value), config)?;
-        let name = input.ast.attr.as_str();
-        let identifier = self
-            .mapper
-            .to_ligen(&name.into())
-            .cloned()
-            .unwrap_or(Identifier::from(name));
         type_.path = Path::from(identifier).join(type_.path);
         Ok(type_)
     }
@@ -185,3 +247,42 @@ impl Parser<WithSource<&Expr>> for TypeParser {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rustpython_parser::Parse;
+    use ligen::parser::ParserConfig;
+
+    fn parse(annotation: &str, config: &ParserConfig) -> Result<Type> {
+        let expr = Expr::parse(annotation, "<embedded>").map_err(|error| Error::Message(format!("{:?}", error)))?;
+        TypeParser::new().parse(WithSource::new(annotation, &expr), config)
+    }
+
+    #[test]
+    fn typing_vocabulary() -> Result<()> {
+        let config = Default::default();
+        assert_eq!(parse("Sequence[str]", &config)?, Type::vector(Type::string()));
+        assert_eq!(parse("typing.Optional[int]", &config)?, Type::option(Type::i32()));
+        assert_eq!(parse("collections.abc.Iterable", &config)?, Type::vector(Type::opaque()));
+        assert_eq!(parse("frozenset[int]", &config)?, Type::set(Type::i32()));
+        assert_eq!(parse("bytes", &config)?, Type::vector(Type::u8()));
+        assert_eq!(parse("typing_extensions.Sequence[int]", &config)?, Type::vector(Type::i32()));
+        for annotation in ["mypkg.Type", "np.Set", "foo.Counter", "sqlalchemy.Sequence"] {
+            assert!(!parse(annotation, &config)?.is_builtin(), "{}", annotation);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn configured_mapping() -> Result<()> {
+        let mut config = PythonParserConfig::default();
+        config.set_type_mapping("int", "I64");
+        config.set_type_mapping("UUID", "String");
+        let config = config.into();
+        assert_eq!(parse("int", &config)?, Type::i64());
+        assert_eq!(parse("List[int]", &config)?, Type::vector(Type::i64()));
+        assert_eq!(parse("UUID", &config)?, Type::string());
+        Ok(())
+    }
+}
@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-use crate::{prelude::*, identifier::IdentifierParser, macro_attributes::attributes::AttributesParser, function::FunctionParser, types::type_::TypeParser, parser::PythonParserConfig};
+use crate::{prelude::*, identifier::IdentifierParser, macro_attributes::{attributes::AttributesParser, comment::CommentParser}, function::FunctionParser, types::type_::TypeParser, parser::PythonParserConfig};
 use ligen::{ir::{Type, TypeDefinition, Path, KindDefinition, Structure, Attribute, Field}, parser::ParserConfig};
 use ligen::ir::macro_attributes::Group;
 use ligen::ir::Mutability;
@@ -51,7 +51,7 @@ impl TypeDefinitionParser {
         let visibility = identifier_parser.get_visibility(&identifier);
         let identifier = Some(identifier);
         let type_ = TypeParser::new().parse(input.sub(&*input.ast.annotation), config)?;
-        let attributes = Default::default();
+        let attributes = CommentParser::new().parse(input.sub(input.ast), config)?;
         Ok(Field { identifier, type_, visibility, attributes })
     }
 
@@ -69,7 +69,7 @@ impl TypeDefinitionParser {
             let visibility = parser.get_visibility(&identifier);
             let identifier = Some(identifier);
             let type_ = Default::default();
-            let attributes = Default::default();
+            let attributes = CommentParser::new().parse(input.sub(input.ast), config)?;
             Ok(Field { identifier, type_, visibility, attributes })
         } else {
             Err(Error::Message("Expected mutable identifier".into()))
@@ -86,7 +86,7 @@ impl TypeDefinitionParser {
                     let visibility = parser.get_visibility(&identifier);
                     let identifier = Some(identifier);
                     let type_ = Default::default();
-                    let attributes = Default::default();
+                    let attributes = CommentParser::new().parse(input.sub(input.ast), config)?;
                     let field = Field { identifier, type_, visibility, attributes };
                     fields.push(field);
                 }
@@ -1,12 +1,40 @@
 use ligen_ir::Identifier;
 use ligen_ir::prelude::*;
 use ligen_ir::Library;
-use ligen_parser::Parser;
+use ligen_parser::{Filtered, ParseCache, Parser, ParserPlugin};
 use ligen_parser::ParserConfig;
 use ligen_rust_parser::module::ModuleParser;
 
+/// The parser's plugin.
+pub fn plugin() -> ParserPlugin {
+    ParserPlugin::from_library_parser("Rust", "Rust crates, without their dependencies.", LibraryParser::default)
+        .with_input("Cargo.toml")
+        .with_input("Crate directory")
+}
+
+/// The parser's plugin, caching the modules parsed from each file in `cache`.
+pub fn cached_plugin(cache: ParseCache) -> ParserPlugin {
+    ParserPlugin::from_library_parser("Rust", "Rust crates, without their dependencies.", move || LibraryParser::new().with_cache(cache.clone()))
+        .with_input("Cargo.toml")
+        .with_input("Crate directory")
+}
+
 #[derive(Default)]
-pub struct LibraryParser {}
+pub struct LibraryParser {
+    module_parser: ModuleParser
+}
+
+impl LibraryParser {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Caches the modules parsed from each file in `cache`.
+    pub fn with_cache(mut self, cache: ParseCache) -> Self {
+        self.module_parser = std::mem::take(&mut self.module_parser).with_cache(cache);
+        self
+    }
+}
 
 impl Parser<&std::path::Path> for LibraryParser {
     type Output = Library;
@@ -28,9 +56,12 @@ impl Parser<&std::path::Path> for LibraryParser {
         let library_path = directory.join(library.path.unwrap_or("src/lib.rs".into()));
 
         let identifier = Identifier::from(package.name.as_str());
-        let mut root_module = ModuleParser::new().parse(library_path.as_path(), config)?;
-        root_module.identifier = identifier.clone();
+        let root_module = self.module_parser.parse_file(library_path.as_path(), package.name.as_str(), config)?;
         let metadata = Default::default();
         Ok(Self::Output { identifier, metadata, root_module })
     }
+
+    fn filtered(&self) -> Vec<Filtered> {
+        Parser::<&std::path::Path>::filtered(&self.module_parser)
+    }
 }
\ No newline at end of file
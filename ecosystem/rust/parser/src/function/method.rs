@@ -1,12 +1,12 @@
-use ligen::ir::Mutability;
+use ligen_ir::Mutability;
 use crate::prelude::*;
 
-use ligen::ir::{Attributes, Method, Parameter, Type};
-use ligen::parser::{Parser, ParserConfig};
+use ligen_ir::{Attributes, Method, Parameter, Type};
+use ligen_parser::{Parser, ParserConfig};
 use crate::function::parameter::ParameterParser;
 use crate::function::SynchronyParser;
 use crate::identifier::IdentifierParser;
-use crate::macro_attributes::attributes::AttributeParser;
+use crate::macro_attributes::attributes::{AttributeParser, AttributesParser};
 use crate::types::TypeParser;
 use crate::visibility::VisibilityParser;
 
@@ -22,7 +22,12 @@ impl MethodParser {
 impl Parser<syn::ImplItemFn> for MethodParser {
     type Output = Method;
     fn parse(&self, method: syn::ImplItemFn, config: &ParserConfig) -> Result<Self::Output> {
-        if let Some(receiver) = method.sig.receiver() {
+        if method.sig.receiver().is_some() && config.get_only_parse_symbols() {
+            let attributes = AttributesParser::default().parse(method.attrs, config)?;
+            let visibility = VisibilityParser.parse(method.vis, config)?;
+            let identifier = IdentifierParser::new().parse(method.sig.ident, config)?;
+            Ok(Method { attributes, visibility, identifier, ..Default::default() })
+        } else if let Some(receiver) = method.sig.receiver() {
             let mutability = if receiver.mutability.is_some() { Mutability::Mutable } else { Mutability::Constant };
             let syn::Signature { asyncness, ident, inputs, output, .. } = method.sig;
             let inputs: Vec<Parameter> = inputs
@@ -51,6 +56,7 @@ impl Parser<syn::ImplItemFn> for MethodParser {
                 identifier: IdentifierParser::new().parse(ident, config)?,
                 inputs,
                 output,
+                overloads: Default::default(),
             })
         } else {
             Err(Error::Message("Function is not a method.".to_string()))
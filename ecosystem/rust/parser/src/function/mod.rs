@@ -1,7 +1,7 @@
 use crate::prelude::*;
 
-use ligen::ir::{Function, Parameter, Type};
-use ligen::parser::{Parser, ParserConfig};
+use ligen_ir::{Function, Parameter, Type};
+use ligen_parser::{Parser, ParserConfig};
 use crate::function::parameter::ParameterParser;
 use crate::identifier::IdentifierParser;
 use crate::macro_attributes::attributes::AttributesParser;
@@ -27,13 +27,17 @@ impl FunctionParser {
 impl Parser<syn::ItemFn> for FunctionParser {
     type Output = Function;
     fn parse(&self, item_fn: syn::ItemFn, config: &ParserConfig) -> Result<Self::Output> {
+        let identifier = IdentifierParser::new().parse(item_fn.sig.ident, config)?;
         let attributes = AttributesParser::default().parse(item_fn.attrs, config)?;
         let visibility = VisibilityParser.parse(item_fn.vis, config)?;
+        if config.get_only_parse_symbols() {
+            return Ok(Function { attributes, visibility, identifier, ..Default::default() });
+        }
         let synchrony = SynchronyParser.parse(item_fn.sig.asyncness, config)?;
-        let identifier = IdentifierParser::new().parse(item_fn.sig.ident, config)?;
         let inputs = self.parse_inputs(item_fn.sig.inputs, config)?;
         let output = self.parse_output(item_fn.sig.output, config)?;
-        Ok(Self::Output { attributes, visibility, synchrony, identifier, inputs, output })
+        let overloads = Default::default();
+        Ok(Self::Output { attributes, visibility, synchrony, identifier, inputs, output, overloads })
     }
 }
 
@@ -42,6 +46,11 @@ impl Parser<syn::ImplItemFn> for FunctionParser {
     fn parse(&self, function: syn::ImplItemFn, config: &ParserConfig) -> Result<Self::Output> {
         if function.sig.receiver().is_some() {
             Err(Error::Message("Function is not a method.".to_string()))
+        } else if config.get_only_parse_symbols() {
+            let attributes = AttributesParser::default().parse(function.attrs, config)?;
+            let visibility = VisibilityParser.parse(function.vis, config)?;
+            let identifier = IdentifierParser::new().parse(function.sig.ident, config)?;
+            Ok(Function { attributes, visibility, identifier, ..Default::default() })
         } else {
             let attributes = AttributesParser::default().parse(function.attrs, config)?;
             let visibility = VisibilityParser.parse(function.vis, config)?;
@@ -49,7 +58,8 @@ impl Parser<syn::ImplItemFn> for FunctionParser {
             let identifier = IdentifierParser::new().parse(function.sig.ident, config)?;
             let inputs = self.parse_inputs(function.sig.inputs, config)?;
             let output = self.parse_output(function.sig.output, config)?;
-            Ok(Self::Output { attributes, visibility, synchrony, identifier, inputs, output })    
+            let overloads = Default::default();
+            Ok(Self::Output { attributes, visibility, synchrony, identifier, inputs, output, overloads })    
         }
     }
 }
@@ -99,11 +109,11 @@ impl Parser<&str> for FunctionParser {
 
 #[cfg(test)]
 mod test {
-    use ligen::parser::assert::assert_eq;
+    use ligen_parser::assert::assert_eq;
     use crate::function::FunctionParser;
     use crate::prelude::*;
 
-    use ligen::ir::function::mock;
+    use ligen_ir::function::mock;
 
     #[test]
     fn function() -> Result<()> {
@@ -1,8 +1,8 @@
 //! Function parameter.
 
 use crate::prelude::*;
-use ligen::ir::{Identifier, Type, Mutability, Parameter};
-use ligen::parser::{Parser, ParserConfig};
+use ligen_ir::{Identifier, Type, Mutability, Parameter};
+use ligen_parser::{Parser, ParserConfig};
 use crate::identifier::IdentifierParser;
 use crate::macro_attributes::attributes::AttributesParser;
 use crate::types::TypeParser;
@@ -82,8 +82,8 @@ mod test {
     use crate::function::parameter::ParameterParser;
     use crate::prelude::*;
 
-    use ligen::ir::function::parameter::mock;
-    use ligen::parser::assert::assert_eq;
+    use ligen_ir::function::parameter::mock;
+    use ligen_parser::assert::assert_eq;
 
     #[test]
     fn primitive_parameter() -> Result<()> {
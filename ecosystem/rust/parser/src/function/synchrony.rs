@@ -1,5 +1,5 @@
-use ligen::ir::Synchrony;
-use ligen::parser::{Parser, ParserConfig};
+use ligen_ir::Synchrony;
+use ligen_parser::{Parser, ParserConfig};
 use crate::prelude::*;
 
 pub struct SynchronyParser;
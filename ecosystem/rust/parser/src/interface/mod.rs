@@ -1,11 +1,12 @@
 use crate::function::{FunctionParser, MethodParser};
+use crate::identifier::IdentifierParser;
 use crate::macro_attributes::attributes::AttributesParser;
 use crate::object::ObjectParser;
 use crate::prelude::*;
 use crate::types::TypeParser;
 
-use ligen::parser::{Parser, ParserConfig};
-use ligen::ir::{Path, Interface, Visibility, Function, Method, Object};
+use ligen_parser::{Parser, ParserConfig};
+use ligen_ir::{Path, Interface, Visibility, Function, Method, Object};
 
 
 #[derive(Default)]
@@ -20,6 +21,9 @@ impl InterfaceParser {
 impl Parser<syn::ItemImpl> for InterfaceParser {
     type Output = Interface;
     fn parse(&self, input: syn::ItemImpl, config: &ParserConfig) -> Result<Self::Output> {
+        if config.get_only_parse_symbols() {
+            return self.parse_symbols(input, config);
+        }
         let attributes = AttributesParser::default().parse(input.attrs, config)?;
         let visibility = Visibility::Public;
 
@@ -36,6 +40,21 @@ impl Parser<syn::ItemImpl> for InterfaceParser {
 }
 
 impl InterfaceParser {
+    /// Parses the identifiers of the interface and its members, without their types.
+    fn parse_symbols(&self, input: syn::ItemImpl, config: &ParserConfig) -> Result<Interface> {
+        let segment = match &*input.self_ty {
+            syn::Type::Path(type_path) => type_path.path.segments.last().ok_or("Interface without a self type.")?,
+            _ => return Err(Error::Message("Only paths are supported as the self type of impl blocks.".into()))
+        };
+        let identifier = IdentifierParser::new().parse(segment.ident.clone(), config)?;
+        let attributes = AttributesParser::default().parse(input.attrs, config)?;
+        let visibility = Visibility::Public;
+        let functions = self.extract_functions(input.items.as_slice(), config)?;
+        let methods = self.extract_methods(input.items.as_slice(), config)?;
+        let objects = self.extract_objects(input.items.as_slice(), config)?;
+        Ok(Interface { attributes, visibility, identifier, methods, objects, functions, ..Default::default() })
+    }
+
     fn extract_interfaces(&self, _items: &[syn::ImplItem], _config: &ParserConfig) -> Result<Vec<Path>> {
         Ok(Default::default())
     }
@@ -0,0 +1,105 @@
+//! Items that can be annotated with `#[ligen]`.
+
+use serde::{Deserialize, Serialize};
+use ligen_ir::{Function, Identifier, Interface, Module, Object, TypeDefinition};
+use ligen_parser::{Parser, ParserConfig};
+use crate::function::FunctionParser;
+use crate::interface::InterfaceParser;
+use crate::module::ModuleParser;
+use crate::object::ObjectParser;
+use crate::prelude::*;
+use crate::types::enumeration::EnumerationParser;
+use crate::types::structure::StructureParser;
+use crate::types::type_alias::TypeAliasParser;
+
+/// An item annotated with `#[ligen]`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Item {
+    Module(Module),
+    TypeDefinition(TypeDefinition),
+    Function(Function),
+    Interface(Interface),
+    Object(Object)
+}
+
+impl Item {
+    /// Kind of the item, e.g. `function`.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Module(_) => "module",
+            Self::TypeDefinition(_) => "type",
+            Self::Function(_) => "function",
+            Self::Interface(_) => "interface",
+            Self::Object(_) => "object"
+        }
+    }
+
+    /// Identifier of the item.
+    pub fn identifier(&self) -> &Identifier {
+        match self {
+            Self::Module(module) => &module.identifier,
+            Self::TypeDefinition(type_) => &type_.identifier,
+            Self::Function(function) => &function.identifier,
+            Self::Interface(interface) => &interface.identifier,
+            Self::Object(object) => &object.identifier
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct ItemParser {
+    module_parser: ModuleParser,
+    structure_parser: StructureParser,
+    enumeration_parser: EnumerationParser,
+    type_alias_parser: TypeAliasParser,
+    function_parser: FunctionParser,
+    interface_parser: InterfaceParser,
+    object_parser: ObjectParser
+}
+
+impl ItemParser {
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
+
+impl Parser<proc_macro2::TokenStream> for ItemParser {
+    type Output = Item;
+    fn parse(&self, token_stream: proc_macro2::TokenStream, config: &ParserConfig) -> Result<Self::Output> {
+        syn::parse2::<syn::Item>(token_stream)
+            .map_err(|e| Error::Message(format!("Failed to parse item: {:?}", e)))
+            .and_then(|item| self.parse(item, config))
+    }
+}
+
+impl Parser<syn::Item> for ItemParser {
+    type Output = Item;
+    fn parse(&self, item: syn::Item, config: &ParserConfig) -> Result<Self::Output> {
+        match item {
+            syn::Item::Mod(module) => Ok(Item::Module(self.module_parser.parse(module, config)?)),
+            syn::Item::Struct(structure) => Ok(Item::TypeDefinition(self.structure_parser.parse(structure, config)?)),
+            syn::Item::Enum(enumeration) => Ok(Item::TypeDefinition(self.enumeration_parser.parse(enumeration, config)?)),
+            syn::Item::Type(type_) => Ok(Item::TypeDefinition(self.type_alias_parser.parse(type_, config)?)),
+            syn::Item::Fn(function) => Ok(Item::Function(self.function_parser.parse(function, config)?)),
+            syn::Item::Impl(implementation) => Ok(Item::Interface(self.interface_parser.parse(implementation, config)?)),
+            syn::Item::Const(constant) => Ok(Item::Object(self.object_parser.parse(constant, config)?)),
+            _ => Err(Error::Message("Only modules, structs, enums, type aliases, functions, impl blocks and constants are supported.".into()))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn items() -> Result<()> {
+        let item = ItemParser::new().parse(quote! { pub struct Structure; }, &Default::default())?;
+        assert_eq!(item.kind(), "type");
+        assert_eq!(*item.identifier(), "Structure");
+        let item = ItemParser::new().parse(quote! { impl Structure { pub fn new() -> Self { Self } } }, &Default::default())?;
+        assert_eq!(item.kind(), "interface");
+        assert!(ItemParser::new().parse(quote! { pub trait Trait {} }, &Default::default()).is_err());
+        Ok(())
+    }
+}
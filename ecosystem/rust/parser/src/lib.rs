@@ -11,6 +11,8 @@ pub mod identifier;
 pub mod module;
 pub mod object;
 pub mod interface;
+pub mod item;
+pub mod registration;
 
 pub mod parser;
 
@@ -1,11 +1,11 @@
-use ligen::ir::Literal;
-use ligen::parser::{Parser, ParserConfig};
+use ligen_ir::Literal;
+use ligen_parser::{Parser, ParserConfig};
 use crate::prelude::*;
 
 #[derive(Default)]
 pub struct LiteralParser;
 
-impl ligen::parser::universal::literal::LiteralParser for LiteralParser {}
+impl ligen_parser::universal::literal::LiteralParser for LiteralParser {}
 
 impl Parser<syn::Lit> for LiteralParser {
     type Output = Literal;
@@ -87,8 +87,8 @@ impl Parser<&str> for LiteralParser {
 mod test {
     use crate::literal::LiteralParser;
     use crate::prelude::*;
-    use ligen::ir::literal::mock;
-    use ligen::parser::assert::*;
+    use ligen_ir::literal::mock;
+    use ligen_parser::assert::*;
 
     #[test]
     fn literal_verbatim() -> Result<()> {
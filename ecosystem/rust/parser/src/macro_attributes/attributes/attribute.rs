@@ -2,14 +2,14 @@
 
 use crate::literal::LiteralParser;
 
-pub type AttributeParser = ligen::parser::universal::attributes::attribute::AttributeParser<LiteralParser>;
+pub type AttributeParser = ligen_parser::universal::attributes::attribute::AttributeParser<LiteralParser>;
 
 #[cfg(test)]
 mod test {
     use crate::prelude::*;
     use super::*;
-    use ligen::parser::assert::assert_eq;
-    use ligen::ir::attribute::mock;
+    use ligen_parser::assert::assert_eq;
+    use ligen_ir::attribute::mock;
 
     #[test]
     fn attribute_literal() -> Result<()> {
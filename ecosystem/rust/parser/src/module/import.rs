@@ -1,8 +1,8 @@
 //! Import representation.
 
 use crate::prelude::*;
-use ligen::ir::{Path, Attributes, Visibility, Import};
-use ligen::parser::{Parser, ParserConfig};
+use ligen_ir::{Path, Attributes, Visibility, Import};
+use ligen_parser::{Parser, ParserConfig};
 use crate::identifier::IdentifierParser;
 use crate::macro_attributes::attributes::AttributesParser;
 use crate::visibility::VisibilityParser;
@@ -103,8 +103,8 @@ impl Parser<ImportsBuilder> for ImportsParser {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use ligen::parser::assert::*;
-    use ligen::ir::module::import::mock;
+    use ligen_parser::assert::*;
+    use ligen_ir::module::import::mock;
 
     #[test]
     fn import() -> Result<()> {
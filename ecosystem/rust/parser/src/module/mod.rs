@@ -3,12 +3,13 @@
 mod import;
 
 use syn::spanned::Spanned;
-use ligen::ir::Object;
-use ligen::parser::{Parser, ParserConfig};
+use ligen_ir::Object;
+use ligen_parser::{parallel, DirectiveApplier, FilterReport, Filtered, ParseCache, Parser, ParserConfig, PathFilter};
+use serde::{Deserialize, Serialize};
 use crate::interface::InterfaceParser;
 use crate::prelude::*;
 use crate::types::type_alias::TypeAliasParser;
-use ligen::ir::{Function, Module, Import, TypeDefinition, Interface};
+use ligen_ir::{Function, Module, Import, TypeDefinition, Interface};
 use crate::object::ObjectParser;
 use crate::function::FunctionParser;
 use crate::identifier::IdentifierParser;
@@ -30,12 +31,20 @@ pub struct ModuleParser {
     enumeration_parser: EnumerationParser,
     structure_parser: StructureParser,
     imports_parser: ImportsParser,
+    filtered: FilterReport,
+    cache: Option<ParseCache>,
 }
 
 impl ModuleParser {
     pub fn new() -> Self {
         Default::default()
     }
+
+    /// Caches the modules parsed from each file in `cache`.
+    pub fn with_cache(mut self, cache: ParseCache) -> Self {
+        self.cache = Some(cache.for_parser("Rust", env!("CARGO_PKG_VERSION")));
+        self
+    }
 }
 
 impl Parser<&str> for ModuleParser {
@@ -56,50 +65,158 @@ impl Parser<proc_macro2::TokenStream> for ModuleParser {
     }
 }
 
+/// Parses the module and applies its `ligen(...)` directives.
 impl Parser<syn::ItemMod> for ModuleParser {
     type Output = Module;
     fn parse(&self, module: syn::ItemMod, config: &ParserConfig) -> Result<Self::Output> {
+        let mut module = self.parse_module(module, config)?;
+        DirectiveApplier::new().apply_module(&mut module);
+        Ok(module)
+    }
+}
+
+impl ModuleParser {
+    fn parse_module(&self, module: syn::ItemMod, config: &ParserConfig) -> Result<Module> {
         let items = module
             .content
             .map(|(_, items)| items)
             .ok_or("Module file isn't loaded.")?;
+        let identifier = self.identifier_parser.parse(module.ident, config)?;
         let attributes = self.attributes_parser.parse(module.attrs, config)?;
         let visibility = self.visibility_parser.parse(module.vis, config)?;
-        let identifier = self.identifier_parser.parse(module.ident, config)?;
-
-        let imports = self.extract_imports(items.as_slice(), config)?;
         let functions = self.extract_functions(items.as_slice(), config)?;
         let objects = self.extract_objects(items.as_slice(), config)?;
         let types = self.extract_types(items.as_slice(), config)?;
-        let interfaces = self.extract_interfaces(items.as_slice())?;
+        let interfaces = self.extract_interfaces(items.as_slice(), config)?;
+        if config.get_only_parse_symbols() {
+            let modules = self.extract_modules(items, config)?;
+            return Ok(Module { attributes, visibility, identifier, functions, objects, types, interfaces, modules, ..Default::default() });
+        }
+        let imports = self.extract_imports(items.as_slice(), config)?;
         let modules = self.extract_modules(items, config)?;
-        Ok(Self::Output { attributes, visibility, identifier, imports, functions, objects, types, interfaces, modules })
+        Ok(Module { attributes, visibility, identifier, imports, functions, objects, types, interfaces, modules })
     }
 }
 
 impl Parser<&std::path::Path> for ModuleParser {
     type Output = Module;
     fn parse(&self, path: &std::path::Path, config: &ParserConfig) -> Result<Self::Output> {
-        let module = syn2::file_parser::parse_file_recursive(path)?;
-        let ident = syn::Ident::new(path.file_stem().unwrap_or_default().to_str().unwrap_or_default(), module.span()); // FIXME: This is hardcoded.
-        let attrs = module.attrs;
+        let name = path.file_stem().unwrap_or_default().to_str().unwrap_or_default(); // FIXME: This is hardcoded.
+        self.parse_file(path, name, config)
+    }
+
+    fn filtered(&self) -> Vec<Filtered> {
+        self.filtered.to_vec()
+    }
+}
+
+impl ModuleParser {
+    /// Parses the module file at `path` and the files of its modules as the module `name`,
+    /// leaving out the modules and items filtered by the include and exclude patterns. The files
+    /// are parsed on `ligen::workers` threads.
+    pub fn parse_file(&self, path: &std::path::Path, name: &str, config: &ParserConfig) -> Result<Module> {
+        let filter = PathFilter::from_config(config);
+        parallel::with_workers(config, || {
+            let (mut module, mut filtered) = self.parse_file_tree(path, &ligen_ir::Path::from(name), &filter, config)?;
+            module.identifier = name.into();
+            DirectiveApplier::new().apply_module(&mut module);
+            filtered.extend(filter.apply_module(&mut module));
+            self.filtered.extend(filtered);
+            Ok(module)
+        })?
+    }
+
+    /// Parses the module file at `path`, whose module path is `module`, and the files of its
+    /// modules, without applying directives.
+    fn parse_file_tree(&self, path: &std::path::Path, module: &ligen_ir::Path, filter: &PathFilter, config: &ParserConfig) -> Result<(Module, Vec<Filtered>)> {
+        let content = std::fs::read_to_string(path)?;
+        let parse = || self.parse_source(&content, module, filter, config);
+        let ParsedFile { module: mut parsed, declarations, mut filtered } = match &self.cache {
+            // Excluded modules depend on the module's path, so it's part of the key.
+            Some(cache) => cache.get_or_parse(&cache.key(&[content.as_bytes(), module.to_string().as_bytes()], config), parse)?,
+            None => parse()?
+        };
+        let files = parsed
+            .modules
+            .iter()
+            .zip(&declarations)
+            .filter(|(_, declaration)| **declaration)
+            .map(|(sub_module, _)| syn2::file_parser::find_module_file(path, module, sub_module.identifier.name.as_str()))
+            .collect::<Result<Vec<_>>>()?;
+        // syn's items can't be sent between threads, so each file is parsed into a module by a
+        // worker and the modules are put in place of their declarations.
+        let sub_modules = parallel::map(files, |file| self.parse_file_tree(&file.file, &file.module, filter, config))
+            .into_iter()
+            .collect::<Result<Vec<_>>>()?;
+        let declared_modules = parsed
+            .modules
+            .iter_mut()
+            .zip(declarations)
+            .filter_map(|(sub_module, declaration)| declaration.then_some(sub_module));
+        for (declared, (sub_module, sub_filtered)) in declared_modules.zip(sub_modules) {
+            // The declaration's attributes come before the file's inner attributes.
+            declared.attributes.attributes.extend(sub_module.attributes.attributes);
+            declared.imports = sub_module.imports;
+            declared.functions = sub_module.functions;
+            declared.objects = sub_module.objects;
+            declared.types = sub_module.types;
+            declared.interfaces = sub_module.interfaces;
+            declared.modules = sub_module.modules;
+            filtered.extend(sub_filtered);
+        }
+        Ok((parsed, filtered))
+    }
+
+    /// Parses the source of the module file whose module path is `module`, leaving the modules
+    /// it declares with `mod module;` empty.
+    fn parse_source(&self, content: &str, module: &ligen_ir::Path, filter: &PathFilter, config: &ParserConfig) -> Result<ParsedFile> {
+        let mut filtered = Vec::new();
+        let mut file = syn2::file_parser::parse_source(content)?;
+        syn2::file_parser::retain_modules(module, &mut file.items, filter, &mut filtered);
+        let declarations = file
+            .items
+            .iter_mut()
+            .filter_map(|item| match item {
+                syn::Item::Mod(sub_module) => {
+                    let declaration = sub_module.content.is_none();
+                    sub_module.content.get_or_insert_with(Default::default);
+                    Some(declaration)
+                },
+                _ => None
+            })
+            .collect::<Vec<_>>();
+        // The identifier comes from the module declaration, or the library name for the root.
+        let ident = syn::Ident::new("module", file.span());
+        let attrs = file.attrs;
         let pub_token = Default::default();
         let semi = Default::default();
         let mod_token = Default::default();
-        let content = Some((Default::default(), module.items));
+        let content = Some((Default::default(), file.items));
         let vis = syn::Visibility::Public(pub_token);
         let unsafety = Default::default();
-        let module = syn::ItemMod { unsafety, attrs, vis, mod_token, ident, semi, content };
-        self.parse(module, config)
+        let item_mod = syn::ItemMod { unsafety, attrs, vis, mod_token, ident, semi, content };
+        let module = self.parse_module(item_mod, config)?;
+        Ok(ParsedFile { module, declarations, filtered })
     }
 }
 
+/// A module file parsed without the files of its modules.
+#[derive(Serialize, Deserialize)]
+struct ParsedFile {
+    /// The module, with the modules declared by `mod module;` left empty.
+    module: Module,
+    /// Whether each of its modules is declared by `mod module;`.
+    declarations: Vec<bool>,
+    /// The modules left out of it.
+    filtered: Vec<Filtered>
+}
+
 impl ModuleParser {
-    fn extract_interfaces(&self, items: &[syn::Item]) -> Result<Vec<Interface>> {
+    fn extract_interfaces(&self, items: &[syn::Item], config: &ParserConfig) -> Result<Vec<Interface>> {
         let mut interfaces = Vec::new();
         for item in items {
             if let syn::Item::Impl(impl_) = item {
-                if let Ok(interface) = self.interface_parser.parse(impl_.clone(), &ParserConfig::default()) {
+                if let Ok(interface) = self.interface_parser.parse(impl_.clone(), config) {
                     interfaces.push(interface);
                 }
             }
@@ -117,9 +234,8 @@ impl ModuleParser {
                 syn::Item::Type(type_) => {
                     types.push(self.type_alias_parser.parse(type_.clone(), config)?);
                 },
-                syn::Item::Union(_union) => {
-                    todo!("Union object isn't implemented yet.")
-                },
+                syn::Item::Union(_union) =>
+                    return Err(Error::Message("Unions aren't supported yet.".into())),
                 _ => ()
             }
         }
@@ -156,8 +272,9 @@ impl ModuleParser {
                     None
                 }
             });
+        // Directives are applied once the whole module tree is parsed.
         for module in items {
-            modules.push(self.parse(module, config)?)
+            modules.push(self.parse_module(module, config)?)
         }
         Ok(modules)
     }
@@ -179,8 +296,8 @@ impl ModuleParser {
 mod tests {
     use super::*;
     use quote::quote;
-    use ligen::ir::module::mock;
-    use ligen::parser::assert::*;
+    use ligen_ir::module::mock;
+    use ligen_parser::assert::*;
 
     #[test]
     fn module_file() -> Result<()> {
@@ -208,6 +325,144 @@ mod tests {
         })
     }
 
+    #[test]
+    fn module_directives() -> Result<()> {
+        let module = ModuleParser::default().parse(quote! {
+            pub mod directives {
+                #[ligen(rename = "Person")]
+                pub struct User {
+                    #[ligen(skip_field)]
+                    pub password: String,
+                    pub name: String
+                }
+
+                impl User {}
+
+                #[ligen(ignore)]
+                pub fn debug() {}
+            }
+        }, &Default::default())?;
+        assert!(module.functions.is_empty());
+        assert_eq!(module.types[0].identifier, "Person");
+        assert_eq!(module.interfaces[0].identifier, "Person");
+        let ligen_ir::KindDefinition::Structure(structure) = &module.types[0].definition else {
+            panic!("Not a structure.");
+        };
+        assert_eq!(structure.fields.len(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn excluded_modules() -> Result<()> {
+        let directory = std::env::temp_dir().join("ligen-rust-excluded-modules");
+        let _ = std::fs::remove_dir_all(&directory);
+        std::fs::create_dir_all(&directory)?;
+        // `tests.rs` doesn't exist, so parsing fails if the excluded module is read.
+        std::fs::write(directory.join("lib.rs"), "pub mod api;\npub mod tests;\npub fn debug() {}\n")?;
+        std::fs::write(directory.join("api.rs"), "pub fn api() {}\n")?;
+        let mut config = ParserConfig::default();
+        config.set_exclude(vec!["tests", "package::debug"]);
+        let parser = ModuleParser::default();
+        let module = parser.parse_file(&directory.join("lib.rs"), "package", &config)?;
+        assert!(module.functions.is_empty());
+        assert_eq!(module.modules.len(), 1);
+        assert_eq!(module.modules[0].functions.len(), 1);
+        assert_eq!(parser.filtered.to_vec().iter().map(|filtered| filtered.to_string()).collect::<Vec<_>>(), vec![
+            "package::tests: excluded by `tests`",
+            "package::debug: excluded by `package::debug`"
+        ]);
+        std::fs::remove_dir_all(&directory)?;
+        Ok(())
+    }
+
+    #[test]
+    fn module_files() -> Result<()> {
+        let directory = std::env::temp_dir().join("ligen-rust-module-files");
+        let _ = std::fs::remove_dir_all(&directory);
+        std::fs::create_dir_all(directory.join("b"))?;
+        std::fs::write(directory.join("lib.rs"), "pub mod b;\npub mod a;\npub mod inline { pub fn inline() {} }\npub mod c;\n")?;
+        std::fs::write(directory.join("a.rs"), "pub fn a() {}\n")?;
+        std::fs::write(directory.join("b").join("mod.rs"), "pub mod inner;\n")?;
+        std::fs::write(directory.join("b").join("inner.rs"), "pub fn inner() {}\n")?;
+        std::fs::write(directory.join("c.rs"), "pub struct C;\n")?;
+        let mut config = ParserConfig::default();
+        config.set_workers(2);
+        let module = ModuleParser::default().parse_file(&directory.join("lib.rs"), "package", &config)?;
+        let names = module.modules.iter().map(|module| module.identifier.name.as_str()).collect::<Vec<_>>();
+        assert_eq!(names, vec!["b", "a", "inline", "c"]);
+        assert_eq!(module.modules[0].modules[0].identifier, "inner");
+        assert_eq!(module.modules[0].modules[0].functions[0].identifier, "inner");
+        assert_eq!(module.modules[1].functions[0].identifier, "a");
+        assert_eq!(module.modules[2].functions[0].identifier, "inline");
+        assert_eq!(module.modules[3].types[0].identifier, "C");
+        std::fs::remove_dir_all(&directory)?;
+        Ok(())
+    }
+
+    #[test]
+    fn cached_files() -> Result<()> {
+        let directory = std::env::temp_dir().join("ligen-rust-cached-files");
+        let _ = std::fs::remove_dir_all(&directory);
+        std::fs::create_dir_all(&directory)?;
+        std::fs::write(directory.join("lib.rs"), "pub mod a;\npub fn root() {}\n")?;
+        std::fs::write(directory.join("a.rs"), "pub fn a() {}\n")?;
+        let parser = ModuleParser::new().with_cache(ParseCache::new(directory.join("cache")));
+        let config = ParserConfig::default();
+        let module = parser.parse_file(&directory.join("lib.rs"), "package", &config)?;
+        assert_eq!(std::fs::read_dir(directory.join("cache").join("rust"))?.count(), 2);
+        assert_eq!(parser.parse_file(&directory.join("lib.rs"), "package", &config)?, module);
+        std::fs::write(directory.join("a.rs"), "pub fn a() {}\npub fn b() {}\n")?;
+        let module = parser.parse_file(&directory.join("lib.rs"), "package", &config)?;
+        assert_eq!(module.modules[0].functions.len(), 2);
+        assert_eq!(module.functions[0].identifier, "root");
+        std::fs::remove_dir_all(&directory)?;
+        Ok(())
+    }
+
+    #[test]
+    fn only_parse_symbols() -> Result<()> {
+        let mut config = ParserConfig::default();
+        config.set_only_parse_symbols(true);
+        let module = ModuleParser::default().parse(quote! {
+            pub mod symbols {
+                use std::collections::HashMap;
+                pub struct Structure { pub map: HashMap<String, i32> }
+                pub enum Enumeration { A, B }
+                impl Structure {
+                    pub fn new(map: HashMap<String, i32>) -> Self { Self { map } }
+                    pub fn get(&self, key: &str) -> Option<i32> { self.map.get(key).copied() }
+                }
+                pub fn function(a: i32) -> i32 { a }
+                fn private() {}
+                #[ligen(ignore)]
+                pub fn ignored() {}
+                pub mod inner {
+                    pub fn inner() {}
+                }
+            }
+        }, &config)?;
+        assert_eq!(module.identifier, "symbols");
+        assert!(module.imports.is_empty());
+        assert_eq!(module.functions[0].identifier, "function");
+        assert!(module.functions[0].inputs.is_empty());
+        assert!(module.functions[0].output.is_none());
+        assert_eq!(module.functions[0].visibility, ligen_ir::Visibility::Public);
+        assert_eq!(module.functions[1].identifier, "private");
+        assert_eq!(module.functions[1].visibility, ligen_ir::Visibility::Private);
+        assert_eq!(module.functions.len(), 2);
+        let ligen_ir::KindDefinition::Structure(structure) = &module.types[0].definition else {
+            panic!("Not a structure.");
+        };
+        assert!(structure.fields.is_empty());
+        assert!(matches!(module.types[1].definition, ligen_ir::KindDefinition::Enumeration(_)));
+        assert_eq!(module.interfaces[0].identifier, "Structure");
+        assert_eq!(module.interfaces[0].functions[0].identifier, "new");
+        assert_eq!(module.interfaces[0].methods[0].identifier, "get");
+        assert!(module.interfaces[0].methods[0].inputs.is_empty());
+        assert_eq!(module.modules[0].functions[0].identifier, "inner");
+        Ok(())
+    }
+
     // TODO: Implement these:
     // #[test]
     // fn module_functions() -> Result<()> {
@@ -1,7 +1,8 @@
 //! Mutability enumeration.
 
 use crate::prelude::*;
-use ligen::{ir::Mutability, parser::ParserConfig};
+use ligen_ir::Mutability;
+use ligen_parser::ParserConfig;
 
 #[derive(Default)]
 pub struct MutabilityParser;
@@ -1,7 +1,8 @@
-use ligen::ir::{Object, Mutability};
-use ligen::parser::{Parser, ParserConfig};
+use ligen_ir::{Object, Mutability};
+use ligen_parser::{Parser, ParserConfig};
 use crate::identifier::IdentifierParser;
 use crate::literal::LiteralParser;
+use crate::macro_attributes::attributes::AttributesParser;
 use crate::prelude::*;
 use crate::types::TypeParser;
 
@@ -20,9 +21,13 @@ impl Parser<syn::ImplItemConst> for ObjectParser {
         if let syn::Expr::Lit(syn::ExprLit { lit, .. }) = item_const.expr {
             let mutability = Mutability::Constant;
             let identifier = IdentifierParser::new().parse(item_const.ident.clone(), config)?;
+            let attributes = AttributesParser::default().parse(item_const.attrs, config)?;
+            if config.get_only_parse_symbols() {
+                return Ok(Self::Output { attributes, mutability, identifier, ..Default::default() });
+            }
             let type_ = TypeParser::new().parse(item_const.ty, config)?;
             let literal = LiteralParser.parse(lit, config)?;
-            Ok(Self::Output { mutability, identifier, type_, literal })
+            Ok(Self::Output { attributes, mutability, identifier, type_, literal })
         } else {
             Err("Undefined Constant inside Impl block".into())
         }
@@ -35,9 +40,13 @@ impl Parser<syn::ItemConst> for ObjectParser {
         if let syn::Expr::Lit(syn::ExprLit { lit, .. }) = *item_const.expr {
             let mutability = Mutability::Constant;
             let identifier = IdentifierParser::new().parse(item_const.ident.clone(), config)?;
+            let attributes = AttributesParser::default().parse(item_const.attrs, config)?;
+            if config.get_only_parse_symbols() {
+                return Ok(Self::Output { attributes, mutability, identifier, ..Default::default() });
+            }
             let type_ = TypeParser::new().parse(*item_const.ty, config)?;
             let literal = LiteralParser.parse(lit, config)?;
-            Ok(Self::Output { mutability, identifier, type_, literal })
+            Ok(Self::Output { attributes, mutability, identifier, type_, literal })
         } else {
             Err("Undefined Constant".into())
         }
@@ -66,8 +75,8 @@ mod test {
     use crate::object::ObjectParser;
     use crate::prelude::*;
     
-    use ligen::parser::assert::assert_eq;
-    use ligen::ir::object::mock;
+    use ligen_parser::assert::assert_eq;
+    use ligen_ir::object::mock;
     
     #[test]
     fn constant() -> Result<()> {
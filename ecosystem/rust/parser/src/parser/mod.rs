@@ -1,6 +1,6 @@
-use ligen::prelude::*;
-use ligen::parser::{Parser, ParserConfig};
-use ligen::ir::Registry;
+use ligen_common::*;
+use ligen_parser::{Parser, ParserConfig};
+use ligen_ir::Registry;
 
 #[derive(Default)]
 pub struct RustParser {
@@ -1,5 +1,5 @@
-use ligen::ir::{Path, PathSegment};
-use ligen::parser::{Parser, ParserConfig};
+use ligen_ir::{Path, PathSegment};
+use ligen_parser::{Parser, ParserConfig};
 use crate::identifier::IdentifierParser;
 use crate::prelude::*;
 
@@ -64,8 +64,8 @@ mod test {
     use crate::path::PathParser;
     use crate::prelude::*;
 
-    use ligen::parser::assert::*;
-    use ligen::ir::path::mock;
+    use ligen_parser::assert::*;
+    use ligen_ir::path::mock;
 
     #[test]
     fn identifier_as_path() -> Result<()> {
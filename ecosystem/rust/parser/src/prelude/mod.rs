@@ -1,7 +1,7 @@
-pub use ligen::common::*;
+pub use ligen_common::*;
 pub use quote::TokenStreamExt;
 pub mod syn2;
 
 #[cfg(test)]
 pub use quote::quote;
-pub(crate) use ligen::parser::Parser;
\ No newline at end of file
+pub(crate) use ligen_parser::Parser;
\ No newline at end of file
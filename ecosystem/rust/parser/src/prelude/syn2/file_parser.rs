@@ -1,38 +1,46 @@
-use std::path::Path;
-use ligen::common::*;
+use std::path::{Path, PathBuf};
+use ligen_common::*;
+use ligen_parser::{Filtered, PathFilter};
 
-pub fn parse_file_recursive(path: &Path) -> Result<syn::File> {
-    let mut file = load_file(path)?;
-    load_modules(path, &mut file.items)?;
-    Ok(file)
+/// A `mod module;` declaration and the file it's loaded from.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModuleFile {
+    /// Path of the file.
+    pub file: PathBuf,
+    /// Path of the module.
+    pub module: ligen_ir::Path
 }
 
-fn load_file(path: &Path) -> Result<syn::File> {
-    let content = std::fs::read_to_string(path)?;
-    syn::parse_file(&content)
+/// Parses the source of a file, without loading its modules.
+pub fn parse_source(content: &str) -> Result<syn::File> {
+    syn::parse_file(content)
         .map_err(|e| Error::Message(format!("Failed to parse file {:?}.", e)))
 }
 
-fn load_modules(path: &Path, items: &mut [syn::Item]) -> Result<()> {
-    let path = path_handling::find_base_path(path)?;
-    for item in items {
-        if let syn::Item::Mod(module) = item {
-            load_module(&path, module)?;
-        }
-    }
-    Ok(())
+/// Removes the modules excluded by `filter` from the `items` of the module `module`, pushing them
+/// to `filtered`.
+pub fn retain_modules(module: &ligen_ir::Path, items: &mut Vec<syn::Item>, filter: &PathFilter, filtered: &mut Vec<Filtered>) {
+    items.retain(|item| match item {
+        syn::Item::Mod(sub_module) => {
+            let sub_module = module.clone().join(sub_module.ident.to_string());
+            match filter.check_module(&sub_module) {
+                Some(reason) => {
+                    filtered.push(Filtered { path: sub_module, reason });
+                    false
+                },
+                None => true
+            }
+        },
+        _ => true
+    });
 }
 
-fn load_module(path: &Path, module: &mut syn::ItemMod) -> Result<()> {
-    if module.content.is_none() {
-        let module_name = module.ident.to_string();
-        let module_path = path_handling::find_module_path(path, &module_name)?;
-        let mut file = load_file(&module_path)?;
-        load_modules(&module_path, &mut file.items)?;
-        module.attrs.extend(file.attrs);
-        module.content = Some((syn::token::Brace::default(), file.items));
-    }
-    Ok(())
+/// Finds the file of the module `name` declared in the file at `path`, whose module is `module`.
+pub fn find_module_file(path: &Path, module: &ligen_ir::Path, name: &str) -> Result<ModuleFile> {
+    let path = path_handling::find_base_path(path)?;
+    let file = path_handling::find_module_path(&path, name)?;
+    let module = module.clone().join(name);
+    Ok(ModuleFile { file, module })
 }
 
 mod path_handling {
@@ -0,0 +1,83 @@
+//! Registration of the items annotated with `#[ligen]`, so a build script can collect their IR.
+//!
+//! When `LIGEN_REGISTRATION_DIR` is set while a crate compiles, `#[ligen]` writes the IR of every
+//! item it annotates to `$LIGEN_REGISTRATION_DIR/<package>/`. The build script of a crate compiled
+//! afterwards, e.g. the one generating the bindings, collects them with [`collect`]. Entries of
+//! items that were since removed aren't deleted, so the directory should be cleared on clean builds.
+//!
+//! Cargo doesn't track `LIGEN_REGISTRATION_DIR`, so setting or changing it doesn't recompile
+//! anything and items already compiled aren't registered. Set it before a clean build. Failing to
+//! register an item is a warning rather than a compile error.
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use crate::item::Item;
+use crate::prelude::*;
+
+/// Environment variable naming the registration directory.
+pub const REGISTRATION_DIR: &str = "LIGEN_REGISTRATION_DIR";
+
+/// Registers `item` for the package being compiled, if `LIGEN_REGISTRATION_DIR` is set.
+pub fn register(item: &Item) -> Result<Option<PathBuf>> {
+    match std::env::var_os(REGISTRATION_DIR) {
+        Some(directory) => {
+            let package = std::env::var("CARGO_PKG_NAME")?;
+            register_in(directory, &package, item).map(Some)
+        },
+        None => Ok(None)
+    }
+}
+
+/// Registers `item` for `package` in `directory`, returning the path of its entry.
+pub fn register_in(directory: impl AsRef<Path>, package: &str, item: &Item) -> Result<PathBuf> {
+    let directory = directory.as_ref().join(package);
+    std::fs::create_dir_all(&directory)?;
+    let contents = serde_json::to_string_pretty(item)?;
+    // Implementations share the identifier of their type, so the contents tell them apart.
+    let mut hasher = DefaultHasher::new();
+    contents.hash(&mut hasher);
+    let path = directory.join(format!("{}.{}.{:016x}.json", item.kind(), item.identifier(), hasher.finish()));
+    std::fs::write(&path, contents)?;
+    Ok(path)
+}
+
+/// Collects the items registered for `package` in `directory`.
+pub fn collect(directory: impl AsRef<Path>, package: &str) -> Result<Vec<Item>> {
+    let directory = directory.as_ref().join(package);
+    if !directory.exists() {
+        return Ok(Default::default());
+    }
+    let mut paths = std::fs::read_dir(&directory)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<std::io::Result<Vec<_>>>()?;
+    paths.retain(|path| path.extension().is_some_and(|extension| extension == "json"));
+    paths.sort();
+    let mut items = Vec::new();
+    for path in paths {
+        items.push(serde_json::from_slice(&std::fs::read(path)?)?);
+    }
+    Ok(items)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ligen_parser::Parser;
+    use crate::item::ItemParser;
+
+    #[test]
+    fn registration() -> Result<()> {
+        let directory = std::env::temp_dir().join("ligen-registration-test");
+        let _ = std::fs::remove_dir_all(&directory);
+        let structure = ItemParser::new().parse(quote! { pub struct Structure; }, &Default::default())?;
+        let function = ItemParser::new().parse(quote! { pub fn function() {} }, &Default::default())?;
+        register_in(&directory, "package", &structure)?;
+        register_in(&directory, "package", &function)?;
+        register_in(&directory, "package", &function)?;
+        assert_eq!(collect(&directory, "package")?, vec![function, structure]);
+        assert!(collect(&directory, "other")?.is_empty());
+        std::fs::remove_dir_all(&directory)?;
+        Ok(())
+    }
+}
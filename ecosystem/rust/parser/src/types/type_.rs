@@ -1,8 +1,9 @@
-use ligen::{ir::Type, parser::ParserConfig};
+use ligen_ir::Type;
+use ligen_parser::ParserConfig;
 use quote::ToTokens;
 use syn::{TypeArray, TypeSlice};
 use crate::{literal::LiteralParser, mutability::MutabilityParser, prelude::*};
-use ligen::parser::Parser;
+use ligen_parser::Parser;
 use crate::path::PathParser;
 
 #[derive(Default)]
@@ -99,7 +100,7 @@ impl Parser<proc_macro2::TokenStream> for TypeParser {
 
 #[cfg(test)]
 mod test {
-    use ligen::parser::Parser;
+    use ligen_parser::Parser;
     use crate::types::type_::TypeParser;
     use crate::prelude::*;
     use super::*;
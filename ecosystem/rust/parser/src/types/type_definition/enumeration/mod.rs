@@ -4,8 +4,8 @@ pub mod variant;
 
 use crate::prelude::*;
 use crate::types::GenericsParser;
-use ligen::ir::{Enumeration, TypeDefinition};
-use ligen::parser::{Parser, ParserConfig};
+use ligen_ir::{Enumeration, TypeDefinition};
+use ligen_parser::{Parser, ParserConfig};
 use crate::identifier::IdentifierParser;
 use crate::macro_attributes::attributes::AttributesParser;
 use crate::types::type_definition::enumeration::variant::VariantParser;
@@ -23,9 +23,13 @@ impl EnumerationParser {
 impl Parser<syn::ItemEnum> for EnumerationParser {
     type Output = TypeDefinition;
     fn parse(&self, enumeration: syn::ItemEnum, config: &ParserConfig) -> Result<Self::Output> {
-        let attributes = AttributesParser::default().parse(enumeration.attrs, config)?;
         let identifier = IdentifierParser::new().parse(enumeration.ident, config)?;
+        let attributes = AttributesParser::default().parse(enumeration.attrs, config)?;
         let visibility = VisibilityParser::new().parse(enumeration.vis, config)?;
+        if config.get_only_parse_symbols() {
+            let definition = Enumeration::default().into();
+            return Ok(TypeDefinition { attributes, visibility, identifier, definition, ..Default::default() });
+        }
         let interfaces = Default::default();
         let variants = VariantParser.parse(enumeration.variants, config)?;
         let definition = Enumeration { variants }.into();
@@ -53,8 +57,8 @@ impl Parser<proc_macro2::TokenStream> for EnumerationParser {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use ligen::ir::enumeration::mock;
-    use ligen::parser::assert::*;
+    use ligen_ir::enumeration::mock;
+    use ligen_parser::assert::*;
 
     #[test]
     fn enumeration() -> Result<()> {
@@ -1,8 +1,8 @@
 //! Enumeration variant representation.
 
 use crate::prelude::*;
-use ligen::ir::Variant;
-use ligen::parser::{Parser, ParserConfig};
+use ligen_ir::Variant;
+use ligen_parser::{Parser, ParserConfig};
 use crate::identifier::IdentifierParser;
 use crate::macro_attributes::attributes::AttributesParser;
 
@@ -31,8 +31,8 @@ impl Parser<syn::punctuated::Punctuated<syn::Variant, syn::token::Comma>> for Va
 #[cfg(test)]
 mod tests {
     use syn::parse_quote;
-    use ligen::ir::Variant;
-    use ligen::parser::Parser;
+    use ligen_ir::Variant;
+    use ligen_parser::Parser;
     use crate::types::type_definition::enumeration::variant::VariantParser;
 
     #[test]
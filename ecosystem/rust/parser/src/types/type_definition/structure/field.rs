@@ -1,8 +1,8 @@
 //! Structure field representation.
 
 use crate::prelude::*;
-use ligen::ir::Field;
-use ligen::parser::{Parser, ParserConfig};
+use ligen_ir::Field;
+use ligen_parser::{Parser, ParserConfig};
 use crate::identifier::IdentifierParser;
 use crate::macro_attributes::attributes::AttributesParser;
 use crate::types::TypeParser;
@@ -35,7 +35,7 @@ impl Parser<syn::Fields> for FieldParser {
 #[cfg(test)]
 mod tests {
     use syn::parse_quote;
-    use ligen::ir::{Field, Visibility, Path};
+    use ligen_ir::{Field, Visibility, Path};
     use crate::types::structure::FieldParser;
     use crate::prelude::*;
 
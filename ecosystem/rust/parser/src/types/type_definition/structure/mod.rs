@@ -6,8 +6,8 @@ pub use field::*;
 
 use crate::prelude::*;
 use crate::types::GenericsParser;
-use ligen::ir::{Structure, TypeDefinition};
-use ligen::parser::{Parser, ParserConfig};
+use ligen_ir::{Structure, TypeDefinition};
+use ligen_parser::{Parser, ParserConfig};
 use crate::identifier::IdentifierParser;
 use crate::macro_attributes::attributes::AttributesParser;
 use crate::visibility::VisibilityParser;
@@ -40,9 +40,13 @@ impl Parser<proc_macro2::TokenStream> for StructureParser {
 impl Parser<syn::ItemStruct> for StructureParser {
     type Output = TypeDefinition;
     fn parse(&self, structure: syn::ItemStruct, config: &ParserConfig) -> Result<Self::Output> {
-        let attributes = AttributesParser::default().parse(structure.attrs, config)?;
         let identifier = IdentifierParser::new().parse(structure.ident, config)?;
+        let attributes = AttributesParser::default().parse(structure.attrs, config)?;
         let visibility = VisibilityParser::new().parse(structure.vis, config)?;
+        if config.get_only_parse_symbols() {
+            let definition = Structure::default().into();
+            return Ok(TypeDefinition { attributes, visibility, identifier, definition, ..Default::default() });
+        }
         let interfaces = Default::default();
         let fields = FieldParser.parse(structure.fields, config)?;
         let definition = Structure { fields }.into();
@@ -56,8 +60,8 @@ mod tests {
     use crate::types::structure::StructureParser;
     use crate::prelude::*;
 
-    use ligen::parser::assert::*;
-    use ligen::ir::structure::mock;
+    use ligen_parser::assert::*;
+    use ligen_ir::structure::mock;
 
     #[test]
     fn structure() -> Result<()> {
@@ -2,8 +2,8 @@
 
 use crate::prelude::*;
 use crate::types::{GenericsParser, TypeParser};
-use ligen::ir::{TypeAlias, TypeDefinition};
-use ligen::parser::{Parser, ParserConfig};
+use ligen_ir::{TypeAlias, TypeDefinition};
+use ligen_parser::{Parser, ParserConfig};
 use crate::identifier::IdentifierParser;
 use crate::macro_attributes::attributes::AttributesParser;
 use crate::visibility::VisibilityParser;
@@ -36,9 +36,13 @@ impl Parser<proc_macro2::TokenStream> for TypeAliasParser {
 impl Parser<syn::ItemType> for TypeAliasParser {
     type Output = TypeDefinition;
     fn parse(&self, type_alias: syn::ItemType, config: &ParserConfig) -> Result<Self::Output> {
-        let attributes = AttributesParser::default().parse(type_alias.attrs, config)?;
         let identifier = IdentifierParser::new().parse(type_alias.ident, config)?;
+        let attributes = AttributesParser::default().parse(type_alias.attrs, config)?;
         let visibility = VisibilityParser::new().parse(type_alias.vis, config)?;
+        if config.get_only_parse_symbols() {
+            let definition = TypeAlias::default().into();
+            return Ok(Self::Output { attributes, visibility, identifier, definition, ..Default::default() });
+        }
         let interfaces = Default::default();
         let type_ = TypeParser::default().parse(*type_alias.ty, config)?;
         let definition = TypeAlias { type_ }.into();
@@ -51,8 +55,8 @@ impl Parser<syn::ItemType> for TypeAliasParser {
 mod tests {
     use crate::prelude::*;
 
-    use ligen::parser::assert::*;
-    use ligen::ir::type_alias::mock;
+    use ligen_parser::assert::*;
+    use ligen_ir::type_alias::mock;
 
     use super::TypeAliasParser;
 
@@ -1,7 +1,8 @@
 //! Visibility enumeration.
 
 use crate::prelude::*;
-use ligen::{ir::Visibility, parser::ParserConfig};
+use ligen_ir::Visibility;
+use ligen_parser::ParserConfig;
 
 #[derive(Default)]
 pub struct VisibilityParser;
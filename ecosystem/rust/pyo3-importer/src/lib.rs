@@ -10,9 +10,15 @@ use std::path::PathBuf;
 use ligen::ir::{Library, Visitors};
 
 use ligen::generator::file_generator::{FileGenerator, FileSet, Template};
+use ligen::generator::GeneratorPlugin;
 use is_tree::{HasBranch, TreeIterator};
 
 
+/// The generator's plugin.
+pub fn plugin() -> GeneratorPlugin {
+    GeneratorPlugin::new("pyo3-importer", "Rust crate importing the library with PyO3.", LibraryGenerator::default)
+}
+
 #[derive(Default)]
 pub struct LibraryGenerator {
     pub module_generator: ModuleGenerator
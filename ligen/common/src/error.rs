@@ -12,7 +12,7 @@ pub enum Error {
     /// Misc errors.
     Message(String),
     /// Generic.
-    Generic(Box<dyn std::error::Error>)
+    Generic(Box<dyn std::error::Error + Send + Sync>)
 }
 
 impl From<&str> for Error {
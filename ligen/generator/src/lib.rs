@@ -1,4 +1,6 @@
 pub mod prelude;
 mod generator;
+mod plugin;
 
 pub use generator::*;
+pub use plugin::*;
\ No newline at end of file
@@ -0,0 +1,91 @@
+//! Generators listed by name, so tools can offer every generator available to them.
+//!
+//! Each generator crate provides a [`GeneratorPlugin`], e.g. `ligen_rust_pyo3_importer::plugin()`,
+//! and tools register the ones they link with in [`GeneratorPlugins`]. Third-party crates can
+//! provide their own the same way.
+
+use std::fmt::{Debug, Formatter};
+use std::sync::Arc;
+use ligen_ir::Library;
+use crate::prelude::*;
+use crate::Generator;
+
+/// A generator of bindings for a library.
+pub type DynGenerator = Box<dyn Generator<Input = Library>>;
+
+/// A generator and what it's about.
+#[derive(Clone)]
+pub struct GeneratorPlugin {
+    pub name: String,
+    pub description: String,
+    create: Arc<dyn Fn() -> DynGenerator + Send + Sync>
+}
+
+impl GeneratorPlugin {
+    /// A plugin creating its generator with `create`.
+    pub fn new<G>(name: impl Into<String>, description: impl Into<String>, create: impl Fn() -> G + Send + Sync + 'static) -> Self
+    where G: Generator<Input = Library> + 'static
+    {
+        let name = name.into();
+        let description = description.into();
+        let create = Arc::new(move || Box::new(create()) as DynGenerator);
+        Self { name, description, create }
+    }
+
+    /// Creates the generator.
+    pub fn create(&self) -> DynGenerator {
+        (self.create)()
+    }
+}
+
+impl Debug for GeneratorPlugin {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GeneratorPlugin")
+            .field("name", &self.name)
+            .field("description", &self.description)
+            .finish()
+    }
+}
+
+/// Generator plugins by name.
+#[derive(Debug, Clone, Default)]
+pub struct GeneratorPlugins {
+    plugins: Vec<GeneratorPlugin>
+}
+
+impl GeneratorPlugins {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Registers `plugin`, replacing the one with the same name.
+    pub fn register(&mut self, plugin: GeneratorPlugin) {
+        self.plugins.retain(|registered| !registered.name.eq_ignore_ascii_case(&plugin.name));
+        self.plugins.push(plugin);
+    }
+
+    pub fn with(mut self, plugin: GeneratorPlugin) -> Self {
+        self.register(plugin);
+        self
+    }
+
+    /// Gets the plugin named `name`, ignoring case.
+    pub fn get(&self, name: &str) -> Option<&GeneratorPlugin> {
+        self.plugins.iter().find(|plugin| plugin.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Creates the generator named `name`, ignoring case.
+    pub fn create(&self, name: &str) -> Result<DynGenerator> {
+        self.get(name)
+            .map(GeneratorPlugin::create)
+            .ok_or_else(|| Error::Message(format!("Generator \"{}\" not found. Expected one of: {}.", name, self.names().join(", "))))
+    }
+
+    pub fn names(&self) -> Vec<&str> {
+        self.plugins.iter().map(|plugin| plugin.name.as_str()).collect()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &GeneratorPlugin> {
+        self.plugins.iter()
+    }
+}
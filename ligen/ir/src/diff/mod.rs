@@ -0,0 +1,430 @@
+//! Semantic difference between two versions of a library's public surface.
+
+use std::collections::BTreeMap;
+use crate::prelude::*;
+use crate::{Function, Identifier, Interface, KindDefinition, Library, Method, Module, Object, Parameter, Path, TypeDefinition, Visibility};
+
+/// Kind of the item that changed.
+#[allow(missing_docs)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Display)]
+pub enum ItemKind {
+    #[display(fmt = "module")]
+    Module,
+    #[display(fmt = "type")]
+    Type,
+    #[display(fmt = "field")]
+    Field,
+    #[display(fmt = "variant")]
+    Variant,
+    #[display(fmt = "function")]
+    Function,
+    #[display(fmt = "method")]
+    Method,
+    #[display(fmt = "parameter")]
+    Parameter,
+    #[display(fmt = "object")]
+    Object
+}
+
+/// Property of an item present in both versions.
+#[allow(missing_docs)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Display)]
+pub enum Property {
+    /// Kind of a type definition, e.g. structure or enumeration.
+    #[display(fmt = "kind")]
+    Kind,
+    #[display(fmt = "generics")]
+    Generics,
+    #[display(fmt = "type")]
+    Type,
+    #[display(fmt = "output")]
+    Output,
+    #[display(fmt = "synchrony")]
+    Synchrony,
+    #[display(fmt = "mutability")]
+    Mutability,
+    /// Position of a parameter.
+    #[display(fmt = "position")]
+    Position,
+    /// Default value of a parameter.
+    #[display(fmt = "default value")]
+    Default,
+    /// Value of an object.
+    #[display(fmt = "value")]
+    Value
+}
+
+/// How an item changed.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ChangeKind {
+    /// The item was added. It's `optional` if existing uses don't need to mention it, e.g. a parameter with a default value.
+    Added {
+        /// Whether existing uses can omit the item.
+        optional: bool
+    },
+    /// The item was removed.
+    Removed,
+    /// A property of the item was modified. `None` means the property is absent, e.g. a function without output.
+    Modified {
+        /// Modified property.
+        property: Property,
+        /// Previous value.
+        before: Option<String>,
+        /// New value.
+        after: Option<String>
+    }
+}
+
+/// Semantic versioning compatibility of a change.
+#[allow(missing_docs)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Display)]
+pub enum Compatibility {
+    #[display(fmt = "non-breaking")]
+    NonBreaking,
+    #[display(fmt = "breaking")]
+    Breaking
+}
+
+/// A change to an item of the public surface.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Change {
+    /// Absolute path of the item, starting with the root module identifier.
+    pub path: Path,
+    /// Kind of the item.
+    pub item: ItemKind,
+    /// How the item changed.
+    pub kind: ChangeKind
+}
+
+impl Change {
+    /// Classifies the change in semver terms. Removals and signature changes break existing uses,
+    /// and so do new required parameters, fields and variants, which break exhaustive construction
+    /// and matching.
+    pub fn compatibility(&self) -> Compatibility {
+        let breaking = match &self.kind {
+            ChangeKind::Added { optional } => !optional && matches!(self.item, ItemKind::Parameter | ItemKind::Field | ItemKind::Variant),
+            ChangeKind::Removed => true,
+            ChangeKind::Modified { property, after, .. } => match property {
+                Property::Default => after.is_none(),
+                Property::Value => false,
+                _ => true
+            }
+        };
+        if breaking {
+            Compatibility::Breaking
+        } else {
+            Compatibility::NonBreaking
+        }
+    }
+
+    /// Check if the change is breaking.
+    pub fn is_breaking(&self) -> bool {
+        self.compatibility() == Compatibility::Breaking
+    }
+}
+
+impl std::fmt::Display for Change {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let describe = |value: &Option<String>| value.clone().unwrap_or_else(|| "none".into());
+        match &self.kind {
+            ChangeKind::Added { .. } => write!(f, "[{}] added {} {}", self.compatibility(), self.item, self.path),
+            ChangeKind::Removed => write!(f, "[{}] removed {} {}", self.compatibility(), self.item, self.path),
+            ChangeKind::Modified { property, before, after } => write!(f, "[{}] changed {} of {} {}: {} -> {}", self.compatibility(), property, self.item, self.path, describe(before), describe(after))
+        }
+    }
+}
+
+/// Changes between two versions of a library, keyed by path.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Diff {
+    /// Changes sorted by path.
+    pub changes: Vec<Change>
+}
+
+impl Diff {
+    /// Compares the public surfaces of `old` and `new`.
+    pub fn new(old: &Library, new: &Library) -> Self {
+        let old = Surface::new(old).items;
+        let mut new = Surface::new(new).items;
+        let mut changes = Vec::new();
+        for ((path, item), old) in old {
+            match new.remove(&(path.clone(), item)) {
+                Some(new) => {
+                    for ((property, before), (_, after)) in old.properties.into_iter().zip(new.properties) {
+                        if before != after {
+                            let kind = ChangeKind::Modified { property, before, after };
+                            changes.push(Change { path: path.clone(), item, kind });
+                        }
+                    }
+                },
+                None => changes.push(Change { path, item, kind: ChangeKind::Removed })
+            }
+        }
+        for ((path, item), new) in new {
+            let kind = ChangeKind::Added { optional: new.optional };
+            changes.push(Change { path, item, kind });
+        }
+        changes.sort_by(|a, b| (&a.path, a.item).cmp(&(&b.path, b.item)));
+        Self { changes }
+    }
+
+    /// Compares two libraries saved with `Library::save`.
+    pub fn load(old: impl AsRef<std::path::Path>, new: impl AsRef<std::path::Path>) -> Result<Self> {
+        Ok(Self::new(&Library::load(old)?, &Library::load(new)?))
+    }
+
+    /// Check if any change is breaking.
+    pub fn is_breaking(&self) -> bool {
+        self.changes.iter().any(Change::is_breaking)
+    }
+
+    /// The breaking changes.
+    pub fn breaking_changes(&self) -> impl Iterator<Item = &Change> {
+        self.changes.iter().filter(|change| change.is_breaking())
+    }
+}
+
+impl std::fmt::Display for Diff {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        for change in &self.changes {
+            writeln!(f, "{}", change)?;
+        }
+        Ok(())
+    }
+}
+
+/// Properties of an item, always listed in the same order for a given item kind.
+struct Entry {
+    optional: bool,
+    properties: Vec<(Property, Option<String>)>
+}
+
+impl Entry {
+    fn new(properties: Vec<(Property, Option<String>)>) -> Self {
+        let optional = false;
+        Self { optional, properties }
+    }
+}
+
+/// Public items of a library, keyed by path and kind. Private items and the items of private
+/// modules aren't part of the surface, so visibility changes show up as additions or removals.
+struct Surface {
+    items: BTreeMap<(Path, ItemKind), Entry>
+}
+
+impl Surface {
+    fn new(library: &Library) -> Self {
+        let mut surface = Self { items: Default::default() };
+        let root_module = &library.root_module;
+        surface.module(root_module, Path::from(root_module.identifier.clone()));
+        surface
+    }
+
+    fn insert(&mut self, path: Path, item: ItemKind, entry: Entry) {
+        self.items.insert((path, item), entry);
+    }
+
+    fn module(&mut self, module: &Module, path: Path) {
+        for type_ in module.types.iter().filter(|type_| type_.visibility == Visibility::Public) {
+            self.type_definition(type_, path.clone().join(type_.identifier.clone()));
+        }
+        for function in module.functions.iter().filter(|function| function.visibility == Visibility::Public) {
+            self.function(function, path.clone().join(function.identifier.clone()));
+        }
+        for object in &module.objects {
+            self.object(object, path.clone().join(object.identifier.clone()));
+        }
+        for interface in module.interfaces.iter().filter(|interface| interface.visibility == Visibility::Public) {
+            self.interface(interface, path.clone().join(interface.identifier.clone()));
+        }
+        for sub_module in module.modules.iter().filter(|module| module.visibility == Visibility::Public) {
+            let sub_module_path = path.clone().join(sub_module.identifier.clone());
+            self.insert(sub_module_path.clone(), ItemKind::Module, Entry::new(vec![]));
+            self.module(sub_module, sub_module_path);
+        }
+    }
+
+    fn type_definition(&mut self, type_definition: &TypeDefinition, path: Path) {
+        let mut properties = vec![
+            (Property::Kind, Some(type_definition.definition.kind_name().to_string())),
+            (Property::Generics, Some(type_definition.generics.to_string()))
+        ];
+        match &type_definition.definition {
+            KindDefinition::Structure(structure) => {
+                for (index, field) in structure.fields.iter().enumerate().filter(|(_, field)| field.visibility == Visibility::Public) {
+                    let identifier = field.identifier.clone().unwrap_or_else(|| Identifier::from(index.to_string().as_str()));
+                    let entry = Entry::new(vec![(Property::Type, Some(field.type_.to_string()))]);
+                    self.insert(path.clone().join(identifier), ItemKind::Field, entry);
+                }
+            },
+            KindDefinition::Enumeration(enumeration) => {
+                for variant in &enumeration.variants {
+                    self.insert(path.clone().join(variant.identifier.clone()), ItemKind::Variant, Entry::new(vec![]));
+                }
+            },
+            KindDefinition::TypeAlias(type_alias) => properties.push((Property::Type, Some(type_alias.type_.to_string())))
+        }
+        self.insert(path, ItemKind::Type, Entry::new(properties));
+    }
+
+    /// Interface members are keyed under the interface path, which usually is the path of the implementing type.
+    fn interface(&mut self, interface: &Interface, path: Path) {
+        for function in interface.functions.iter().filter(|function| function.visibility == Visibility::Public) {
+            self.function(function, path.clone().join(function.identifier.clone()));
+        }
+        for method in interface.methods.iter().filter(|method| method.visibility == Visibility::Public) {
+            self.method(method, path.clone().join(method.identifier.clone()));
+        }
+        for object in &interface.objects {
+            self.object(object, path.clone().join(object.identifier.clone()));
+        }
+    }
+
+    /// Overloads are keyed by their index under the function path.
+    fn function(&mut self, function: &Function, path: Path) {
+        self.parameters(&function.inputs, &path);
+        for (index, overload) in function.overloads.iter().enumerate() {
+            self.function(overload, path.clone().join(Identifier::from(index.to_string().as_str())));
+        }
+        let properties = vec![
+            (Property::Output, function.output.as_ref().map(ToString::to_string)),
+            (Property::Synchrony, Some(function.synchrony.to_string()))
+        ];
+        self.insert(path, ItemKind::Function, Entry::new(properties));
+    }
+
+    /// Overloads are keyed by their index under the method path.
+    fn method(&mut self, method: &Method, path: Path) {
+        self.parameters(&method.inputs, &path);
+        for (index, overload) in method.overloads.iter().enumerate() {
+            self.method(overload, path.clone().join(Identifier::from(index.to_string().as_str())));
+        }
+        let properties = vec![
+            (Property::Output, method.output.as_ref().map(ToString::to_string)),
+            (Property::Synchrony, Some(method.synchrony.to_string())),
+            (Property::Mutability, Some(format!("{:?}", method.mutability)))
+        ];
+        self.insert(path, ItemKind::Method, Entry::new(properties));
+    }
+
+    fn parameters(&mut self, parameters: &[Parameter], path: &Path) {
+        for (index, parameter) in parameters.iter().enumerate() {
+            let properties = vec![
+                (Property::Type, Some(parameter.type_.to_string())),
+                (Property::Position, Some(index.to_string())),
+                (Property::Default, parameter.default_value.as_ref().map(ToString::to_string))
+            ];
+            let optional = parameter.default_value.is_some();
+            let entry = Entry { optional, properties };
+            self.insert(path.clone().join(parameter.identifier.clone()), ItemKind::Parameter, entry);
+        }
+    }
+
+    fn object(&mut self, object: &Object, path: Path) {
+        let properties = vec![
+            (Property::Type, Some(object.type_.to_string())),
+            (Property::Mutability, Some(format!("{:?}", object.mutability))),
+            (Property::Value, Some(object.literal.to_string()))
+        ];
+        self.insert(path, ItemKind::Object, Entry::new(properties));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Structure, Field, Enumeration, Variant, Type, Literal, Synchrony};
+
+    fn library(functions: Vec<Function>, types: Vec<TypeDefinition>) -> Library {
+        let module = Module { identifier: "api".into(), functions, types, ..Default::default() };
+        Library {
+            identifier: "sdk".into(),
+            root_module: Module { identifier: "sdk".into(), modules: vec![module], ..Default::default() },
+            ..Default::default()
+        }
+    }
+
+    fn parameter(identifier: &str, type_: Type, default_value: Option<Literal>) -> Parameter {
+        Parameter { identifier: identifier.into(), type_, default_value, ..Default::default() }
+    }
+
+    fn changes(diff: &Diff) -> Vec<String> {
+        diff.changes.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn unchanged() {
+        let library = library(vec![Function { identifier: "login".into(), ..Default::default() }], vec![]);
+        let diff = Diff::new(&library, &library);
+        assert!(diff.changes.is_empty());
+        assert!(!diff.is_breaking());
+    }
+
+    #[test]
+    fn functions_and_parameters() {
+        let old = library(vec![
+            Function { identifier: "login".into(), inputs: vec![parameter("user", Type::string(), None)], ..Default::default() },
+            Function { identifier: "logout".into(), ..Default::default() }
+        ], vec![]);
+        let new = library(vec![
+            Function {
+                identifier: "login".into(),
+                inputs: vec![parameter("user", Type::string(), None), parameter("remember", Type::boolean(), Some(false.into()))],
+                synchrony: Synchrony::Asynchronous,
+                ..Default::default()
+            },
+            Function { identifier: "register".into(), ..Default::default() }
+        ], vec![]);
+        let diff = Diff::new(&old, &new);
+        assert_eq!(changes(&diff), vec![
+            "[breaking] changed synchrony of function sdk::api::login: Synchronous -> Asynchronous",
+            "[non-breaking] added parameter sdk::api::login::remember",
+            "[breaking] removed function sdk::api::logout",
+            "[non-breaking] added function sdk::api::register"
+        ]);
+        assert_eq!(diff.breaking_changes().count(), 2);
+    }
+
+    #[test]
+    fn types() {
+        let structure = |fields: Vec<Field>| TypeDefinition {
+            identifier: "User".into(),
+            definition: KindDefinition::Structure(Structure { fields }),
+            ..Default::default()
+        };
+        let field = |identifier: &str, type_: Type, visibility: Visibility| Field { identifier: Some(identifier.into()), type_, visibility, ..Default::default() };
+        let status = |variants: Vec<&str>| TypeDefinition {
+            identifier: "Status".into(),
+            definition: KindDefinition::Enumeration(Enumeration { variants: variants.into_iter().map(|identifier| Variant { identifier: identifier.into(), ..Default::default() }).collect() }),
+            ..Default::default()
+        };
+        let old = library(vec![], vec![
+            structure(vec![field("id", Type::i32(), Visibility::Public), field("token", Type::string(), Visibility::Private)]),
+            status(vec!["Active"])
+        ]);
+        let new = library(vec![], vec![
+            structure(vec![field("id", Type::i64(), Visibility::Public), field("token", Type::string(), Visibility::Public)]),
+            status(vec!["Active", "Banned"])
+        ]);
+        let diff = Diff::new(&old, &new);
+        assert_eq!(changes(&diff), vec![
+            "[breaking] added variant sdk::api::Status::Banned",
+            "[breaking] changed type of field sdk::api::User::id: I32 -> I64",
+            "[breaking] added field sdk::api::User::token"
+        ]);
+    }
+
+    #[test]
+    fn overloads() {
+        let overload = |type_: Type| Function { identifier: "convert".into(), inputs: vec![parameter("value", type_, None)], ..Default::default() };
+        let convert = |overloads: Vec<Function>| Function { overloads, ..overload(Type::i32()) };
+        let old = library(vec![convert(vec![overload(Type::string()), overload(Type::boolean())])], vec![]);
+        let new = library(vec![convert(vec![overload(Type::f64())])], vec![]);
+        let diff = Diff::new(&old, &new);
+        assert_eq!(changes(&diff), vec![
+            "[breaking] changed type of parameter sdk::api::convert::0::value: String -> F64",
+            "[breaking] removed function sdk::api::convert::1",
+            "[breaking] removed parameter sdk::api::convert::1::value"
+        ]);
+    }
+}
@@ -0,0 +1,122 @@
+//! Serialization formats of IR documents.
+
+use std::str::FromStr;
+use serde_json::Value;
+use crate::prelude::*;
+
+/// Format of an IR document. Every format encodes the same data model as JSON, so documents keep
+/// being migrated the same way regardless of their format.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, EnumIter, Display)]
+pub enum Format {
+    /// Default format, also used by `.lir` files.
+    #[default]
+    #[display(fmt = "json")]
+    Json,
+    /// Human editable.
+    #[display(fmt = "yaml")]
+    Yaml,
+    /// Human editable.
+    #[display(fmt = "ron")]
+    Ron,
+    /// Compact binary.
+    #[display(fmt = "msgpack")]
+    MessagePack,
+    /// Compact binary.
+    #[display(fmt = "cbor")]
+    Cbor
+}
+
+impl Format {
+    /// File extensions of the format.
+    pub fn extensions(&self) -> &'static [&'static str] {
+        match self {
+            Self::Json => &["json", "lir"],
+            Self::Yaml => &["yaml", "yml"],
+            Self::Ron => &["ron"],
+            Self::MessagePack => &["msgpack", "mpk"],
+            Self::Cbor => &["cbor"]
+        }
+    }
+
+    /// File extensions of every format.
+    pub fn all_extensions() -> Vec<&'static str> {
+        Self::iter()
+            .flat_map(|format| format.extensions().iter().copied())
+            .collect()
+    }
+
+    /// Gets the format of a file extension.
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        let extension = extension.to_lowercase();
+        Self::iter().find(|format| format.extensions().contains(&extension.as_str()))
+    }
+
+    /// Gets the format from the extension of `path`, defaulting to JSON.
+    pub fn from_path(path: impl AsRef<std::path::Path>) -> Self {
+        path
+            .as_ref()
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .and_then(Self::from_extension)
+            .unwrap_or_default()
+    }
+
+    /// Serializes `value` in this format.
+    pub fn serialize<T: Serialize>(&self, value: &T) -> Result<Vec<u8>> {
+        let bytes = match self {
+            // Serialized directly to keep the fields in declaration order.
+            Self::Json => serde_json::to_vec_pretty(value)?,
+            Self::Yaml => serde_yaml::to_string(&serde_json::to_value(value)?).map_err(generic)?.into_bytes(),
+            Self::Ron => ron::ser::to_string_pretty(&serde_json::to_value(value)?, Default::default()).map_err(generic)?.into_bytes(),
+            Self::MessagePack => rmp_serde::to_vec(&serde_json::to_value(value)?).map_err(generic)?,
+            Self::Cbor => {
+                let mut bytes = Vec::new();
+                ciborium::ser::into_writer(&serde_json::to_value(value)?, &mut bytes).map_err(generic)?;
+                bytes
+            }
+        };
+        Ok(bytes)
+    }
+
+    /// Deserializes `bytes` in this format.
+    pub fn deserialize(&self, bytes: &[u8]) -> Result<Value> {
+        let value = match self {
+            Self::Json => serde_json::from_slice(bytes)?,
+            Self::Yaml => serde_yaml::from_slice(bytes).map_err(generic)?,
+            Self::Ron => ron::de::from_bytes(bytes).map_err(generic)?,
+            Self::MessagePack => rmp_serde::from_slice(bytes).map_err(generic)?,
+            Self::Cbor => ciborium::de::from_reader(bytes).map_err(generic)?
+        };
+        Ok(value)
+    }
+}
+
+impl FromStr for Format {
+    type Err = String;
+    fn from_str(name: &str) -> std::result::Result<Self, Self::Err> {
+        Self::iter()
+            .find(|format| format.to_string().eq_ignore_ascii_case(name))
+            .or_else(|| Self::from_extension(name))
+            .ok_or_else(|| format!("Unknown IR format \"{}\". Expected one of: {}.", name, Self::all_extensions().join(", ")))
+    }
+}
+
+fn generic(error: impl std::error::Error + Send + Sync + 'static) -> Error {
+    Error::Generic(Box::new(error))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_selection() {
+        assert_eq!(Format::from_path("library.lir"), Format::Json);
+        assert_eq!(Format::from_path("library.YML"), Format::Yaml);
+        assert_eq!(Format::from_path("library.mpk"), Format::MessagePack);
+        assert_eq!(Format::from_path("library"), Format::Json);
+        assert_eq!("cbor".parse::<Format>(), Ok(Format::Cbor));
+        assert_eq!("msgpack".parse::<Format>(), Ok(Format::MessagePack));
+        assert!("xml".parse::<Format>().is_err());
+    }
+}
@@ -1,8 +1,9 @@
 use crate::prelude::*;
+use is_tree::IsTree;
 use crate::{Synchrony, Attributes, Mutability, Parameter, Type, Visibility, Identifier};
 
 /// Method structure.
-#[derive(Debug, Default, PartialEq, Clone, Serialize, Deserialize)]
+#[derive(Debug, Default, PartialEq, Clone, Serialize, Deserialize, JsonSchema, IsTree)]
 pub struct Method {
     /// Attributes field.
     pub attributes: Attributes,
@@ -13,11 +14,17 @@ pub struct Method {
     /// Synchrony field.
     pub synchrony: Synchrony,
     /// Method's identifier.
+    #[tree(path_segment)]
     pub identifier: Identifier,
     /// Inputs field.
+    #[tree(branch)]
     pub inputs: Vec<Parameter>,
     /// Output field.
+    #[tree(branch)]
     pub output: Option<Type>,
+    /// Alternative signatures of an overloaded method.
+    #[tree(branch)]
+    pub overloads: Vec<Method>,
 }
 
 impl CountSymbols for &Vec<Method> {
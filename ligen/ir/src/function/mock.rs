@@ -7,7 +7,8 @@ pub fn function() -> Function {
         synchrony: Synchrony::Synchronous,
         identifier: "test".into(),
         inputs: vec![],
-        output: None
+        output: None,
+        overloads: vec![]
     }
 }
 
@@ -31,7 +32,8 @@ pub fn function_input() -> Function {
                 default_value: Default::default()
             },
         ],
-        output: None
+        output: None,
+        overloads: vec![]
     }
 }
 
@@ -42,7 +44,8 @@ pub fn function_output() -> Function {
         synchrony: Synchrony::Synchronous,
         identifier: "test".into(),
         inputs: vec![],
-        output: Some(Type::string())
+        output: Some(Type::string()),
+        overloads: vec![]
     }
 }
 
@@ -64,7 +67,8 @@ pub fn function_input_output() -> Function {
                 .. Default::default()
             }
         ],
-        output: Some(Type::i32())
+        output: Some(Type::i32()),
+        overloads: vec![]
     }
 }
 
@@ -75,7 +79,8 @@ pub fn function_attribute() -> Function {
         synchrony: Synchrony::Synchronous,
         identifier: "test".into(),
         inputs: vec![],
-        output: None
+        output: None,
+        overloads: vec![]
     }
 }
 
@@ -86,7 +91,8 @@ pub fn function_async() -> Function {
         synchrony: Synchrony::Asynchronous,
         identifier: "test".into(),
         inputs: vec![],
-        output: None
+        output: None,
+        overloads: vec![]
     }
 }
 
@@ -116,6 +122,7 @@ pub fn function_complete() -> Function {
                 default_value: Default::default()
             },
         ],
-        output: Some(Type::constant_reference(Type::string()))
+        output: Some(Type::constant_reference(Type::string())),
+        overloads: vec![]
     }
 }
\ No newline at end of file
@@ -1,4 +1,5 @@
 use crate::prelude::*;
+use is_tree::IsTree;
 
 use crate::{Attributes, Identifier, Type, Visibility};
 
@@ -11,7 +12,7 @@ pub use method::*;
 pub use synchrony::*;
 
 /// Function structure.
-#[derive(Debug, Default, PartialEq, Clone, Serialize, Deserialize)]
+#[derive(Debug, Default, PartialEq, Clone, Serialize, Deserialize, JsonSchema, IsTree)]
 pub struct Function {
     /// Attributes field.
     pub attributes: Attributes,
@@ -20,11 +21,17 @@ pub struct Function {
     /// Synchrony field.
     pub synchrony: Synchrony,
     /// Function's identifier.
+    #[tree(path_segment)]
     pub identifier: Identifier,
     /// Inputs field.
+    #[tree(branch)]
     pub inputs: Vec<Parameter>,
     /// Output field.
+    #[tree(branch)]
     pub output: Option<Type>,
+    /// Alternative signatures of an overloaded function.
+    #[tree(branch)]
+    pub overloads: Vec<Function>,
 }
 
 impl CountSymbols for Vec<Function> {
@@ -48,6 +55,7 @@ impl From<Method> for Function {
             identifier: method.identifier,
             inputs: method.inputs,
             output: method.output,
+            overloads: method.overloads.into_iter().map(Function::from).collect(),
         }
     }
 }
@@ -4,18 +4,21 @@
 pub mod mock;
 
 use std::fmt::{Display, Formatter};
+use is_tree::IsTree;
 
 use crate::{prelude::*, Literal};
 use crate::{Identifier, Type, Attributes, Mutability};
 
-#[derive(Debug, Default, PartialEq, Clone, Serialize, Deserialize)]
+#[derive(Debug, Default, PartialEq, Clone, Serialize, Deserialize, JsonSchema, IsTree)]
 /// Parameter representation.
 pub struct Parameter {
     /// Attributes.
     pub attributes: Attributes,
     /// Identifier.
+    #[tree(path_segment)]
     pub identifier: Identifier,
     /// Type.
+    #[tree(branch)]
     pub type_: Type,
     /// Default value.
     pub default_value: Option<Literal>,
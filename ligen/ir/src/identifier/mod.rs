@@ -9,7 +9,7 @@ use crate::path::PathSegment;
 use crate::{prelude::*, Mutability};
 
 /// Identifier structure
-#[derive(Clone, Default, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Display, Serialize, Deserialize)]
+#[derive(Clone, Default, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Display, Serialize, Deserialize, JsonSchema)]
 #[display(fmt = "{}", name)]
 pub struct Identifier {
     /// Name field of Identifier
@@ -67,6 +67,11 @@ impl Identifier {
         "Dictionary".into()
     }
 
+    /// Returns a new `Identifier` representing a set type.
+    pub fn set() -> Self {
+        "Set".into()
+    }
+
     /// Returns a new `Identifier` representing a slice type.
     pub fn slice() -> Self {
         "Slice".into()
@@ -206,6 +211,11 @@ impl Identifier {
     pub fn self_() -> Self {
         "self".into()
     }
+
+    /// Returns a new `Identifier` marking paths defined outside of a library.
+    pub fn external() -> Self {
+        "external".into()
+    }
     
 }
 
@@ -3,7 +3,7 @@
 use crate::{prelude::*, Identifier};
 
 /// Enumerated naming conventions.
-#[derive(Debug, Clone, Serialize, Deserialize, Display, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Display, PartialEq, Eq)]
 pub enum NamingConvention {
     /// kebab-case.
     KebabCase,
@@ -21,6 +21,19 @@ pub enum NamingConvention {
     Unknown,
 }
 
+impl NamingConvention {
+    /// Get the naming convention written as itself, e.g. `snake_case` or `camelCase`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "kebab-case" => Some(Self::KebabCase),
+            "snake_case" => Some(Self::SnakeCase),
+            "PascalCase" => Some(Self::PascalCase),
+            "camelCase" => Some(Self::CamelCase),
+            _ => None
+        }
+    }
+}
+
 impl Identifier {
     /// Get the name convention of the Identifier.
     pub fn naming_convention(&self) -> NamingConvention {
@@ -95,6 +108,17 @@ impl Identifier {
         result.into()
     }
 
+    /// Set the name convention of the Identifier to `naming_convention`. Unknown keeps it as it is.
+    pub fn to_naming_convention(&self, naming_convention: &NamingConvention) -> Self {
+        match naming_convention {
+            NamingConvention::KebabCase => self.to_kebab_case(),
+            NamingConvention::SnakeCase => self.to_snake_case(),
+            NamingConvention::PascalCase => self.to_pascal_case(),
+            NamingConvention::CamelCase => self.to_camel_case(),
+            NamingConvention::Unknown => self.clone()
+        }
+    }
+
     /// Get the words of the Identifier.
     pub fn words(&self) -> Vec<&str> {
         match self.naming_convention() {
@@ -186,5 +210,8 @@ mod tests {
         assert_eq!(Identifier::from("camelCase").to_kebab_case(), Identifier::from("camel-case"));
         assert_eq!(Identifier::from("camelCase").to_snake_case(), Identifier::from("camel_case"));
         assert_eq!(Identifier::from("camelCase").to_pascal_case(), Identifier::from("CamelCase"));
+        assert_eq!(Identifier::from("camelCase").to_naming_convention(&NamingConvention::SnakeCase), Identifier::from("camel_case"));
+        assert_eq!(NamingConvention::from_name("PascalCase"), Some(NamingConvention::PascalCase));
+        assert_eq!(NamingConvention::from_name("Pascal"), None);
     }
 }
\ No newline at end of file
@@ -1,19 +1,24 @@
 use crate::{Attributes, Object, Function, Identifier, Method, Path, Visibility};
 use crate::prelude::*;
+use is_tree::IsTree;
 
-#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema, IsTree)]
 pub struct Interface {
     /// Interface attributes.
     pub attributes: Attributes,
     /// Interface visibility.
     pub visibility: Visibility,
     /// Interface identifier.
+    #[tree(path_segment)]
     pub identifier: Identifier,
     /// Interface objects.
+    #[tree(branch)]
     pub objects: Vec<Object>,
     /// Public functions.
+    #[tree(branch)]
     pub functions: Vec<Function>,
     /// Interface methods.
+    #[tree(branch)]
     pub methods: Vec<Method>,
     /// Interfaces that this interface extends.
     pub interfaces: Vec<Path>
@@ -14,6 +14,8 @@ pub use library::*;
 pub use mutability::*;
 pub use interface::*;
 pub use registry::*;
+pub use resolver::*;
+pub use format::*;
 
 pub use visitor::*;
 
@@ -33,6 +35,10 @@ pub mod mutability;
 pub mod source;
 pub mod library;
 pub mod registry;
+pub mod resolver;
+pub mod diff;
+pub mod format;
+pub mod schema;
 
 pub mod symbols;
 
@@ -0,0 +1,222 @@
+//! Versioned file format of the IR.
+
+use serde_json::{json, Value};
+use crate::prelude::*;
+use crate::{Library, Registry};
+
+/// Upgrades a serialized library from the schema version at its index to the next one.
+type Migration = fn(&mut Value) -> Result<()>;
+
+/// Migrations from every older schema version to the current one.
+const MIGRATIONS: &[Migration] = &[
+    migrate_v0_to_v1
+];
+
+/// Current IR schema version. Increment it by appending a migration whenever the IR changes in a way
+/// older documents can't be deserialized.
+pub const SCHEMA_VERSION: u64 = MIGRATIONS.len() as u64;
+
+/// Version of ligen producing documents.
+pub const LIGEN_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Envelope recording which IR schema and ligen version produced a library.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct Document<L = Library> {
+    /// IR schema version.
+    pub schema_version: u64,
+    /// Version of ligen that produced the document.
+    pub ligen_version: String,
+    /// The library.
+    pub library: L
+}
+
+impl<L> Document<L> {
+    /// Wraps `library` with the current versions.
+    pub fn new(library: L) -> Self {
+        let schema_version = SCHEMA_VERSION;
+        let ligen_version = LIGEN_VERSION.to_string();
+        Self { schema_version, ligen_version, library }
+    }
+}
+
+impl Document {
+    /// Reads a document of any older schema version, migrating it to the current one.
+    pub fn from_value(value: Value) -> Result<Self> {
+        let (version, ligen_version, mut library) = open_envelope(value, "library")?;
+        migrate(&mut library, version)?;
+        let library = serde_json::from_value(library)?;
+        Ok(Self { schema_version: SCHEMA_VERSION, ligen_version, library })
+    }
+
+    /// Reads a JSON document, migrating it to the current schema version.
+    pub fn from_json(json: &str) -> Result<Self> {
+        Self::from_value(serde_json::from_str(json)?)
+    }
+}
+
+impl<L: Serialize> Document<L> {
+    /// Writes the document as pretty printed JSON.
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+}
+
+/// Envelope recording which IR schema and ligen version produced a registry.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct RegistryDocument<R = Registry> {
+    /// IR schema version.
+    pub schema_version: u64,
+    /// Version of ligen that produced the document.
+    pub ligen_version: String,
+    /// The registry.
+    pub registry: R
+}
+
+impl<R> RegistryDocument<R> {
+    /// Wraps `registry` with the current versions.
+    pub fn new(registry: R) -> Self {
+        let schema_version = SCHEMA_VERSION;
+        let ligen_version = LIGEN_VERSION.to_string();
+        Self { schema_version, ligen_version, registry }
+    }
+}
+
+impl RegistryDocument {
+    /// Reads a document of any older schema version, migrating every library to the current one.
+    pub fn from_value(value: Value) -> Result<Self> {
+        let (version, ligen_version, mut registry) = open_envelope(value, "registry")?;
+        for library in elements(&mut registry, "libraries") {
+            migrate(library, version)?;
+        }
+        let registry = serde_json::from_value(registry)?;
+        Ok(Self { schema_version: SCHEMA_VERSION, ligen_version, registry })
+    }
+}
+
+/// Splits a document into its schema version, the version of ligen producing it and its contents
+/// at `key`. Documents without an envelope were produced before versioning, at schema version 0.
+fn open_envelope(value: Value, key: &str) -> Result<(u64, String, Value)> {
+    let mut object = match value {
+        Value::Object(object) if object.contains_key("schema_version") => object,
+        value => return Ok((0, "unknown".to_string(), value))
+    };
+    let version = object
+        .get("schema_version")
+        .and_then(Value::as_u64)
+        .ok_or("Invalid IR schema version.")?;
+    let ligen_version = object
+        .get("ligen_version")
+        .and_then(Value::as_str)
+        .unwrap_or("unknown")
+        .to_string();
+    if version > SCHEMA_VERSION {
+        return Err(Error::Message(format!(
+            "IR schema version {} (produced by ligen {}) is newer than the version {} supported by ligen {}. Please upgrade ligen.",
+            version, ligen_version, SCHEMA_VERSION, LIGEN_VERSION
+        )));
+    }
+    let contents = object
+        .remove(key)
+        .ok_or_else(|| format!("IR document doesn't have a {}.", key))?;
+    Ok((version, ligen_version, contents))
+}
+
+/// Migrates a serialized library from `version` to the current schema version.
+fn migrate(library: &mut Value, version: u64) -> Result<()> {
+    MIGRATIONS[version as usize..]
+        .iter()
+        .try_for_each(|migration| migration(library))
+}
+
+/// Sets `key` to `default` if the object doesn't have it yet.
+fn insert_default(value: &mut Value, key: &str, default: Value) {
+    if let Value::Object(object) = value {
+        object.entry(key).or_insert(default);
+    }
+}
+
+/// The elements of the array at `key`, if any.
+fn elements<'a>(value: &'a mut Value, key: &str) -> impl Iterator<Item = &'a mut Value> {
+    value
+        .get_mut(key)
+        .and_then(Value::as_array_mut)
+        .into_iter()
+        .flatten()
+}
+
+/// Adds the function and method `overloads` and the object `attributes`.
+fn migrate_v0_to_v1(library: &mut Value) -> Result<()> {
+    fn migrate_members(value: &mut Value) {
+        for key in ["functions", "methods"] {
+            for function in elements(value, key) {
+                insert_default(function, "overloads", json!([]));
+            }
+        }
+        for object in elements(value, "objects") {
+            insert_default(object, "attributes", json!({ "attributes": [] }));
+        }
+    }
+    fn migrate_module(module: &mut Value) {
+        migrate_members(module);
+        for interface in elements(module, "interfaces") {
+            migrate_members(interface);
+        }
+        for module in elements(module, "modules") {
+            migrate_module(module);
+        }
+    }
+    if let Some(root_module) = library.get_mut("root_module") {
+        migrate_module(root_module);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Function, Module, Object};
+
+    fn library() -> Library {
+        let root_module = Module {
+            identifier: "library".into(),
+            functions: vec![Function { identifier: "function".into(), ..Default::default() }],
+            objects: vec![Object { identifier: "OBJECT".into(), ..Default::default() }],
+            ..Default::default()
+        };
+        Library { identifier: "library".into(), root_module, ..Default::default() }
+    }
+
+    #[test]
+    fn round_trip() {
+        let library = library();
+        let json = Document::new(&library).to_json().expect("Failed to serialize.");
+        let document = Document::from_json(&json).expect("Failed to deserialize.");
+        assert_eq!(document.schema_version, SCHEMA_VERSION);
+        assert_eq!(document.ligen_version, LIGEN_VERSION);
+        assert_eq!(document.library, library);
+    }
+
+    #[test]
+    fn unversioned_document() {
+        let mut legacy = serde_json::to_value(library()).expect("Failed to serialize.");
+        let root_module = legacy.get_mut("root_module").expect("Missing root module.");
+        for function in elements(root_module, "functions") {
+            function.as_object_mut().expect("Function isn't an object.").remove("overloads");
+        }
+        for object in elements(root_module, "objects") {
+            object.as_object_mut().expect("Object isn't an object.").remove("attributes");
+        }
+        let document = Document::from_value(legacy).expect("Failed to migrate.");
+        assert_eq!(document.schema_version, SCHEMA_VERSION);
+        assert_eq!(document.ligen_version, "unknown");
+        assert_eq!(document.library, library());
+    }
+
+    #[test]
+    fn newer_document() {
+        let mut document = serde_json::to_value(Document::new(library())).expect("Failed to serialize.");
+        document["schema_version"] = json!(SCHEMA_VERSION + 1);
+        let error = Document::from_value(document).expect_err("Newer documents must be rejected.");
+        assert!(matches!(error, Error::Message(message) if message.contains("Please upgrade ligen")));
+    }
+}
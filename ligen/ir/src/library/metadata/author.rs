@@ -2,7 +2,7 @@ use std::fmt::Display;
 
 use crate::prelude::*;
 
-#[derive(Default, Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Default, Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
 pub struct Author {
     pub name: String,
     pub email: String,
@@ -10,7 +10,7 @@ pub use dependency::*;
 pub use author::*;
 pub use version::*;
 
-#[derive(Default, Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Default, Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
 pub struct Metadata {
     pub version: Version,
     pub language: Language,
@@ -1,16 +1,18 @@
 //! Library representation.
 
 pub mod metadata;
+pub mod document;
 use is_tree::IsTree;
 pub use metadata::*;
+pub use document::*;
 
-use crate::Identifier;
+use crate::{Format, Identifier};
 use crate::Module;
 use crate::prelude::*;
 
 /// Library representation.
 #[allow(missing_docs)]
-#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq, IsTree)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize, JsonSchema, PartialEq, IsTree)]
 pub struct Library {
     #[tree(path_segment)]
     pub identifier: Identifier,
@@ -20,16 +22,28 @@ pub struct Library {
 }
 
 impl Library {
-    /// Save library to file.
+    /// Save library to file, in a document recording the IR schema and ligen versions.
+    /// The format is chosen by the file extension, defaulting to JSON.
     pub fn save(&self, path: impl AsRef<std::path::Path>) -> Result<()> {
-        let contents = serde_json::to_string_pretty(self)?;
+        self.save_as(&path, Format::from_path(&path))
+    }
+
+    /// Save library to file in `format`.
+    pub fn save_as(&self, path: impl AsRef<std::path::Path>, format: Format) -> Result<()> {
+        let contents = format.serialize(&Document::new(self))?;
         std::fs::write(path, contents)?;
         Ok(())
     }
 
-    /// Load library from file.
+    /// Load library from file, migrating documents saved by older versions of ligen.
+    /// The format is chosen by the file extension, defaulting to JSON.
     pub fn load(path: impl AsRef<std::path::Path>) -> Result<Self> {
-        let json = std::fs::read_to_string(path)?;
-        Ok(serde_json::from_str(&json)?)
+        Self::load_as(&path, Format::from_path(&path))
+    }
+
+    /// Load library from a file in `format`.
+    pub fn load_as(path: impl AsRef<std::path::Path>, format: Format) -> Result<Self> {
+        let contents = std::fs::read(path)?;
+        Ok(Document::from_value(format.deserialize(&contents)?)?.library)
     }
 }
@@ -10,7 +10,7 @@ use crate::Path;
 use crate::Literal;
 use crate::prelude::*;
 
-#[derive(Shrinkwrap, Default, Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[derive(Shrinkwrap, Default, Debug, PartialEq, Clone, Serialize, Deserialize, JsonSchema)]
 #[shrinkwrap(mutable)]
 /// Attributes representation.
 pub struct Attributes {
@@ -95,6 +95,16 @@ impl Attributes {
     pub fn has_ignore_attribute(&self) -> bool {
         self.contains(&Attribute::Group(Group::new("ligen", Group::from("ignore"))))
     }
+
+    /// Check if the attributes list marks its field or object as read-only.
+    pub fn has_readonly_attribute(&self) -> bool {
+        self.contains(&Attribute::Group(Group::new("ligen", Group::from("readonly"))))
+    }
+
+    /// Check if the attributes list marks its type as inferred instead of declared.
+    pub fn has_inferred_attribute(&self) -> bool {
+        self.contains(&Attribute::Group(Group::new("ligen", Group::from("inferred"))))
+    }
 }
 
 impl From<Group> for Attributes {
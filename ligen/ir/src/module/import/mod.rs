@@ -4,10 +4,11 @@
 pub mod mock;
 
 use crate::prelude::*;
+use is_tree::HasPathSegment;
 use crate::{Path, Attributes, Visibility, Identifier};
 
 /// Import representation.
-#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct Import {
     /// Attributes.
     pub attributes: Attributes,
@@ -18,3 +19,15 @@ pub struct Import {
     /// Optional renaming.
     pub renaming: Option<Identifier>
 }
+
+/// Imports are identified by the name they bring into scope.
+impl HasPathSegment for Import {
+    fn path_segment(&self) -> String {
+        self
+            .renaming
+            .as_ref()
+            .or_else(|| self.path.segments.last().map(|segment| &segment.identifier))
+            .map(ToString::to_string)
+            .unwrap_or_default()
+    }
+}
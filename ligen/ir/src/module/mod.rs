@@ -13,7 +13,7 @@ use crate::{Visibility, Attributes, Function, Object, Identifier, TypeDefinition
 use crate::interface::Interface;
 
 /// Module representation.
-#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize, IsTree)]
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize, JsonSchema, IsTree)]
 pub struct Module {
     /// Attributes.
     pub attributes: Attributes,
@@ -23,14 +23,19 @@ pub struct Module {
     #[tree(path_segment)]
     pub identifier: Identifier,
     /// Imports.
+    #[tree(branch)]
     pub imports: Vec<Import>,
     /// Objects.
+    #[tree(branch)]
     pub objects: Vec<Object>,
     /// Functions.
+    #[tree(branch)]
     pub functions: Vec<Function>,
     /// Types.
+    #[tree(branch)]
     pub types: Vec<TypeDefinition>,
     /// Interfaces.
+    #[tree(branch)]
     pub interfaces: Vec<Interface>,
     /// Sub-modules.
     #[tree(branch)]
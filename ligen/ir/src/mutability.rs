@@ -3,7 +3,7 @@
 use crate::prelude::*;
 
 /// Mutability.
-#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize, EnumIter)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize, JsonSchema, EnumIter)]
 #[allow(missing_docs)]
 pub enum Mutability {
     Constant,
@@ -2,6 +2,7 @@ pub use crate::*;
 
 pub fn constant() -> Object {
     Object {
+        attributes: Default::default(),
         mutability: Mutability::Constant,
         identifier: "CONSTANT".into(),
         type_: Type::boolean(),
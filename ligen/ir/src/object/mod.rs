@@ -1,17 +1,22 @@
-use crate::{Identifier, Literal, Type, Mutability};
+use crate::{Attributes, Identifier, Literal, Type, Mutability};
 use crate::prelude::*;
+use is_tree::IsTree;
 
 #[cfg(any(test, feature = "mocks"))]
 pub mod mock;
 
-#[derive(Debug, Default, PartialEq, Clone, Serialize, Deserialize)]
+#[derive(Debug, Default, PartialEq, Clone, Serialize, Deserialize, JsonSchema, IsTree)]
 /// Object struct
 pub struct Object {
+    /// Object's attributes.
+    pub attributes: Attributes,
     /// Object's mutability.
     pub mutability: Mutability,
     /// Object's identifier.
+    #[tree(path_segment)]
     pub identifier: Identifier,
     /// Object's type.
+    #[tree(branch)]
     pub type_: Type,
     /// Object's literal value.
     pub literal: Literal,
@@ -3,5 +3,6 @@ mod traits;
 pub use ligen_common::*;
 pub use strum::{EnumIter, IntoEnumIterator};
 pub use enum_as_inner::EnumAsInner;
+pub use schemars::JsonSchema;
 
 pub use crate::prelude::traits::*;
\ No newline at end of file
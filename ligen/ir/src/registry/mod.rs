@@ -1,9 +1,9 @@
 use is_tree::IsTree;
 
-use crate::{prelude::*, Library};
+use crate::{prelude::*, Format, Library, RegistryDocument};
 
 #[allow(missing_docs)]
-#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq, IsTree)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize, JsonSchema, PartialEq, IsTree)]
 #[tree(branches)]
 pub struct Registry {
     pub libraries: Vec<Library>
@@ -13,4 +13,78 @@ impl Registry {
     pub fn new() -> Self {
         Self::default()
     }
-}
\ No newline at end of file
+
+    /// Save registry to file, in a document recording the IR schema and ligen versions.
+    /// The format is chosen by the file extension, defaulting to JSON.
+    pub fn save(&self, path: impl AsRef<std::path::Path>) -> Result<()> {
+        self.save_as(&path, Format::from_path(&path))
+    }
+
+    /// Save registry to file in `format`.
+    pub fn save_as(&self, path: impl AsRef<std::path::Path>, format: Format) -> Result<()> {
+        let contents = format.serialize(&RegistryDocument::new(self))?;
+        std::fs::write(path, contents)?;
+        Ok(())
+    }
+
+    /// Load registry from file, migrating documents saved by older versions of ligen.
+    /// The format is chosen by the file extension, defaulting to JSON.
+    pub fn load(path: impl AsRef<std::path::Path>) -> Result<Self> {
+        Self::load_as(&path, Format::from_path(&path))
+    }
+
+    /// Load registry from a file in `format`.
+    pub fn load_as(path: impl AsRef<std::path::Path>, format: Format) -> Result<Self> {
+        let contents = std::fs::read(path)?;
+        Ok(RegistryDocument::from_value(format.deserialize(&contents)?)?.registry)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Function, Interface, Literal, Module, Object, Type};
+    use crate::function::mock::function_complete;
+    use crate::macro_attributes::attributes::mock::parse_literals;
+    use crate::module::mock::{module_types, sub_modules};
+    use crate::types::type_definition::kind_definition::{enumeration, structure, type_alias};
+
+    fn registry() -> Registry {
+        let root_module = Module {
+            identifier: "library".into(),
+            attributes: parse_literals(),
+            functions: vec![function_complete()],
+            types: vec![structure::mock::structure(), enumeration::mock::enumeration(), type_alias::mock::type_alias()],
+            objects: vec![
+                Object { identifier: "RATIO".into(), type_: Type::f64(), literal: Literal::Float(3.5), ..Default::default() },
+                Object { identifier: "OFFSET".into(), type_: Type::i64(), literal: Literal::Integer(-2), ..Default::default() }
+            ],
+            interfaces: vec![Interface { identifier: "Structure".into(), functions: vec![Function { identifier: "new".into(), ..Default::default() }], ..Default::default() }],
+            modules: vec![module_types(), sub_modules()],
+            ..Default::default()
+        };
+        let mut library = Library { identifier: "library".into(), root_module, ..Default::default() };
+        library.metadata.summary = "A library with \"quotes\", unicode ✓ and\nnew lines.".into();
+        Registry { libraries: vec![library, Library { identifier: "dependency".into(), ..Default::default() }] }
+    }
+
+    #[test]
+    fn lossless_formats() {
+        let registry = registry();
+        let directory = std::env::temp_dir().join("ligen-ir-registry");
+        std::fs::create_dir_all(&directory).expect("Failed to create directory.");
+        for format in Format::iter() {
+            for extension in format.extensions() {
+                let path = directory.join(format!("registry.{}", extension));
+                registry.save(&path).expect("Failed to save registry.");
+                assert_eq!(Registry::load(&path).expect("Failed to load registry."), registry, "Registry changed in {}.", format);
+                let library = &registry.libraries[0];
+                library.save(&path).expect("Failed to save library.");
+                assert_eq!(&Library::load(&path).expect("Failed to load library."), library, "Library changed in {}.", format);
+            }
+            let path = directory.join("registry.data");
+            registry.save_as(&path, format).expect("Failed to save registry.");
+            assert_eq!(Registry::load_as(&path, format).expect("Failed to load registry."), registry);
+        }
+    }
+}
@@ -0,0 +1,359 @@
+//! Symbol resolution.
+
+use std::cell::RefCell;
+use std::collections::HashSet;
+use crate::prelude::*;
+use crate::{Field, Function, Identifier, Import, Interface, KindDefinition, Library, Method, Module, Object, Path, PathSegment, Registry, TypeDefinition, Variant};
+
+/// Maximum number of imports followed while resolving a path, guarding against import cycles.
+const MAX_DEPTH: usize = 32;
+
+/// An item a path can refer to.
+#[allow(missing_docs)]
+#[derive(Debug, Clone, Copy, PartialEq, EnumAsInner)]
+pub enum Item<'a> {
+    Module(&'a Module),
+    TypeDefinition(&'a TypeDefinition),
+    Function(&'a Function),
+    Interface(&'a Interface),
+    Object(&'a Object),
+    Method(&'a Method),
+    Field(&'a Field),
+    Variant(&'a Variant),
+    /// An import that couldn't be followed, e.g. because it points to an external library.
+    Import(&'a Import)
+}
+
+/// The item a path refers to.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Resolution<'a> {
+    /// Library where the item is defined.
+    pub library: &'a Library,
+    /// Absolute path of the item, starting with the library's root module identifier.
+    pub path: Path,
+    /// The item itself.
+    pub item: Item<'a>
+}
+
+impl Library {
+    /// Get the module at the absolute `module_path`, which starts with the root module identifier.
+    pub fn find_module(&self, module_path: &Path) -> Option<&Module> {
+        let (first, rest) = module_path.segments.split_first()?;
+        if first.identifier != self.root_module.identifier {
+            return None;
+        }
+        rest
+            .iter()
+            .try_fold(&self.root_module, |module, segment| {
+                module.modules.iter().find(|module| module.identifier == segment.identifier)
+            })
+    }
+
+    /// Resolve `path` as written in the module at the absolute `module_path`.
+    pub fn resolve(&self, path: &Path, module_path: &Path) -> Option<Resolution<'_>> {
+        Resolver::new(std::slice::from_ref(self)).resolve(self, path, module_path, false, 0)
+    }
+}
+
+impl Registry {
+    /// Resolve `path` as written in the module at the absolute `module_path` of `library`.
+    /// Paths starting with another library's identifier are resolved in that library.
+    pub fn resolve(&self, library: &Identifier, path: &Path, module_path: &Path) -> Option<Resolution<'_>> {
+        let library = self.libraries.iter().find(|candidate| candidate.identifier == *library)?;
+        Resolver::new(&self.libraries).resolve(library, path, module_path, false, 0)
+    }
+}
+
+struct Resolver<'a> {
+    libraries: &'a [Library],
+    visiting: RefCell<HashSet<(Identifier, Path, Path)>>
+}
+
+impl<'a> Resolver<'a> {
+    fn new(libraries: &'a [Library]) -> Self {
+        let visiting = Default::default();
+        Self { libraries, visiting }
+    }
+}
+
+impl<'a> Resolver<'a> {
+    /// Resolves `path` in the namespace of the module at `module_path`. `scoped` paths are
+    /// continuations of a qualified path, so they aren't looked up in other libraries.
+    fn resolve(&self, library: &'a Library, path: &Path, module_path: &Path, scoped: bool, depth: usize) -> Option<Resolution<'a>> {
+        if depth > MAX_DEPTH {
+            return None;
+        }
+        let Some((first, rest)) = path.segments.split_first() else {
+            let item = Item::Module(library.find_module(module_path)?);
+            return Some(Resolution { library, path: module_path.clone(), item });
+        };
+        let rest = Path { segments: rest.to_vec() };
+        let name = &first.identifier;
+        if *name == Identifier::self_() {
+            self.resolve(library, &rest, module_path, true, depth + 1)
+        } else if *name == Identifier::super_() {
+            let parent = module_path.clone().without_last();
+            if parent.is_empty() {
+                None
+            } else {
+                self.resolve(library, &rest, &parent, true, depth + 1)
+            }
+        } else if *name == Identifier::root() || name.name == "crate" {
+            self.resolve(library, &rest, &Path::from(library.root_module.identifier.clone()), true, depth + 1)
+        } else {
+            let module = library.find_module(module_path)?;
+            // Lookups already in progress are import cycles.
+            let key = (library.identifier.clone(), module_path.clone(), path.clone());
+            if !self.visiting.borrow_mut().insert(key.clone()) {
+                return None;
+            }
+            let resolution = self.member(library, module, module_path, name, &rest, depth)
+                .or_else(|| self.imported(library, module, module_path, name, &rest, depth))
+                .or_else(|| self.glob_imported(library, module, module_path, path, depth))
+                .or_else(|| {
+                    if scoped {
+                        None
+                    } else if *name == library.root_module.identifier {
+                        self.resolve(library, &rest, &Path::from(name.clone()), true, depth + 1)
+                    } else {
+                        self.external(name, &rest, depth)
+                    }
+                });
+            self.visiting.borrow_mut().remove(&key);
+            resolution
+        }
+    }
+
+    /// Items defined in `module`.
+    fn member(&self, library: &'a Library, module: &'a Module, module_path: &Path, name: &Identifier, rest: &Path, depth: usize) -> Option<Resolution<'a>> {
+        let path = module_path.clone().join(name.clone());
+        if let Some(sub_module) = module.modules.iter().find(|sub_module| sub_module.identifier == *name) {
+            return if rest.is_empty() {
+                Some(Resolution { library, path, item: Item::Module(sub_module) })
+            } else {
+                self.resolve(library, rest, &path, true, depth + 1)
+            };
+        }
+        // A type and its implementations share the identifier, so the type comes first and its
+        // members are looked up in its fields or variants and then in every implementation.
+        let type_definition = module.types.iter().find(|type_| type_.identifier == *name);
+        let mut interfaces = module.interfaces.iter().filter(|interface| interface.identifier == *name);
+        if !rest.is_empty() {
+            return type_definition
+                .and_then(|type_definition| self.type_member(library, type_definition, path.clone(), rest))
+                .or_else(|| interfaces.find_map(|interface| self.interface_member(library, interface, path.clone(), rest)));
+        }
+        type_definition
+            .map(Item::TypeDefinition)
+            .or_else(|| interfaces.next().map(Item::Interface))
+            .or_else(|| module.functions.iter().find(|function| function.identifier == *name).map(Item::Function))
+            .or_else(|| module.objects.iter().find(|object| object.identifier == *name).map(Item::Object))
+            .map(|item| Resolution { library, path, item })
+    }
+
+    /// Fields of a structure or variants of an enumeration.
+    fn type_member(&self, library: &'a Library, type_definition: &'a TypeDefinition, path: Path, rest: &Path) -> Option<Resolution<'a>> {
+        let [segment] = rest.segments.as_slice() else {
+            return None;
+        };
+        let name = &segment.identifier;
+        let item = match &type_definition.definition {
+            KindDefinition::Structure(structure) => structure
+                .fields
+                .iter()
+                .find(|field| field.identifier.as_ref() == Some(name))
+                .map(Item::Field),
+            KindDefinition::Enumeration(enumeration) => enumeration
+                .variants
+                .iter()
+                .find(|variant| variant.identifier == *name)
+                .map(Item::Variant),
+            KindDefinition::TypeAlias(_) => None
+        }?;
+        Some(Resolution { library, path: path.join(name.clone()), item })
+    }
+
+    fn interface_member(&self, library: &'a Library, interface: &'a Interface, path: Path, rest: &Path) -> Option<Resolution<'a>> {
+        match rest.segments.as_slice() {
+            [] => Some(Resolution { library, path, item: Item::Interface(interface) }),
+            [segment] => {
+                let name = &segment.identifier;
+                let path = path.join(name.clone());
+                interface
+                    .functions
+                    .iter()
+                    .find(|function| function.identifier == *name)
+                    .map(Item::Function)
+                    .or_else(|| interface.methods.iter().find(|method| method.identifier == *name).map(Item::Method))
+                    .or_else(|| interface.objects.iter().find(|object| object.identifier == *name).map(Item::Object))
+                    .map(|item| Resolution { library, path, item })
+            },
+            _ => None
+        }
+    }
+
+    /// Items brought into `module` by a named or renamed import.
+    fn imported(&self, library: &'a Library, module: &'a Module, module_path: &Path, name: &Identifier, rest: &Path, depth: usize) -> Option<Resolution<'a>> {
+        let import = module.imports.iter().find(|import| match &import.renaming {
+            Some(renaming) => renaming == name,
+            None => !import.path.is_empty() && import.path.last().identifier == *name
+        })?;
+        let target = import.path.clone().join(rest.clone());
+        self.resolve(library, &target, module_path, false, depth + 1)
+            .or_else(|| {
+                rest.is_empty().then(|| {
+                    let path = module_path.clone().join(name.clone());
+                    Resolution { library, path, item: Item::Import(import) }
+                })
+            })
+    }
+
+    /// Items brought into `module` by glob imports such as `use a::*` or `from a import *`.
+    fn glob_imported(&self, library: &'a Library, module: &'a Module, module_path: &Path, path: &Path, depth: usize) -> Option<Resolution<'a>> {
+        module
+            .imports
+            .iter()
+            .filter(|import| !import.path.is_empty() && import.path.last().identifier.name == "*")
+            .find_map(|import| {
+                let base = import.path.clone().without_last();
+                let base = self.resolve(library, &base, module_path, false, depth + 1)?;
+                base.item.as_module()?;
+                self.resolve(base.library, path, &base.path, true, depth + 1)
+            })
+    }
+
+    /// Items defined in other libraries of the registry.
+    fn external(&self, name: &Identifier, rest: &Path, depth: usize) -> Option<Resolution<'a>> {
+        let library = self
+            .libraries
+            .iter()
+            .find(|library| library.identifier == *name || library.root_module.identifier == *name)?;
+        let root = Path::from(PathSegment::from(library.root_module.identifier.clone()));
+        self.resolve(library, rest, &root, true, depth + 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Enumeration, Structure};
+
+    fn registry() -> Registry {
+        let models = Module {
+            identifier: "models".into(),
+            types: vec![
+                TypeDefinition {
+                    identifier: "User".into(),
+                    definition: Structure { fields: vec![Field { identifier: Some("email".into()), ..Default::default() }] }.into(),
+                    ..Default::default()
+                },
+                TypeDefinition {
+                    identifier: "Role".into(),
+                    definition: Enumeration { variants: vec![Variant { identifier: "Admin".into(), ..Default::default() }] }.into(),
+                    ..Default::default()
+                }
+            ],
+            // A class or a type with several impl blocks.
+            interfaces: vec![
+                Interface {
+                    identifier: "User".into(),
+                    functions: vec![Function { identifier: "new".into(), ..Default::default() }],
+                    ..Default::default()
+                },
+                Interface {
+                    identifier: "User".into(),
+                    methods: vec![Method { identifier: "name".into(), ..Default::default() }],
+                    ..Default::default()
+                }
+            ],
+            ..Default::default()
+        };
+        let api = Module {
+            identifier: "api".into(),
+            imports: vec![
+                Import { path: "super::models::User".into(), renaming: Some("Account".into()), ..Default::default() },
+                Import { path: "other::*".into(), ..Default::default() },
+                Import { path: "numpy::ndarray".into(), ..Default::default() }
+            ],
+            functions: vec![Function { identifier: "login".into(), ..Default::default() }],
+            ..Default::default()
+        };
+        let mylib = Library {
+            identifier: "mylib".into(),
+            root_module: Module { identifier: "mylib".into(), modules: vec![models, api], ..Default::default() },
+            ..Default::default()
+        };
+        let other = Library {
+            identifier: "other".into(),
+            root_module: Module {
+                identifier: "other".into(),
+                objects: vec![Object { identifier: "VERSION".into(), ..Default::default() }],
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        Registry { libraries: vec![mylib, other] }
+    }
+
+    fn resolve<'a>(registry: &'a Registry, path: &str, module_path: &str) -> Option<Resolution<'a>> {
+        registry.resolve(&"mylib".into(), &path.into(), &module_path.into())
+    }
+
+    #[test]
+    fn absolute_and_relative_paths() {
+        let registry = registry();
+        let user = resolve(&registry, "mylib::models::User", "mylib::api").expect("Failed to resolve.");
+        assert_eq!(user.path, Path::from("mylib::models::User"));
+        assert!(user.item.is_type_definition());
+        assert_eq!(resolve(&registry, "root::models::User", "mylib::api").map(|resolution| resolution.path), Some(user.path.clone()));
+        assert_eq!(resolve(&registry, "super::models::User", "mylib::api").map(|resolution| resolution.path), Some(user.path.clone()));
+        assert_eq!(resolve(&registry, "self::login", "mylib::api").map(|resolution| resolution.path), Some("mylib::api::login".into()));
+        assert!(resolve(&registry, "models", "mylib").expect("Failed to resolve.").item.is_module());
+        assert!(resolve(&registry, "models::Missing", "mylib").is_none());
+    }
+
+    #[test]
+    fn type_and_interfaces() {
+        let registry = registry();
+        let user = resolve(&registry, "mylib::models::User", "mylib").expect("Failed to resolve.");
+        assert!(user.item.is_type_definition());
+        let new = resolve(&registry, "models::User::new", "mylib").expect("Failed to resolve.");
+        assert_eq!(new.path, Path::from("mylib::models::User::new"));
+        assert!(new.item.is_function());
+        let name = resolve(&registry, "Account::name", "mylib::api").expect("Failed to resolve.");
+        assert_eq!(name.path, Path::from("mylib::models::User::name"));
+        assert!(name.item.is_method());
+        assert!(resolve(&registry, "models::User::missing", "mylib").is_none());
+    }
+
+    #[test]
+    fn fields_and_variants() {
+        let registry = registry();
+        let email = resolve(&registry, "models::User::email", "mylib").expect("Failed to resolve.");
+        assert_eq!(email.path, Path::from("mylib::models::User::email"));
+        assert!(email.item.is_field());
+        let admin = resolve(&registry, "super::models::Role::Admin", "mylib::api").expect("Failed to resolve.");
+        assert_eq!(admin.path, Path::from("mylib::models::Role::Admin"));
+        assert!(admin.item.is_variant());
+        assert!(resolve(&registry, "models::Role::Admin::missing", "mylib").is_none());
+    }
+
+    #[test]
+    fn imports() {
+        let registry = registry();
+        let account = resolve(&registry, "Account", "mylib::api").expect("Failed to resolve.");
+        assert_eq!(account.path, Path::from("mylib::models::User"));
+        let version = resolve(&registry, "VERSION", "mylib::api").expect("Failed to resolve.");
+        assert_eq!(version.library.identifier, Identifier::from("other"));
+        assert!(version.item.is_object());
+        let ndarray = resolve(&registry, "ndarray", "mylib::api").expect("Failed to resolve.");
+        assert!(ndarray.item.is_import());
+    }
+
+    #[test]
+    fn cross_library() {
+        let registry = registry();
+        let version = resolve(&registry, "other::VERSION", "mylib").expect("Failed to resolve.");
+        assert_eq!(version.path, Path::from("other::VERSION"));
+    }
+}
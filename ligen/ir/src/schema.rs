@@ -0,0 +1,92 @@
+//! JSON Schema of the IR, the contract for generators consuming IR documents in other languages.
+
+use schemars::schema::RootSchema;
+use schemars::schema_for;
+use crate::{Document, Library, Registry, RegistryDocument};
+
+/// Schema of a serialized `Library`.
+pub fn library() -> RootSchema {
+    schema_for!(Library)
+}
+
+/// Schema of a serialized `Registry`.
+pub fn registry() -> RootSchema {
+    schema_for!(Registry)
+}
+
+/// Schema of library files, as written by `Library::save`.
+pub fn document() -> RootSchema {
+    schema_for!(Document)
+}
+
+/// Schema of registry files, as written by `Registry::save`.
+pub fn registry_document() -> RootSchema {
+    schema_for!(RegistryDocument)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+    use jsonschema::JSONSchema;
+    use crate::{Function, Interface, Literal, Module, Object, Type, TypeDefinition};
+    use crate::macro_attributes::attributes::mock::{parse_expressions, parse_literals};
+    use crate::function::mock::{function_complete, function_input_output};
+    use crate::module::mock::{module_types, sub_modules};
+    use crate::module::import::mock::renamed_import;
+    use crate::types::type_definition::kind_definition::{enumeration, structure, type_alias};
+
+    fn library() -> Library {
+        let literals = [Literal::Integer(-2), Literal::UnsignedInteger(2), Literal::Float(3.5), Literal::Boolean(true), Literal::Character('A'), Literal::String("string".into()), Literal::Tuple(vec![Literal::Integer(1), Literal::String("a".into())]), Literal::Vector(vec![]), Literal::None];
+        let objects = literals
+            .into_iter()
+            .enumerate()
+            .map(|(index, literal)| Object { identifier: format!("OBJECT_{}", index).as_str().into(), type_: Type::opaque(), literal, ..Default::default() })
+            .collect();
+        let root_module = Module {
+            identifier: "library".into(),
+            attributes: parse_expressions(),
+            imports: renamed_import(),
+            functions: vec![function_complete(), function_input_output()],
+            types: vec![
+                structure::mock::structure(),
+                enumeration::mock::enumeration(),
+                type_alias::mock::type_alias(),
+                TypeDefinition { identifier: "Array".into(), attributes: parse_literals(), definition: type_alias::TypeAlias { type_: Type::array(Type::u8(), 4) }.into(), ..Default::default() }
+            ],
+            objects,
+            interfaces: vec![Interface { identifier: "Structure".into(), functions: vec![Function { identifier: "new".into(), ..Default::default() }], interfaces: vec!["Display".into()], ..Default::default() }],
+            modules: vec![module_types(), sub_modules()],
+            ..Default::default()
+        };
+        Library { identifier: "library".into(), root_module, ..Default::default() }
+    }
+
+    fn validate(schema: RootSchema, instance: Value) {
+        let schema = serde_json::to_value(schema).expect("Failed to serialize schema.");
+        let schema = JSONSchema::compile(&schema).expect("Invalid schema.");
+        if let Err(errors) = schema.validate(&instance) {
+            let errors = errors.map(|error| format!("{} at {}", error, error.instance_path)).collect::<Vec<_>>();
+            panic!("Instance doesn't match the schema:\n{}", errors.join("\n"));
+        }
+    }
+
+    #[test]
+    fn mock_libraries() {
+        let library = library();
+        validate(super::library(), serde_json::to_value(&library).expect("Failed to serialize library."));
+        validate(document(), serde_json::to_value(Document::new(&library)).expect("Failed to serialize document."));
+        let registry = Registry { libraries: vec![library, Default::default()] };
+        validate(super::registry(), serde_json::to_value(&registry).expect("Failed to serialize registry."));
+        validate(registry_document(), serde_json::to_value(RegistryDocument::new(&registry)).expect("Failed to serialize document."));
+    }
+
+    #[test]
+    fn invalid_library() {
+        let schema = serde_json::to_value(super::library()).expect("Failed to serialize schema.");
+        let schema = JSONSchema::compile(&schema).expect("Invalid schema.");
+        let mut instance = serde_json::to_value(library()).expect("Failed to serialize library.");
+        instance["root_module"]["objects"][0]["literal"] = serde_json::json!({ "unexpected": true });
+        assert!(!schema.is_valid(&instance));
+    }
+}
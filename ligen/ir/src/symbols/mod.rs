@@ -1,46 +1,454 @@
-use crate::{Library, Path, Module, interface};
+//! Symbol index.
 
-#[derive(Default)]
+use std::collections::HashMap;
+use crate::prelude::*;
+use crate::{Identifier, Interface, Item, KindDefinition, Library, Module, Path, Registry, Visibility};
+
+/// Kind of a symbol.
+#[allow(missing_docs)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Display, EnumIter)]
+pub enum SymbolKind {
+    #[display(fmt = "module")]
+    Module,
+    #[display(fmt = "import")]
+    Import,
+    #[display(fmt = "object")]
+    Object,
+    #[display(fmt = "function")]
+    Function,
+    #[display(fmt = "type")]
+    Type,
+    #[display(fmt = "field")]
+    Field,
+    #[display(fmt = "variant")]
+    Variant,
+    #[display(fmt = "interface")]
+    Interface,
+    #[display(fmt = "method")]
+    Method
+}
+
+/// Index of a symbol within its module.
+#[allow(missing_docs)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Member {
+    /// The module itself.
+    Module,
+    Import(usize),
+    Object(usize),
+    Function(usize),
+    Type(usize),
+    Field { type_: usize, field: usize },
+    Variant { type_: usize, variant: usize },
+    Interface(usize),
+    InterfaceObject { interface: usize, object: usize },
+    InterfaceFunction { interface: usize, function: usize },
+    Method { interface: usize, method: usize }
+}
+
+/// Position of a symbol in its library's tree.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Location {
+    /// Indices of the sub-modules leading from the root module to the symbol's module.
+    pub modules: Vec<usize>,
+    /// Index of the symbol within its module.
+    pub member: Member
+}
+
+impl Location {
+    /// Gets the item at this location, if `library` still has it.
+    pub fn get<'a>(&self, library: &'a Library) -> Option<Item<'a>> {
+        let module = self
+            .modules
+            .iter()
+            .try_fold(&library.root_module, |module, index| module.modules.get(*index))?;
+        let item = match self.member {
+            Member::Module => Item::Module(module),
+            Member::Import(index) => Item::Import(module.imports.get(index)?),
+            Member::Object(index) => Item::Object(module.objects.get(index)?),
+            Member::Function(index) => Item::Function(module.functions.get(index)?),
+            Member::Type(index) => Item::TypeDefinition(module.types.get(index)?),
+            Member::Field { type_, field } => match &module.types.get(type_)?.definition {
+                KindDefinition::Structure(structure) => Item::Field(structure.fields.get(field)?),
+                _ => return None
+            },
+            Member::Variant { type_, variant } => match &module.types.get(type_)?.definition {
+                KindDefinition::Enumeration(enumeration) => Item::Variant(enumeration.variants.get(variant)?),
+                _ => return None
+            },
+            Member::Interface(index) => Item::Interface(module.interfaces.get(index)?),
+            Member::InterfaceObject { interface, object } => Item::Object(module.interfaces.get(interface)?.objects.get(object)?),
+            Member::InterfaceFunction { interface, function } => Item::Function(module.interfaces.get(interface)?.functions.get(function)?),
+            Member::Method { interface, method } => Item::Method(module.interfaces.get(interface)?.methods.get(method)?)
+        };
+        Some(item)
+    }
+}
+
+/// An indexed symbol.
+#[derive(Debug, Clone, PartialEq, Display)]
+#[display(fmt = "{} {}", kind, path)]
+pub struct Symbol {
+    /// Absolute path, starting with the root module identifier.
+    pub path: Path,
+    /// Symbol kind.
+    pub kind: SymbolKind,
+    /// Visibility. Symbols without their own visibility, such as objects and variants, take their owner's.
+    pub visibility: Visibility,
+    /// Identifier of the library defining the symbol.
+    pub library: Identifier,
+    /// Back-reference into the library's tree.
+    pub location: Location
+}
+
+impl Symbol {
+    /// Unqualified name.
+    pub fn name(&self) -> &str {
+        self
+            .path
+            .segments
+            .last()
+            .map(|segment| segment.identifier.name.as_str())
+            .unwrap_or_default()
+    }
+
+    /// Gets the symbol's item from its library.
+    pub fn get<'a>(&self, library: &'a Library) -> Option<Item<'a>> {
+        self.location.get(library)
+    }
+}
+
+/// Symbol index of one or more libraries.
+#[derive(Debug, Default, Clone)]
 pub struct Symbols {
-    pub symbols: Vec<Path>,
+    /// Indexed symbols.
+    pub symbols: Vec<Symbol>,
+    by_path: HashMap<Path, Vec<usize>>,
+    by_name: HashMap<String, Vec<usize>>
 }
 
 impl Symbols {
+    /// Indexes `library`.
     pub fn new(library: &Library) -> Self {
-        let symbols = Self::from_module(&library.root_module, &Default::default());
-        Symbols { symbols }
+        let mut symbols = Self::default();
+        symbols.update_library(library);
+        symbols
+    }
+
+    /// Indexes every library of `registry`.
+    pub fn from_registry(registry: &Registry) -> Self {
+        let mut symbols = Self::default();
+        for library in &registry.libraries {
+            symbols.insert_library(library);
+        }
+        symbols.reindex();
+        symbols
     }
 
-    fn from_module(module: &Module, path: &Path) -> Vec<Path> {
-        let path = path.clone().join(module.identifier.clone());
-        let mut symbols = Vec::default();
-        module.objects.iter().for_each(|object| {
-            symbols.push(path.clone().join(object.identifier.clone()));
+    /// Indexes `library` again, replacing its previous symbols.
+    pub fn update_library(&mut self, library: &Library) {
+        self.symbols.retain(|symbol| symbol.library != library.identifier);
+        self.insert_library(library);
+        self.reindex();
+    }
+
+    /// Indexes the module at the absolute `module_path` of `library` again, replacing the previous
+    /// symbols of the module and its sub-modules. Returns `false` if the module doesn't exist.
+    pub fn update_module(&mut self, library: &Library, module_path: &Path) -> bool {
+        let Some((indices, module)) = Self::find_module(library, module_path) else {
+            return false;
+        };
+        self.symbols.retain(|symbol| symbol.library != library.identifier || !symbol.path.segments.starts_with(&module_path.segments));
+        let visibility = if indices.is_empty() { Visibility::Public } else { module.visibility };
+        let mut builder = Builder { library: &library.identifier, symbols: &mut self.symbols };
+        builder.module(module, module_path.clone().without_last(), indices, visibility);
+        self.reindex();
+        true
+    }
+
+    /// Removes the symbols of `library`.
+    pub fn remove_library(&mut self, library: &Identifier) {
+        self.symbols.retain(|symbol| symbol.library != *library);
+        self.reindex();
+    }
+
+    /// Symbols at `path`. A type and its interface, for instance, share the same path.
+    pub fn get(&self, path: &Path) -> impl Iterator<Item = &Symbol> {
+        self.indexed(self.by_path.get(path))
+    }
+
+    /// Symbols named `name`, in any module.
+    pub fn find(&self, name: &str) -> impl Iterator<Item = &Symbol> {
+        self.indexed(self.by_name.get(name))
+    }
+
+    /// Symbols fuzzily matching `query`, best matches first. Matches in the unqualified name rank
+    /// above matches spread across the path, and shorter names win ties. An empty query matches
+    /// every symbol.
+    pub fn search(&self, query: &str) -> Vec<&Symbol> {
+        let mut matches = self
+            .symbols
+            .iter()
+            .filter_map(|symbol| {
+                fuzzy_score(query, symbol.name())
+                    .map(|score| score * 2)
+                    .or_else(|| fuzzy_score(query, &symbol.path.to_string()))
+                    .map(|score| (score, symbol))
+            })
+            .collect::<Vec<_>>();
+        matches.sort_by(|(a_score, a), (b_score, b)| {
+            b_score
+                .cmp(a_score)
+                .then_with(|| a.name().len().cmp(&b.name().len()))
+                .then_with(|| a.path.segments.len().cmp(&b.path.segments.len()))
+                .then_with(|| a.path.cmp(&b.path))
         });
-        for type_ in module.types.iter() {
-            symbols.push(path.clone().join(type_.identifier.clone()));
+        matches
+            .into_iter()
+            .map(|(_, symbol)| symbol)
+            .collect()
+    }
+
+    /// Iterates over every symbol.
+    pub fn iter(&self) -> impl Iterator<Item = &Symbol> {
+        self.symbols.iter()
+    }
+
+    /// Number of symbols.
+    pub fn len(&self) -> usize {
+        self.symbols.len()
+    }
+
+    /// Returns `true` if there are no symbols.
+    pub fn is_empty(&self) -> bool {
+        self.symbols.is_empty()
+    }
+
+    fn indexed<'a>(&'a self, indices: Option<&'a Vec<usize>>) -> impl Iterator<Item = &'a Symbol> {
+        indices
+            .into_iter()
+            .flatten()
+            .map(|index| &self.symbols[*index])
+    }
+
+    fn insert_library(&mut self, library: &Library) {
+        let mut builder = Builder { library: &library.identifier, symbols: &mut self.symbols };
+        builder.module(&library.root_module, Path::default(), Vec::new(), Visibility::Public);
+    }
+
+    fn reindex(&mut self) {
+        self.by_path.clear();
+        self.by_name.clear();
+        for (index, symbol) in self.symbols.iter().enumerate() {
+            self.by_path.entry(symbol.path.clone()).or_default().push(index);
+            self.by_name.entry(symbol.name().to_string()).or_default().push(index);
         }
-        for module in module.modules.iter() {
-            symbols.append(&mut Self::from_module(module, &path));
+    }
+
+    /// Finds the module at the absolute `module_path` and the indices leading to it.
+    fn find_module<'a>(library: &'a Library, module_path: &Path) -> Option<(Vec<usize>, &'a Module)> {
+        let (first, rest) = module_path.segments.split_first()?;
+        if first.identifier != library.root_module.identifier {
+            return None;
         }
-        for interface in module.interfaces.iter() {
-            symbols.extend(Self::from_interface(interface, &path));
+        let mut indices = Vec::new();
+        let mut module = &library.root_module;
+        for segment in rest {
+            let index = module.modules.iter().position(|module| module.identifier == segment.identifier)?;
+            indices.push(index);
+            module = &module.modules[index];
         }
-        symbols
+        Some((indices, module))
     }
+}
 
-    fn from_interface(interface: &interface::Interface, path: &Path) -> Vec<Path> {
-        let path = path.clone().join(interface.identifier.clone());
-        let mut symbols = Vec::default();
-        interface.objects.iter().for_each(|object| {
-            symbols.push(path.clone().join(object.identifier.clone()));
-        });
-        interface.methods.iter().for_each(|method| {
-            symbols.push(path.clone().join(method.identifier.clone()));
-        });
-        interface.functions.iter().for_each(|function| {
-            symbols.push(path.clone().join(function.identifier.clone()));
-        });
-        symbols
+struct Builder<'a> {
+    library: &'a Identifier,
+    symbols: &'a mut Vec<Symbol>
+}
+
+impl<'a> Builder<'a> {
+    fn push(&mut self, path: Path, kind: SymbolKind, visibility: Visibility, modules: &[usize], member: Member) {
+        let library = self.library.clone();
+        let location = Location { modules: modules.to_vec(), member };
+        self.symbols.push(Symbol { path, kind, visibility, library, location });
+    }
+
+    /// Indexes `module`, whose parent is at `parent_path`.
+    fn module(&mut self, module: &Module, parent_path: Path, modules: Vec<usize>, visibility: Visibility) {
+        let path = parent_path.join(module.identifier.clone());
+        self.push(path.clone(), SymbolKind::Module, visibility, &modules, Member::Module);
+        for (index, import) in module.imports.iter().enumerate() {
+            let name = import.renaming.clone().or_else(|| import.path.segments.last().map(|segment| segment.identifier.clone()));
+            if let Some(name) = name {
+                self.push(path.clone().join(name), SymbolKind::Import, import.visibility, &modules, Member::Import(index));
+            }
+        }
+        for (index, object) in module.objects.iter().enumerate() {
+            self.push(path.clone().join(object.identifier.clone()), SymbolKind::Object, visibility, &modules, Member::Object(index));
+        }
+        for (index, function) in module.functions.iter().enumerate() {
+            self.push(path.clone().join(function.identifier.clone()), SymbolKind::Function, function.visibility, &modules, Member::Function(index));
+        }
+        for (type_index, type_) in module.types.iter().enumerate() {
+            let type_path = path.clone().join(type_.identifier.clone());
+            self.push(type_path.clone(), SymbolKind::Type, type_.visibility, &modules, Member::Type(type_index));
+            match &type_.definition {
+                KindDefinition::Structure(structure) => {
+                    for (index, field) in structure.fields.iter().enumerate() {
+                        let name = field.identifier.clone().unwrap_or_else(|| Identifier::from(index.to_string()));
+                        let member = Member::Field { type_: type_index, field: index };
+                        self.push(type_path.clone().join(name), SymbolKind::Field, field.visibility, &modules, member);
+                    }
+                },
+                KindDefinition::Enumeration(enumeration) => {
+                    for (index, variant) in enumeration.variants.iter().enumerate() {
+                        let member = Member::Variant { type_: type_index, variant: index };
+                        self.push(type_path.clone().join(variant.identifier.clone()), SymbolKind::Variant, type_.visibility, &modules, member);
+                    }
+                },
+                KindDefinition::TypeAlias(_) => {}
+            }
+        }
+        for (index, interface) in module.interfaces.iter().enumerate() {
+            self.interface(interface, index, &path, &modules);
+        }
+        for (index, sub_module) in module.modules.iter().enumerate() {
+            let mut sub_modules = modules.clone();
+            sub_modules.push(index);
+            self.module(sub_module, path.clone(), sub_modules, sub_module.visibility);
+        }
+    }
+
+    fn interface(&mut self, interface: &Interface, interface_index: usize, module_path: &Path, modules: &[usize]) {
+        let path = module_path.clone().join(interface.identifier.clone());
+        self.push(path.clone(), SymbolKind::Interface, interface.visibility, modules, Member::Interface(interface_index));
+        for (index, object) in interface.objects.iter().enumerate() {
+            let member = Member::InterfaceObject { interface: interface_index, object: index };
+            self.push(path.clone().join(object.identifier.clone()), SymbolKind::Object, interface.visibility, modules, member);
+        }
+        for (index, function) in interface.functions.iter().enumerate() {
+            let member = Member::InterfaceFunction { interface: interface_index, function: index };
+            self.push(path.clone().join(function.identifier.clone()), SymbolKind::Function, function.visibility, modules, member);
+        }
+        for (index, method) in interface.methods.iter().enumerate() {
+            let member = Member::Method { interface: interface_index, method: index };
+            self.push(path.clone().join(method.identifier.clone()), SymbolKind::Method, method.visibility, modules, member);
+        }
+    }
+}
+
+/// Scores `query` as a case insensitive subsequence of `text`, favoring consecutive characters
+/// and word starts. Returns `None` if `text` doesn't contain the query.
+fn fuzzy_score(query: &str, text: &str) -> Option<usize> {
+    let text = text.chars().collect::<Vec<_>>();
+    let mut score = 0;
+    let mut start = 0;
+    let mut previous = None;
+    for character in query.chars().filter(|character| !character.is_whitespace()) {
+        let index = (start..text.len()).find(|index| text[*index].to_lowercase().eq(character.to_lowercase()))?;
+        score += 1;
+        if index > 0 && previous == Some(index - 1) {
+            score += 4;
+        }
+        if index == 0 || !text[index - 1].is_alphanumeric() || (text[index].is_uppercase() && text[index - 1].is_lowercase()) {
+            score += 2;
+        }
+        previous = Some(index);
+        start = index + 1;
+    }
+    Some(score)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Enumeration, Function, Import, Method, Object, Structure, Field, TypeDefinition, Variant};
+
+    fn library() -> Library {
+        let models = Module {
+            identifier: "models".into(),
+            imports: vec![Import { path: "typing::Optional".into(), ..Default::default() }],
+            types: vec![
+                TypeDefinition {
+                    identifier: "User".into(),
+                    definition: Structure { fields: vec![Field { identifier: Some("name".into()), ..Default::default() }] }.into(),
+                    ..Default::default()
+                },
+                TypeDefinition {
+                    identifier: "Status".into(),
+                    visibility: Visibility::Private,
+                    definition: Enumeration { variants: vec![Variant { identifier: "Active".into(), ..Default::default() }] }.into(),
+                    ..Default::default()
+                }
+            ],
+            interfaces: vec![Interface {
+                identifier: "User".into(),
+                methods: vec![Method { identifier: "get_user_name".into(), ..Default::default() }],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let root_module = Module {
+            identifier: "library".into(),
+            functions: vec![Function { identifier: "create_user".into(), ..Default::default() }],
+            objects: vec![Object { identifier: "VERSION".into(), ..Default::default() }],
+            modules: vec![models],
+            ..Default::default()
+        };
+        Library { identifier: "library".into(), root_module, ..Default::default() }
+    }
+
+    fn kinds<'a>(symbols: impl Iterator<Item = &'a Symbol>) -> Vec<SymbolKind> {
+        symbols.map(|symbol| symbol.kind).collect()
+    }
+
+    #[test]
+    fn every_kind() {
+        let symbols = Symbols::new(&library());
+        let mut indexed = kinds(symbols.iter());
+        indexed.sort();
+        indexed.dedup();
+        assert_eq!(indexed, SymbolKind::iter().collect::<Vec<_>>());
+        let variant = symbols.get(&"library::models::Status::Active".into()).next().expect("Missing variant.");
+        assert_eq!(variant.visibility, Visibility::Private);
+        assert_eq!(variant.library, Identifier::from("library"));
+        assert_eq!(kinds(symbols.get(&"library::models::User".into())), vec![SymbolKind::Type, SymbolKind::Interface]);
+        assert_eq!(kinds(symbols.find("Optional")), vec![SymbolKind::Import]);
+    }
+
+    #[test]
+    fn back_references() {
+        let library = library();
+        let symbols = Symbols::new(&library);
+        let field = symbols.get(&"library::models::User::name".into()).next().expect("Missing field.");
+        assert_eq!(field.get(&library).and_then(|item| item.as_field().copied()).and_then(|field| field.identifier.clone()), Some("name".into()));
+        let method = symbols.find("get_user_name").next().expect("Missing method.");
+        assert!(method.get(&library).expect("Missing item.").is_method());
+    }
+
+    #[test]
+    fn fuzzy_search() {
+        let symbols = Symbols::new(&library());
+        let names = |query| symbols.search(query).into_iter().map(|symbol| symbol.name().to_string()).collect::<Vec<_>>();
+        assert_eq!(names("cruser"), vec!["create_user"]);
+        assert_eq!(names("GUN"), vec!["get_user_name"]);
+        assert_eq!(names("user").first().map(String::as_str), Some("User"));
+        assert_eq!(symbols.search("").len(), symbols.len());
+    }
+
+    #[test]
+    fn incremental_rebuild() {
+        let mut library = library();
+        let mut symbols = Symbols::new(&library);
+        let count = symbols.len();
+        library.root_module.modules[0].functions.push(Function { identifier: "delete_user".into(), ..Default::default() });
+        assert!(symbols.update_module(&library, &"library::models".into()));
+        assert_eq!(symbols.len(), count + 1);
+        assert_eq!(symbols.find("delete_user").count(), 1);
+        assert_eq!(symbols.find("create_user").count(), 1);
+        assert!(!symbols.update_module(&library, &"library::missing".into()));
+        symbols.remove_library(&library.identifier);
+        assert!(symbols.is_empty());
     }
 }
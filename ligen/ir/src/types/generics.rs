@@ -2,7 +2,7 @@ use crate::Type;
 use crate::prelude::*;
 
 /// Generic arguments list.
-#[derive(Default, Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[derive(Default, Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize, JsonSchema)]
 pub struct Generics {
     /// Generic types.
     pub types: Vec<Type>
@@ -40,10 +40,3 @@ impl std::fmt::Display for Generics {
         }
     }
 }
-
-// FIXME: Remove this.
-// impl IntoIterTypeMut<Type> for Generics {
-//     fn type_iterator(&mut self) -> TypeIterMut<'_, Type> {
-//         self.types.iter_mut().collect::<Vec<_>>().into()
-//     }
-// }
\ No newline at end of file
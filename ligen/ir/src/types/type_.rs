@@ -1,7 +1,8 @@
 use crate::{Path, Identifier, PathSegment, Mutability};
 use crate::prelude::*;
+use is_tree::{HasBranches, HasPathSegment};
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize, JsonSchema)]
 /// Type structure.
 pub struct Type {
     /// Type path.
@@ -45,6 +46,11 @@ impl Type {
         Path::from(PathSegment::new(Identifier::vector(), type_.into())).into()
     }
 
+    /// Returns a new `Type` representing a set type.
+    pub fn set(type_: impl Into<Type>) -> Self {
+        Path::from(PathSegment::new(Identifier::set(), type_.into())).into()
+    }
+
     /// Returns a new `Type`representing an union type.
     pub fn union(types: Vec<Type>) -> Self {
         Path::from(PathSegment::new(Identifier::union(), types)).into()
@@ -172,7 +178,23 @@ impl Default for Type {
     }
 }
 
+/// Types every language maps to, which aren't defined in any library.
+pub const BUILTIN_TYPES: &[&str] = &[
+    "MutableReference", "Reference", "Union", "Variadic", "Tuple", "Dictionary", "Set", "Slice", "Array",
+    "Vector", "DateTime", "Option", "Opaque", "Boolean", "Character", "String",
+    "I8", "I16", "I32", "I64", "I128", "ISize", "U8", "U16", "U32", "U64", "U128", "USize",
+    "F16", "F32", "F64", "F128", "Self"
+];
+
 impl Type {
+    /// Check if the `Type` is one of the `BUILTIN_TYPES`.
+    pub fn is_builtin(&self) -> bool {
+        match self.path.segments.as_slice() {
+            [segment] => BUILTIN_TYPES.contains(&segment.identifier.name.as_str()),
+            _ => false
+        }
+    }
+
     /// Check if the `Type` is `Primitive`.
     pub fn is_primitive(&self) -> bool {
         self.is_boolean()
@@ -267,3 +289,30 @@ impl std::fmt::Display for Type {
         f.write_str(&self.path.to_string())
     }
 }
+
+impl HasPathSegment for Type {
+    fn path_segment(&self) -> String {
+        self.to_string()
+    }
+}
+
+/// The generic arguments of every path segment.
+impl<'a> HasBranches<&'a Type> for &'a Type {
+    fn branches_impl(self) -> impl Iterator<Item = &'a Type> {
+        self
+            .path
+            .segments
+            .iter()
+            .flat_map(|segment| segment.generics.types.iter())
+    }
+}
+
+impl<'a> HasBranches<&'a mut Type> for &'a mut Type {
+    fn branches_impl(self) -> impl Iterator<Item = &'a mut Type> {
+        self
+            .path
+            .segments
+            .iter_mut()
+            .flat_map(|segment| segment.generics.types.iter_mut())
+    }
+}
@@ -9,7 +9,7 @@ pub use variant::*;
 use crate::prelude::*;
 
 /// Enumeration representation.
-#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct Enumeration {
     /// Variants field.
     pub variants: Vec<Variant>,
@@ -1,13 +1,15 @@
 //! Enumeration variant representation.
 
 use crate::prelude::*;
+use is_tree::IsTree;
 use crate::{Attributes, Identifier};
 
 /// Enumeration representation.
-#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize, JsonSchema, IsTree)]
 pub struct Variant {
     /// Attributes field.
     pub attributes: Attributes,
     /// Variant identifier.
+    #[tree(path_segment)]
     pub identifier: Identifier
 }
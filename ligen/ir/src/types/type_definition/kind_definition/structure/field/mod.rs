@@ -1,10 +1,11 @@
 //! Structure field representation.
 
 use crate::prelude::*;
+use is_tree::{HasPathSegment, IsTree};
 use crate::{Identifier, Type, Visibility, Attributes};
 
 /// Property representation.
-#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize, JsonSchema, IsTree)]
 pub struct Field {
     /// Field attributes.
     pub attributes: Attributes,
@@ -13,12 +14,17 @@ pub struct Field {
     /// Field identifier.
     pub identifier: Option<Identifier>,
     /// Field type.
+    #[tree(branch)]
     pub type_: Type
 }
 
-// FIXME: Remove this
-// impl IntoIterTypeMut<Type> for Field {
-//     fn type_iterator(&mut self) -> TypeIterMut<'_, Type> {
-//         self.type_.type_iterator()
-//     }
-// }
\ No newline at end of file
+/// Unnamed fields have an empty path segment.
+impl HasPathSegment for Field {
+    fn path_segment(&self) -> String {
+        self
+            .identifier
+            .as_ref()
+            .map(ToString::to_string)
+            .unwrap_or_default()
+    }
+}
\ No newline at end of file
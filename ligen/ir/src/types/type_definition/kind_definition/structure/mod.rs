@@ -9,7 +9,7 @@ pub use field::*;
 use crate::prelude::*;
 
 /// Structure representation.
-#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct Structure {
     /// Items field.
     pub fields: Vec<Field>,
@@ -5,16 +5,18 @@ pub mod kind_definition;
 pub use kind_definition::*;
 
 use crate::{prelude::*, Attributes, Visibility, Path, Generics};
-use crate::Identifier;
+use crate::{Identifier, Type};
+use is_tree::{HasBranches, IsTree};
 
 /// All the possible ways to define a type.
-#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema, IsTree)]
 pub struct TypeDefinition {
     /// Definition attributes.
     pub attributes: Attributes,
     /// Definition visibility.
     pub visibility: Visibility,
     /// Definition identifier.
+    #[tree(path_segment)]
     pub identifier: Identifier,
     /// Generic parameters.
     pub generics: Generics,
@@ -24,6 +26,69 @@ pub struct TypeDefinition {
     pub definition: KindDefinition
 }
 
+/// Structure fields.
+impl<'a> HasBranches<&'a Field> for &'a TypeDefinition {
+    fn branches_impl(self) -> impl Iterator<Item = &'a Field> {
+        let structure = match &self.definition {
+            KindDefinition::Structure(structure) => Some(structure),
+            _ => None
+        };
+        structure.into_iter().flat_map(|structure| structure.fields.iter())
+    }
+}
+
+impl<'a> HasBranches<&'a mut Field> for &'a mut TypeDefinition {
+    fn branches_impl(self) -> impl Iterator<Item = &'a mut Field> {
+        let structure = match &mut self.definition {
+            KindDefinition::Structure(structure) => Some(structure),
+            _ => None
+        };
+        structure.into_iter().flat_map(|structure| structure.fields.iter_mut())
+    }
+}
+
+/// Enumeration variants.
+impl<'a> HasBranches<&'a Variant> for &'a TypeDefinition {
+    fn branches_impl(self) -> impl Iterator<Item = &'a Variant> {
+        let enumeration = match &self.definition {
+            KindDefinition::Enumeration(enumeration) => Some(enumeration),
+            _ => None
+        };
+        enumeration.into_iter().flat_map(|enumeration| enumeration.variants.iter())
+    }
+}
+
+impl<'a> HasBranches<&'a mut Variant> for &'a mut TypeDefinition {
+    fn branches_impl(self) -> impl Iterator<Item = &'a mut Variant> {
+        let enumeration = match &mut self.definition {
+            KindDefinition::Enumeration(enumeration) => Some(enumeration),
+            _ => None
+        };
+        enumeration.into_iter().flat_map(|enumeration| enumeration.variants.iter_mut())
+    }
+}
+
+/// The aliased type. Generic parameters aren't type references, so they aren't branches.
+impl<'a> HasBranches<&'a Type> for &'a TypeDefinition {
+    fn branches_impl(self) -> impl Iterator<Item = &'a Type> {
+        let type_ = match &self.definition {
+            KindDefinition::TypeAlias(type_alias) => Some(&type_alias.type_),
+            _ => None
+        };
+        type_.into_iter()
+    }
+}
+
+impl<'a> HasBranches<&'a mut Type> for &'a mut TypeDefinition {
+    fn branches_impl(self) -> impl Iterator<Item = &'a mut Type> {
+        let type_ = match &mut self.definition {
+            KindDefinition::TypeAlias(type_alias) => Some(&mut type_alias.type_),
+            _ => None
+        };
+        type_.into_iter()
+    }
+}
+
 impl CountSymbols for Vec<TypeDefinition> {
     fn count_symbols(&self) -> usize {
         self.len()
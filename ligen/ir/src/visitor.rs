@@ -1,12 +1,103 @@
 use is_tree::visitor;
 
-use crate::{Library, Module};
+use crate::{Field, Function, Import, Interface, Library, Method, Module, Object, Parameter, Type, TypeDefinition, Variant};
 
 visitor! {
     pub enum Visitors, VisitorsMut {
         Root(Library visits [Module]),
         Branches(
-            Module visits [Module]
+            Module visits [Module, Import, Object, Function, TypeDefinition, Interface],
+            Import,
+            Object visits [Type],
+            Function visits [Parameter, Type, Function],
+            TypeDefinition visits [Field, Variant, Type],
+            Field visits [Type],
+            Variant,
+            Interface visits [Object, Function, Method],
+            Method visits [Parameter, Type, Method],
+            Parameter visits [Type],
+            Type visits [Type]
         )
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Enumeration, Path, Structure, TypeAlias};
+
+    fn library() -> Library {
+        let user = Type::from(Path::from("User"));
+        let root_module = Module {
+            identifier: "library".into(),
+            imports: vec![Import { path: "models::User".into(), ..Default::default() }],
+            objects: vec![Object { identifier: "DEFAULT_USER".into(), type_: user.clone(), ..Default::default() }],
+            functions: vec![Function {
+                identifier: "find".into(),
+                inputs: vec![Parameter { identifier: "users".into(), type_: Type::vector(user.clone()), ..Default::default() }],
+                output: Some(Type::option(user.clone())),
+                ..Default::default()
+            }],
+            types: vec![
+                TypeDefinition {
+                    identifier: "Group".into(),
+                    definition: Structure { fields: vec![Field { identifier: Some("owner".into()), type_: user.clone(), ..Default::default() }] }.into(),
+                    ..Default::default()
+                },
+                TypeDefinition {
+                    identifier: "Role".into(),
+                    definition: Enumeration { variants: vec![Variant { identifier: "Admin".into(), ..Default::default() }] }.into(),
+                    ..Default::default()
+                },
+                TypeDefinition { identifier: "Users".into(), definition: TypeAlias { type_: Type::vector(user.clone()) }.into(), ..Default::default() }
+            ],
+            interfaces: vec![Interface {
+                identifier: "Group".into(),
+                methods: vec![Method { identifier: "owner".into(), output: Some(user), ..Default::default() }],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        Library { identifier: "library".into(), root_module, ..Default::default() }
+    }
+
+    fn users(library: &Library) -> usize {
+        TreeIterator::<Visitors>::new(library)
+            .filter_map(|visitor| visitor.into_type().ok())
+            .filter(|visitor| visitor.value.path == Path::from("User"))
+            .count()
+    }
+
+    #[test]
+    fn every_node() {
+        let library = library();
+        let visitors = TreeIterator::<Visitors>::new(&library).collect::<Vec<_>>();
+        assert!(visitors.iter().any(|visitor| visitor.is_import()));
+        assert!(visitors.iter().any(|visitor| visitor.is_object()));
+        assert!(visitors.iter().any(|visitor| visitor.is_parameter()));
+        assert!(visitors.iter().any(|visitor| visitor.is_field()));
+        assert!(visitors.iter().any(|visitor| visitor.is_variant()));
+        assert!(visitors.iter().any(|visitor| visitor.is_method()));
+        let field = visitors
+            .iter()
+            .find_map(|visitor| visitor.as_field())
+            .expect("Missing field.");
+        assert_eq!(field.path().to_string(), "library::library::Group::owner");
+        // The object, the field, the method output and the generic arguments of the parameter, the output and the alias.
+        assert_eq!(users(&library), 6);
+    }
+
+    #[test]
+    fn rename_types() {
+        let mut library = library();
+        TreeIterator::<VisitorsMut>::new(&mut library).for_each(|mut visitor| {
+            if let VisitorsMut::Type(visitor) = &mut visitor {
+                if visitor.value.path == Path::from("User") {
+                    visitor.value.path = Path::from("Person");
+                }
+            }
+        });
+        assert_eq!(users(&library), 0);
+        assert_eq!(library.root_module.functions[0].inputs[0].type_, Type::vector(Type::from(Path::from("Person"))));
+    }
+}
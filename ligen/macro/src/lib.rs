@@ -1,11 +1,24 @@
+//! Attribute macros validating at compile time the items exported by ligen.
+//!
+//! `#[ligen(...)]` checks its directives, those of the `#[ligen(...)]` attributes within the item
+//! and the types of the item's signatures, reporting what ligen can't handle as compile errors.
+//! If `LIGEN_REGISTRATION_DIR` is set before a clean build, it also registers the item's IR, see
+//! `ligen_rust_parser::registration`.
+
+mod validator;
+
 use proc_macro::TokenStream;
+use validator::Validator;
 
 #[proc_macro_attribute]
-pub fn ligen(_attributes: TokenStream, input: TokenStream) -> TokenStream {
-    input
+pub fn ligen(attributes: TokenStream, input: TokenStream) -> TokenStream {
+    validator::expand(attributes.into(), input.into()).into()
 }
 
+/// Checks the directives of the enclosing module.
 #[proc_macro]
-pub fn inner_ligen(_input: TokenStream) -> TokenStream {
-    Default::default()
-}
\ No newline at end of file
+pub fn inner_ligen(input: TokenStream) -> TokenStream {
+    let mut validator = Validator::new();
+    validator.check_directives(input.into());
+    validator.to_compile_errors().into()
+}
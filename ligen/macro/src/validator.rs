@@ -0,0 +1,250 @@
+//! Compile-time validation of the items annotated with `#[ligen]`.
+
+use proc_macro2::{Span, TokenStream};
+use quote::ToTokens;
+use syn::parse::Parser as _;
+use syn::punctuated::Punctuated;
+use syn::visit::Visit;
+use syn::visit_mut::VisitMut;
+use ligen_common::Error;
+use ligen_ir::Attributes;
+use ligen_ir::macro_attributes::Group;
+use ligen_parser::{Directive, Parser, ParserConfig};
+use ligen_rust_parser::item::ItemParser;
+use ligen_rust_parser::macro_attributes::attributes::AttributeParser;
+use ligen_rust_parser::registration;
+use ligen_rust_parser::types::TypeParser;
+
+type Directives = Punctuated<syn::Meta, syn::Token![,]>;
+
+/// Collects the errors of the directives and of the unsupported types of an item.
+#[derive(Default)]
+pub struct Validator {
+    config: ParserConfig,
+    errors: Vec<syn::Error>
+}
+
+impl Validator {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Checks the directives in `tokens`, e.g. `rename = "name", opaque`.
+    pub fn check_directives(&mut self, tokens: TokenStream) {
+        match Directives::parse_terminated.parse2(tokens) {
+            Ok(directives) => for directive in directives {
+                self.check_directive(directive);
+            },
+            Err(error) => self.errors.push(error)
+        }
+    }
+
+    fn check_directive(&mut self, meta: syn::Meta) {
+        let result = AttributeParser::default()
+            .parse(meta.clone(), &self.config)
+            .and_then(|attribute| {
+                Directive::parse_all(&Group::new("ligen", Attributes::from(attribute)).into())
+                    .into_iter()
+                    .collect::<ligen_common::Result<Vec<_>>>()
+            });
+        if let Err(error) = result {
+            self.errors.push(syn::Error::new_spanned(meta, message(error)));
+        }
+    }
+
+    /// Checks `item`, annotated with `#[ligen(attributes)]`, and registers its IR if requested.
+    pub fn check_item(&mut self, item: &syn::Item, attributes: TokenStream) {
+        self.check_directives(attributes.clone());
+        self.visit_item(item);
+        // Out-of-line modules aren't loaded, so there is nothing else to check.
+        if !self.errors.is_empty() || matches!(item, syn::Item::Mod(syn::ItemMod { content: None, .. })) {
+            return;
+        }
+        let mut item = item.clone();
+        if let Some(attrs) = item_attributes(&mut item).filter(|_| !attributes.is_empty()) {
+            attrs.insert(0, syn::parse_quote!(#[ligen(#attributes)]));
+        }
+        match ItemParser::new().parse(item, &self.config) {
+            // Registering is a side effect of the build, so failing to register doesn't fail it.
+            Ok(item) => if let Err(error) = registration::register(&item) {
+                eprintln!("warning: ligen failed to register `{}`: {}", item.identifier(), message(error));
+            },
+            Err(error) => self.errors.push(syn::Error::new(Span::call_site(), message(error)))
+        }
+    }
+
+    /// The errors as `compile_error!` invocations.
+    pub fn to_compile_errors(&self) -> TokenStream {
+        self.errors.iter().map(syn::Error::to_compile_error).collect()
+    }
+}
+
+impl<'ast> Visit<'ast> for Validator {
+    fn visit_attribute(&mut self, attribute: &'ast syn::Attribute) {
+        if attribute.path().is_ident("ligen") {
+            match &attribute.meta {
+                syn::Meta::Path(_) => (),
+                syn::Meta::List(list) => self.check_directives(list.tokens.clone()),
+                syn::Meta::NameValue(_) => self.errors.push(syn::Error::new_spanned(attribute, "expected `#[ligen]` or `#[ligen(...)]`"))
+            }
+        }
+    }
+
+    // Only the items ligen parses are checked.
+    fn visit_item(&mut self, item: &'ast syn::Item) {
+        match item {
+            syn::Item::Mod(_) | syn::Item::Struct(_) | syn::Item::Enum(_) | syn::Item::Type(_) |
+            syn::Item::Fn(_) | syn::Item::Impl(_) | syn::Item::Const(_) => syn::visit::visit_item(self, item),
+            syn::Item::Union(union) => self.errors.push(syn::Error::new_spanned(union, "unions aren't supported yet")),
+            _ => ()
+        }
+    }
+
+    fn visit_impl_item(&mut self, item: &'ast syn::ImplItem) {
+        if let syn::ImplItem::Fn(_) | syn::ImplItem::Const(_) = item {
+            syn::visit::visit_impl_item(self, item);
+        }
+    }
+
+    fn visit_generics(&mut self, _generics: &'ast syn::Generics) {}
+
+    fn visit_block(&mut self, _block: &'ast syn::Block) {}
+
+    fn visit_expr(&mut self, _expr: &'ast syn::Expr) {}
+
+    fn visit_type(&mut self, type_: &'ast syn::Type) {
+        if let Err(error) = TypeParser::new().parse(type_.clone(), &self.config) {
+            // Points at the innermost unsupported types, if the error comes from them.
+            let errors = self.errors.len();
+            syn::visit::visit_type(self, type_);
+            if self.errors.len() == errors {
+                self.errors.push(syn::Error::new_spanned(type_, message(error)));
+            }
+        }
+    }
+}
+
+/// Removes the `#[ligen(...)]` attributes within an item, as they aren't attributes the compiler
+/// accepts on fields, variants or nested items.
+pub struct DirectivesStripper;
+
+impl DirectivesStripper {
+    fn strip(attributes: &mut Vec<syn::Attribute>) {
+        attributes.retain(|attribute| !attribute.path().is_ident("ligen"));
+    }
+}
+
+impl VisitMut for DirectivesStripper {
+    fn visit_item_mut(&mut self, item: &mut syn::Item) {
+        if let Some(attributes) = item_attributes(item) {
+            Self::strip(attributes);
+        }
+        syn::visit_mut::visit_item_mut(self, item);
+    }
+
+    fn visit_impl_item_mut(&mut self, item: &mut syn::ImplItem) {
+        match item {
+            syn::ImplItem::Fn(function) => Self::strip(&mut function.attrs),
+            syn::ImplItem::Const(constant) => Self::strip(&mut constant.attrs),
+            syn::ImplItem::Type(type_) => Self::strip(&mut type_.attrs),
+            _ => ()
+        }
+        syn::visit_mut::visit_impl_item_mut(self, item);
+    }
+
+    fn visit_field_mut(&mut self, field: &mut syn::Field) {
+        Self::strip(&mut field.attrs);
+        syn::visit_mut::visit_field_mut(self, field);
+    }
+
+    fn visit_variant_mut(&mut self, variant: &mut syn::Variant) {
+        Self::strip(&mut variant.attrs);
+        syn::visit_mut::visit_variant_mut(self, variant);
+    }
+
+    fn visit_block_mut(&mut self, _block: &mut syn::Block) {}
+}
+
+fn item_attributes(item: &mut syn::Item) -> Option<&mut Vec<syn::Attribute>> {
+    match item {
+        syn::Item::Mod(module) => Some(&mut module.attrs),
+        syn::Item::Struct(structure) => Some(&mut structure.attrs),
+        syn::Item::Enum(enumeration) => Some(&mut enumeration.attrs),
+        syn::Item::Type(type_) => Some(&mut type_.attrs),
+        syn::Item::Fn(function) => Some(&mut function.attrs),
+        syn::Item::Impl(implementation) => Some(&mut implementation.attrs),
+        syn::Item::Const(constant) => Some(&mut constant.attrs),
+        _ => None
+    }
+}
+
+fn message(error: Error) -> String {
+    match error {
+        Error::Message(message) => message,
+        error => format!("{:?}", error)
+    }
+}
+
+/// Validates `item` and returns it without its inner directives, preceded by the errors found.
+pub fn expand(attributes: TokenStream, item: TokenStream) -> TokenStream {
+    let mut item = match syn::parse2::<syn::Item>(item) {
+        Ok(item) => item,
+        Err(error) => return error.to_compile_error()
+    };
+    let mut validator = Validator::new();
+    validator.check_item(&item, attributes);
+    DirectivesStripper.visit_item_mut(&mut item);
+    let mut tokens = validator.to_compile_errors();
+    item.to_tokens(&mut tokens);
+    tokens
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quote::quote;
+
+    fn errors(attributes: TokenStream, item: TokenStream) -> Vec<String> {
+        let mut validator = Validator::new();
+        validator.check_item(&syn::parse2(item).expect("Failed to parse item."), attributes);
+        validator.errors.iter().map(|error| error.to_string()).collect()
+    }
+
+    #[test]
+    fn directives() {
+        assert!(errors(quote! { rename = "Person", opaque }, quote! { pub struct User; }).is_empty());
+        assert_eq!(errors(quote! { renam = "Person" }, quote! { pub struct User; }), vec!["unknown directive `renam`"]);
+        assert_eq!(errors(quote! {}, quote! {
+            pub struct User {
+                #[ligen(skip)]
+                pub password: String
+            }
+        }), vec!["unknown directive `skip`"]);
+    }
+
+    #[test]
+    fn unsupported_types() {
+        assert!(errors(quote! {}, quote! { pub fn function(value: &Vec<u8>) -> Option<String> { None } }).is_empty());
+        assert_eq!(errors(quote! {}, quote! { pub fn function(callback: fn(i32)) -> (i32, i32) { todo!() } }).len(), 2);
+        assert_eq!(errors(quote! {}, quote! { pub trait Trait {} }).len(), 1);
+    }
+
+    #[test]
+    fn unions() {
+        let item = quote! { mod m { pub union U { a: u32 } } };
+        assert_eq!(errors(quote! {}, item.clone()), vec!["unions aren't supported yet"]);
+        assert!(expand(quote! {}, item).to_string().contains("compile_error"));
+    }
+
+    #[test]
+    fn stripped_directives() {
+        let tokens = expand(quote! { opaque }, quote! {
+            #[ligen(rename = "Person")]
+            pub struct User {
+                #[ligen(skip_field)]
+                pub password: String
+            }
+        });
+        assert_eq!(tokens.to_string(), quote! { pub struct User { pub password: String } }.to_string());
+    }
+}
@@ -0,0 +1,177 @@
+//! On-disk cache of the IR parsed from source files.
+//!
+//! Entries are keyed by the file's contents, the parser's name and version and the effective
+//! configuration, so editing a file, upgrading the parser or changing any option invalidates them.
+//! Unused entries are never removed, see [`ParseCache::clean`].
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use serde::de::DeserializeOwned;
+use sha2::{Digest, Sha256};
+use crate::prelude::*;
+use crate::ParserConfig;
+
+/// Environment variable overriding the cache directory.
+pub const CACHE_DIR: &str = "LIGEN_CACHE_DIR";
+
+/// Counter naming the temporary files of the entries being written.
+static TEMPORARY: AtomicUsize = AtomicUsize::new(0);
+
+/// Cache of a parser's output for each source file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseCache {
+    directory: PathBuf,
+    parser: String,
+    version: String
+}
+
+impl ParseCache {
+    pub fn new(directory: impl Into<PathBuf>) -> Self {
+        let directory = directory.into();
+        let parser = Default::default();
+        let version = Default::default();
+        Self { directory, parser, version }
+    }
+
+    /// `$LIGEN_CACHE_DIR`, `$XDG_CACHE_HOME/ligen` or `~/.cache/ligen`, falling back to the
+    /// temporary directory.
+    pub fn default_directory() -> PathBuf {
+        std::env::var_os(CACHE_DIR)
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("XDG_CACHE_HOME").map(|directory| PathBuf::from(directory).join("ligen")))
+            .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".cache").join("ligen")))
+            .unwrap_or_else(|| std::env::temp_dir().join("ligen-cache"))
+    }
+
+    /// The cache of the parser `name` at `version`, e.g. `env!("CARGO_PKG_VERSION")`.
+    pub fn for_parser(mut self, name: impl Into<String>, version: impl Into<String>) -> Self {
+        self.parser = name.into();
+        self.version = version.into();
+        self
+    }
+
+    pub fn directory(&self) -> &Path {
+        &self.directory
+    }
+
+    /// Key of the output parsed from `contents` with `config`. `contents` may have several parts,
+    /// e.g. a file and the path of its module. The key is a SHA-256 digest, so it's the same across
+    /// Rust releases and platforms.
+    pub fn key(&self, contents: &[&[u8]], config: &ParserConfig) -> String {
+        let mut hasher = Sha256::new();
+        // Every part is prefixed by its length, so the boundaries between them are part of the key.
+        let mut update = |part: &[u8]| {
+            hasher.update((part.len() as u64).to_le_bytes());
+            hasher.update(part);
+        };
+        update(self.parser.as_bytes());
+        update(self.version.as_bytes());
+        for content in contents {
+            update(content);
+        }
+        // The configuration is a hash map, so its values are sorted first. They are hashed as JSON,
+        // because their display is lossy, e.g. `["a, b"]` and `["a", "b"]` both display as `[a, b]`.
+        let values = config
+            .iter()
+            .map(|(path, value)| (path.to_string(), serde_json::to_string(&value).unwrap_or_default()))
+            .collect::<BTreeMap<_, _>>();
+        for (path, value) in values {
+            update(path.as_bytes());
+            update(value.as_bytes());
+        }
+        hasher
+            .finalize()
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect()
+    }
+
+    /// Gets the entry at `key`. Unreadable entries, e.g. of an older IR, are misses.
+    pub fn get<T: DeserializeOwned>(&self, key: &str) -> Option<T> {
+        let contents = std::fs::read(self.path(key)).ok()?;
+        serde_json::from_slice(&contents).ok()
+    }
+
+    /// Sets the entry at `key`.
+    pub fn set<T: Serialize>(&self, key: &str, value: &T) -> Result<()> {
+        let path = self.path(key);
+        let directory = path.parent().ok_or("Failed to get the cache directory.")?;
+        std::fs::create_dir_all(directory)?;
+        // Entries are written to a temporary file first, so readers never see them half written.
+        let temporary = path.with_extension(format!("{}.{}.tmp", std::process::id(), TEMPORARY.fetch_add(1, Ordering::Relaxed)));
+        std::fs::write(&temporary, serde_json::to_vec(value)?)?;
+        std::fs::rename(&temporary, &path)?;
+        Ok(())
+    }
+
+    /// Gets the entry at `key` or sets it to the output of `parse`. Failing to set it, e.g. because
+    /// the directory is read-only, is only a warning.
+    pub fn get_or_parse<T: Serialize + DeserializeOwned>(&self, key: &str, parse: impl FnOnce() -> Result<T>) -> Result<T> {
+        match self.get(key) {
+            Some(value) => Ok(value),
+            None => {
+                let value = parse()?;
+                if let Err(error) = self.set(key, &value) {
+                    log::warn!("Failed to cache {} in {}: {}", key, self.directory.display(), error);
+                }
+                Ok(value)
+            }
+        }
+    }
+
+    /// Removes the cache in `directory`, of every parser.
+    pub fn clean(directory: impl AsRef<Path>) -> Result<()> {
+        let directory = directory.as_ref();
+        if directory.exists() {
+            std::fs::remove_dir_all(directory)?;
+        }
+        Ok(())
+    }
+
+    fn path(&self, key: &str) -> PathBuf {
+        let parser = self.parser.to_lowercase();
+        self.directory.join(parser).join(format!("{}.json", key))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ligen_ir::Module;
+
+    #[test]
+    fn cache() -> Result<()> {
+        let directory = std::env::temp_dir().join("ligen-parse-cache-test");
+        ParseCache::clean(&directory)?;
+        let cache = ParseCache::new(&directory).for_parser("Python", "1.0.0");
+        let config = ParserConfig::default();
+        let key = cache.key(&["def function(): pass".as_bytes()], &config);
+        assert_eq!(key, cache.key(&["def function(): pass".as_bytes()], &config));
+        assert_ne!(key, cache.key(&["def other(): pass".as_bytes()], &config));
+        assert_ne!(key, ParseCache::new(&directory).for_parser("Python", "1.0.1").key(&["def function(): pass".as_bytes()], &config));
+        let mut changed = config.clone();
+        changed.set_only_parse_symbols(true);
+        assert_ne!(key, cache.key(&["def function(): pass".as_bytes()], &changed));
+        let mut joined = config.clone();
+        joined.set_exclude(vec!["a, b"]);
+        let mut split = config.clone();
+        split.set_exclude(vec!["a", "b"]);
+        assert_ne!(cache.key(&[], &joined), cache.key(&[], &split));
+        assert_ne!(cache.key(&["ab".as_bytes(), "c".as_bytes()], &config), cache.key(&["a".as_bytes(), "bc".as_bytes()], &config));
+        assert_eq!(key.len(), 64);
+
+        let module = Module { identifier: "module".into(), ..Default::default() };
+        assert_eq!(cache.get::<Module>(&key), None);
+        assert_eq!(cache.get_or_parse(&key, || Ok(module.clone()))?, module);
+        assert_eq!(cache.get_or_parse::<Module>(&key, || Err("Cached.".into()))?, module);
+        ParseCache::clean(&directory)?;
+        assert_eq!(cache.get::<Module>(&key), None);
+
+        // A cache that can't be written still returns what was parsed.
+        std::fs::write(&directory, "")?;
+        assert_eq!(cache.get_or_parse(&key, || Ok(module.clone()))?, module);
+        std::fs::remove_file(&directory)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,470 @@
+//! `ligen(...)` directives, written as `#[ligen(...)]` in Rust, `@ligen(...)` decorators or
+//! `# ligen: ...` comments in Python.
+//!
+//! - `ignore`: leaves the item out of the IR.
+//! - `rename = "name"`: renames the item. Paths referring to a renamed module, type, interface,
+//!   function or object are renamed with it.
+//! - `opaque`: exposes a type without its fields or variants.
+//! - `name_convention = "snake_case"`: renames the objects and functions of a module, the fields
+//!   and variants of a type or the members of an interface to `snake_case`, `camelCase`,
+//!   `PascalCase` or `kebab-case`, unless they are renamed themselves.
+//! - `skip_field`: leaves a field out. On a type, `skip_field = "field"` names the field.
+//! - `readonly`: marks a field or object as read-only. On a type, `readonly = "field"` names the field.
+
+use ligen_ir::{Attribute, Attributes, Field, Function, Identifier, Import, Interface, KindDefinition, Library, Method, Module, Mutability, Object, Path, Type, TypeDefinition, Variant};
+use ligen_ir::identifier::NamingConvention;
+use ligen_ir::macro_attributes::Group;
+use ligen_utils::transformers::{walk_type, Scope, Transformer};
+use crate::prelude::*;
+
+/// A `ligen(...)` directive.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Directive {
+    /// `ignore`.
+    Ignore,
+    /// `rename = "name"`.
+    Rename(Identifier),
+    /// `opaque`.
+    Opaque,
+    /// `name_convention = "snake_case"`.
+    NameConvention(NamingConvention),
+    /// `skip_field`, or `skip_field = "field"` on a type.
+    SkipField(Option<Identifier>),
+    /// `readonly`, or `readonly = "field"` on a type.
+    Readonly(Option<Identifier>)
+}
+
+impl Directive {
+    /// Directives in the `ligen` groups of `attributes`. Unknown or malformed directives are errors.
+    pub fn parse_all(attributes: &Attributes) -> Vec<Result<Self>> {
+        attributes
+            .attributes
+            .iter()
+            .filter_map(|attribute| match attribute {
+                Attribute::Group(group) if group.path == Path::from("ligen") => Some(&group.attributes.attributes),
+                _ => None
+            })
+            .flatten()
+            .map(Self::parse)
+            .collect()
+    }
+
+    /// Valid directives in the `ligen` groups of `attributes`.
+    pub fn from_attributes(attributes: &Attributes) -> Vec<Self> {
+        Self::parse_all(attributes)
+            .into_iter()
+            .filter_map(Result::ok)
+            .collect()
+    }
+
+    fn parse(attribute: &Attribute) -> Result<Self> {
+        match attribute {
+            Attribute::Group(group) if group.attributes.is_empty() => match group.path.to_string().as_str() {
+                "ignore" => Ok(Self::Ignore),
+                "opaque" => Ok(Self::Opaque),
+                "skip_field" => Ok(Self::SkipField(None)),
+                "readonly" => Ok(Self::Readonly(None)),
+                "rename" | "name_convention" => Err(Error::Message(format!("directive `{}` expects a value", group.path))),
+                name => Err(Error::Message(format!("unknown directive `{}`", name)))
+            },
+            Attribute::Named(named) => {
+                let name = named.path.to_string();
+                let value = match (name.as_str(), named.literal.as_string()) {
+                    ("ignore" | "opaque", _) => return Err(Error::Message(format!("directive `{}` doesn't take a value", name))),
+                    ("rename" | "name_convention" | "skip_field" | "readonly", Some(value)) => value,
+                    ("rename" | "name_convention" | "skip_field" | "readonly", None) => return Err(Error::Message(format!("directive `{}` expects a string", name))),
+                    _ => return Err(Error::Message(format!("unknown directive `{}`", name)))
+                };
+                match name.as_str() {
+                    "rename" => Ok(Self::Rename(value.as_str().into())),
+                    "skip_field" => Ok(Self::SkipField(Some(value.as_str().into()))),
+                    "readonly" => Ok(Self::Readonly(Some(value.as_str().into()))),
+                    _ => NamingConvention::from_name(value)
+                        .map(Self::NameConvention)
+                        .ok_or_else(|| Error::Message(format!("unknown naming convention `{}`", value)))
+                }
+            },
+            attribute => Err(Error::Message(format!("unknown directive `{}`", attribute)))
+        }
+    }
+}
+
+/// Applies the directives of every item, so the IR reflects them. Unknown directives are left to
+/// `LibraryValidator` to report.
+#[derive(Default)]
+pub struct DirectiveApplier {}
+
+impl DirectiveApplier {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Applies the directives of the root module and everything in it.
+    pub fn apply_library(&self, library: &mut Library) {
+        self.apply_module(&mut library.root_module);
+    }
+
+    /// Applies the directives of `module` and everything in it. Ignored members are removed, but
+    /// the module itself is kept, as it's up to its parent to remove it.
+    pub fn apply_module(&self, module: &mut Module) {
+        let module_path = Path::from(module.identifier.clone());
+        let mut applied = module.clone();
+        let mut renamed = Vec::new();
+        self.apply_members(&mut applied, &module_path, &mut renamed);
+        if renamed.is_empty() {
+            *module = applied;
+            return;
+        }
+        // References are looked up with the original names, so they are renamed before the items.
+        let library = Library { identifier: module.identifier.clone(), root_module: module.clone(), ..Default::default() };
+        RenameReferences { renamed }.transform_module(&Scope::new(&library), module);
+        self.apply_members(module, &module_path, &mut Vec::new());
+    }
+
+    /// Applies the directives of `module`, whose original path is `module_path`, pushing the
+    /// original paths of the renamed items and their new names to `renamed`.
+    fn apply_members(&self, module: &mut Module, module_path: &Path, renamed: &mut Vec<(Path, Identifier)>) {
+        let directives = Directive::from_attributes(&module.attributes);
+        let identifier = module.identifier.clone();
+        rename(&mut module.identifier, &directives, None);
+        push_renamed(renamed, module_path.clone(), &identifier, &module.identifier);
+        let convention = naming_convention(&directives);
+        module.imports.retain(|import| !is_ignored(&import.attributes));
+        for import in &mut module.imports {
+            self.apply_import(import);
+        }
+        module.objects.retain(|object| !is_ignored(&object.attributes));
+        for object in &mut module.objects {
+            let identifier = object.identifier.clone();
+            self.apply_object(object, convention);
+            push_renamed(renamed, module_path.clone().join(identifier.clone()), &identifier, &object.identifier);
+        }
+        module.functions.retain(|function| !is_ignored(&function.attributes));
+        for function in &mut module.functions {
+            let identifier = function.identifier.clone();
+            self.apply_function(function, convention);
+            push_renamed(renamed, module_path.clone().join(identifier.clone()), &identifier, &function.identifier);
+        }
+        // Implementations follow the type they implement, so they are ignored or renamed with it.
+        let ignored = module
+            .types
+            .iter()
+            .filter(|type_| is_ignored(&type_.attributes))
+            .map(|type_| type_.identifier.clone())
+            .collect::<Vec<_>>();
+        module.types.retain(|type_| !is_ignored(&type_.attributes));
+        let mut renamed_types = Vec::new();
+        for type_ in &mut module.types {
+            let identifier = type_.identifier.clone();
+            self.apply_type_definition(type_);
+            if type_.identifier != identifier {
+                renamed_types.push((identifier, type_.identifier.clone()));
+            }
+        }
+        module.interfaces.retain(|interface| !is_ignored(&interface.attributes) && !ignored.contains(&interface.identifier));
+        for interface in &mut module.interfaces {
+            let identifier = interface.identifier.clone();
+            if let Some((_, name)) = renamed_types.iter().find(|(type_, _)| *type_ == interface.identifier) {
+                interface.identifier = name.clone();
+            }
+            self.apply_interface(interface);
+            push_renamed(renamed, module_path.clone().join(identifier.clone()), &identifier, &interface.identifier);
+        }
+        for (identifier, name) in renamed_types {
+            push_renamed(renamed, module_path.clone().join(identifier.clone()), &identifier, &name);
+        }
+        module.modules.retain(|module| !is_ignored(&module.attributes));
+        for module in &mut module.modules {
+            let module_path = module_path.clone().join(module.identifier.clone());
+            self.apply_members(module, &module_path, renamed);
+        }
+    }
+
+    fn apply_import(&self, import: &mut Import) {
+        let directives = Directive::from_attributes(&import.attributes);
+        if let Some(name) = renaming(&directives) {
+            import.renaming = Some(name);
+        }
+    }
+
+    fn apply_object(&self, object: &mut Object, convention: Option<&NamingConvention>) {
+        let directives = Directive::from_attributes(&object.attributes);
+        rename(&mut object.identifier, &directives, convention);
+        if directives.contains(&Directive::Readonly(None)) {
+            object.mutability = Mutability::Constant;
+        }
+    }
+
+    fn apply_function(&self, function: &mut Function, convention: Option<&NamingConvention>) {
+        let directives = Directive::from_attributes(&function.attributes);
+        rename(&mut function.identifier, &directives, convention);
+        function.overloads.retain(|overload| !is_ignored(&overload.attributes));
+    }
+
+    fn apply_method(&self, method: &mut Method, convention: Option<&NamingConvention>) {
+        let directives = Directive::from_attributes(&method.attributes);
+        rename(&mut method.identifier, &directives, convention);
+        method.overloads.retain(|overload| !is_ignored(&overload.attributes));
+    }
+
+    fn apply_type_definition(&self, type_definition: &mut TypeDefinition) {
+        let directives = Directive::from_attributes(&type_definition.attributes);
+        rename(&mut type_definition.identifier, &directives, None);
+        let convention = naming_convention(&directives);
+        let is_opaque = directives.contains(&Directive::Opaque);
+        match &mut type_definition.definition {
+            // Opaque types keep their kind, but not their fields or variants.
+            KindDefinition::Structure(structure) if is_opaque => structure.fields.clear(),
+            KindDefinition::Enumeration(enumeration) if is_opaque => enumeration.variants.clear(),
+            KindDefinition::Structure(structure) => {
+                structure.fields.retain(|field| !is_skipped(field, &directives));
+                for field in &mut structure.fields {
+                    self.apply_field(field, &directives, convention);
+                }
+            },
+            KindDefinition::Enumeration(enumeration) => {
+                enumeration.variants.retain(|variant| !is_ignored(&variant.attributes));
+                for variant in &mut enumeration.variants {
+                    self.apply_variant(variant, convention);
+                }
+            },
+            KindDefinition::TypeAlias(_) => ()
+        }
+    }
+
+    /// Applies the directives of `field` and the ones of its type naming it.
+    fn apply_field(&self, field: &mut Field, type_directives: &[Directive], convention: Option<&NamingConvention>) {
+        let directives = Directive::from_attributes(&field.attributes);
+        let is_readonly = directives.contains(&Directive::Readonly(None))
+            || field.identifier.as_ref().map_or(false, |identifier| type_directives.contains(&Directive::Readonly(Some(identifier.clone()))));
+        if let Some(identifier) = &mut field.identifier {
+            rename(identifier, &directives, convention);
+        }
+        if is_readonly && !field.attributes.has_readonly_attribute() {
+            field.attributes.push(Group::new("ligen", Group::from("readonly")).into());
+        }
+    }
+
+    fn apply_variant(&self, variant: &mut Variant, convention: Option<&NamingConvention>) {
+        let directives = Directive::from_attributes(&variant.attributes);
+        rename(&mut variant.identifier, &directives, convention);
+    }
+
+    fn apply_interface(&self, interface: &mut Interface) {
+        let directives = Directive::from_attributes(&interface.attributes);
+        rename(&mut interface.identifier, &directives, None);
+        let convention = naming_convention(&directives);
+        interface.objects.retain(|object| !is_ignored(&object.attributes));
+        for object in &mut interface.objects {
+            self.apply_object(object, convention);
+        }
+        interface.functions.retain(|function| !is_ignored(&function.attributes));
+        for function in &mut interface.functions {
+            self.apply_function(function, convention);
+        }
+        interface.methods.retain(|method| !is_ignored(&method.attributes));
+        for method in &mut interface.methods {
+            self.apply_method(method, convention);
+        }
+    }
+}
+
+/// Renames the paths referring to renamed items, as written in the original library.
+struct RenameReferences {
+    /// Original absolute paths of the renamed items and their new names.
+    renamed: Vec<(Path, Identifier)>
+}
+
+impl RenameReferences {
+    /// Renames each segment of `path` that refers to a renamed item.
+    fn rename(&self, scope: &Scope, path: &mut Path) {
+        let written = path.clone();
+        for (index, segment) in path.segments.iter_mut().enumerate() {
+            let prefix = Path { segments: written.segments[..=index].to_vec() };
+            let Some(resolution) = scope.library.resolve(&prefix, &scope.module_path) else {
+                continue;
+            };
+            let name = self
+                .renamed
+                .iter()
+                .find(|(renamed, _)| *renamed == resolution.path && renamed.last().identifier == segment.identifier)
+                .map(|(_, name)| name);
+            if let Some(name) = name {
+                segment.identifier = name.clone();
+            }
+        }
+    }
+}
+
+impl Transformer for RenameReferences {
+    fn transform_import(&self, scope: &Scope, import: &mut Import) {
+        self.rename(scope, &mut import.path);
+    }
+
+    fn transform_type(&self, scope: &Scope, type_: &mut Type) {
+        self.rename(scope, &mut type_.path);
+        walk_type(self, scope, type_);
+    }
+
+    fn transform_path(&self, scope: &Scope, path: &mut Path) {
+        self.rename(scope, path);
+    }
+}
+
+fn push_renamed(renamed: &mut Vec<(Path, Identifier)>, path: Path, identifier: &Identifier, name: &Identifier) {
+    if identifier != name {
+        renamed.push((path, name.clone()));
+    }
+}
+
+fn is_ignored(attributes: &Attributes) -> bool {
+    Directive::from_attributes(attributes).contains(&Directive::Ignore)
+}
+
+fn is_skipped(field: &Field, type_directives: &[Directive]) -> bool {
+    let directives = Directive::from_attributes(&field.attributes);
+    directives.contains(&Directive::Ignore)
+        || directives.contains(&Directive::SkipField(None))
+        || field.identifier.as_ref().map_or(false, |identifier| type_directives.contains(&Directive::SkipField(Some(identifier.clone()))))
+}
+
+fn renaming(directives: &[Directive]) -> Option<Identifier> {
+    directives.iter().find_map(|directive| match directive {
+        Directive::Rename(name) => Some(name.clone()),
+        _ => None
+    })
+}
+
+fn naming_convention(directives: &[Directive]) -> Option<&NamingConvention> {
+    directives.iter().find_map(|directive| match directive {
+        Directive::NameConvention(convention) => Some(convention),
+        _ => None
+    })
+}
+
+/// Renames `identifier` as its directives say, or else to the `convention` of its parent.
+fn rename(identifier: &mut Identifier, directives: &[Directive], convention: Option<&NamingConvention>) {
+    if let Some(name) = renaming(directives) {
+        *identifier = name;
+    } else if let Some(convention) = convention {
+        *identifier = identifier.to_naming_convention(convention);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ligen_ir::macro_attributes::Named;
+    use ligen_ir::{Enumeration, Structure};
+
+    fn directives(attributes: Attributes) -> Attributes {
+        Group::new("ligen", attributes).into()
+    }
+
+    fn flag(name: &str) -> Attributes {
+        directives(Group::from(name).into())
+    }
+
+    fn named(name: &str, value: &str) -> Attributes {
+        directives(Named::new(name, value).into())
+    }
+
+    #[test]
+    fn parsing() {
+        let attributes = directives(Attributes { attributes: vec![
+            Group::from("ignore").into(),
+            Named::new("rename", "name").into(),
+            Named::new("name_convention", "camelCase").into(),
+            Group::from("readonly").into(),
+            Group::from("typo").into(),
+            Named::new("name_convention", "Camel").into(),
+            Group::from("rename").into()
+        ]});
+        let directives = Directive::parse_all(&attributes);
+        let valid = directives.iter().filter_map(|directive| directive.as_ref().ok()).cloned().collect::<Vec<_>>();
+        assert_eq!(valid, vec![
+            Directive::Ignore,
+            Directive::Rename("name".into()),
+            Directive::NameConvention(NamingConvention::CamelCase),
+            Directive::Readonly(None)
+        ]);
+        assert_eq!(directives.iter().filter(|directive| directive.is_err()).count(), 3);
+    }
+
+    #[test]
+    fn application() {
+        let mut attributes = named("name_convention", "camelCase");
+        attributes.attributes.extend(named("skip_field", "cache").attributes);
+        attributes.attributes.extend(named("readonly", "user_name").attributes);
+        let user = TypeDefinition {
+            identifier: "User".into(),
+            attributes,
+            definition: Structure { fields: vec![
+                Field { identifier: Some("user_name".into()), type_: Type::string(), ..Default::default() },
+                Field { identifier: Some("password".into()), attributes: flag("skip_field"), ..Default::default() },
+                Field { identifier: Some("cache".into()), ..Default::default() },
+                Field { identifier: Some("created_at".into()), attributes: named("rename", "creation"), ..Default::default() }
+            ]}.into(),
+            ..Default::default()
+        };
+        let handle = TypeDefinition {
+            identifier: "Handle".into(),
+            attributes: flag("opaque"),
+            definition: Enumeration { variants: vec![Variant { identifier: "Raw".into(), ..Default::default() }] }.into(),
+            ..Default::default()
+        };
+        let mut module = Module {
+            identifier: "module".into(),
+            objects: vec![Object { identifier: "limit".into(), attributes: flag("readonly"), ..Default::default() }],
+            functions: vec![
+                Function { identifier: "create".into(), attributes: named("rename", "new_user"), ..Default::default() },
+                Function { identifier: "debug".into(), attributes: flag("ignore"), ..Default::default() }
+            ],
+            types: vec![user, handle],
+            modules: vec![Module { identifier: "internal".into(), attributes: flag("ignore"), ..Default::default() }],
+            ..Default::default()
+        };
+        DirectiveApplier::new().apply_module(&mut module);
+        assert!(module.modules.is_empty());
+        assert_eq!(module.objects[0].mutability, Mutability::Constant);
+        let functions = module.functions.iter().map(|function| function.identifier.name.as_str()).collect::<Vec<_>>();
+        assert_eq!(functions, vec!["new_user"]);
+        let KindDefinition::Structure(structure) = &module.types[0].definition else {
+            panic!("Not a structure.");
+        };
+        let fields = structure.fields.iter().filter_map(|field| field.identifier.as_ref()).map(|identifier| identifier.name.as_str()).collect::<Vec<_>>();
+        assert_eq!(fields, vec!["userName", "creation"]);
+        assert!(structure.fields[0].attributes.has_readonly_attribute());
+        assert_eq!(module.types[1].definition, KindDefinition::from(Enumeration::default()));
+    }
+
+    #[test]
+    fn renamed_references() {
+        let user = TypeDefinition { identifier: "User".into(), attributes: named("rename", "Account"), ..Default::default() };
+        let models = Module {
+            identifier: "models".into(),
+            types: vec![user],
+            interfaces: vec![Interface { identifier: "User".into(), ..Default::default() }],
+            ..Default::default()
+        };
+        let api = Module {
+            identifier: "api".into(),
+            imports: vec![Import { path: "super::models::User".into(), ..Default::default() }],
+            functions: vec![Function { identifier: "login".into(), output: Some(Type::from(Path::from("User"))), ..Default::default() }],
+            ..Default::default()
+        };
+        let mut module = Module {
+            identifier: "library".into(),
+            objects: vec![Object { identifier: "current".into(), type_: Type::from(Path::from("models::User")), ..Default::default() }],
+            modules: vec![models, api],
+            ..Default::default()
+        };
+        DirectiveApplier::new().apply_module(&mut module);
+        assert_eq!(module.modules[0].types[0].identifier, "Account");
+        assert_eq!(module.modules[0].interfaces[0].identifier, "Account");
+        assert_eq!(module.objects[0].type_, Type::from(Path::from("models::Account")));
+        let api = &module.modules[1];
+        assert_eq!(api.imports[0].path, Path::from("super::models::Account"));
+        assert_eq!(api.functions[0].output, Some(Type::from(Path::from("Account"))));
+    }
+}
@@ -0,0 +1,271 @@
+//! Include and exclude patterns over module and item paths, set with `ligen::include` and
+//! `ligen::exclude`.
+//!
+//! Patterns are globs over path segments separated by `::` or `.`: `*` and `?` match characters
+//! within a segment and `**` any number of segments. A pattern matches a path if it matches its
+//! last segments, so `tests` matches every module or item named `tests` and `internal.*` every
+//! member of a module named `internal`. Excluding a module excludes everything in it, while
+//! including a module includes everything in it.
+
+use std::fmt::{Display, Formatter};
+use std::sync::Mutex;
+use serde::{Deserialize, Serialize};
+use ligen_ir::{Identifier, Library, Module, Path};
+use crate::ParserConfig;
+
+/// Why a module or item was left out.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum FilterReason {
+    /// It matches an exclude pattern.
+    Excluded(String),
+    /// There are include patterns and it matches none of them.
+    NotIncluded
+}
+
+impl Display for FilterReason {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Excluded(pattern) => write!(f, "excluded by `{}`", pattern),
+            Self::NotIncluded => write!(f, "not included by any pattern")
+        }
+    }
+}
+
+/// A module or item left out.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Filtered {
+    pub path: Path,
+    pub reason: FilterReason
+}
+
+impl Display for Filtered {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.path, self.reason)
+    }
+}
+
+/// Modules and items left out while parsing.
+#[derive(Debug, Default)]
+pub struct FilterReport {
+    filtered: Mutex<Vec<Filtered>>
+}
+
+impl FilterReport {
+    pub fn push(&self, filtered: Filtered) {
+        self.filtered.lock().expect("Filter report poisoned.").push(filtered);
+    }
+
+    pub fn extend(&self, filtered: Vec<Filtered>) {
+        self.filtered.lock().expect("Filter report poisoned.").extend(filtered);
+    }
+
+    pub fn to_vec(&self) -> Vec<Filtered> {
+        self.filtered.lock().expect("Filter report poisoned.").clone()
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Pattern {
+    text: String,
+    segments: Vec<String>
+}
+
+impl Pattern {
+    fn new(text: String) -> Self {
+        let separator = if text.contains("::") { "::" } else { "." };
+        let segments = text.split(separator).map(String::from).collect();
+        Self { text, segments }
+    }
+
+    fn matches(&self, path: &[&str]) -> bool {
+        (0..=path.len()).any(|start| matches_segments(&self.segments, &path[start..]))
+    }
+}
+
+fn matches_segments(pattern: &[String], path: &[&str]) -> bool {
+    match pattern.split_first() {
+        None => path.is_empty(),
+        Some((first, rest)) if first == "**" => (0..=path.len()).any(|start| matches_segments(rest, &path[start..])),
+        Some((first, rest)) => path
+            .split_first()
+            .is_some_and(|(segment, path)| matches_wildcard(first.as_bytes(), segment.as_bytes()) && matches_segments(rest, path))
+    }
+}
+
+fn matches_wildcard(pattern: &[u8], text: &[u8]) -> bool {
+    match pattern.split_first() {
+        None => text.is_empty(),
+        Some((b'*', rest)) => (0..=text.len()).any(|start| matches_wildcard(rest, &text[start..])),
+        Some((b'?', rest)) => !text.is_empty() && matches_wildcard(rest, &text[1..]),
+        Some((character, rest)) => text.first() == Some(character) && matches_wildcard(rest, &text[1..])
+    }
+}
+
+/// Include and exclude patterns.
+#[derive(Debug, Default, Clone)]
+pub struct PathFilter {
+    include: Vec<Pattern>,
+    exclude: Vec<Pattern>
+}
+
+impl PathFilter {
+    pub fn new<S: Into<String>>(include: Vec<S>, exclude: Vec<S>) -> Self {
+        let include = include.into_iter().map(|pattern| Pattern::new(pattern.into())).collect();
+        let exclude = exclude.into_iter().map(|pattern| Pattern::new(pattern.into())).collect();
+        Self { include, exclude }
+    }
+
+    pub fn from_config(config: &ParserConfig) -> Self {
+        Self::new(config.get_include(), config.get_exclude())
+    }
+
+    /// Why a module shouldn't be walked. Only exclusions are checked, as its members may still be
+    /// included.
+    pub fn check_module(&self, path: &Path) -> Option<FilterReason> {
+        let segments = segments(path);
+        self.exclude
+            .iter()
+            .find(|pattern| pattern.matches(&segments))
+            .map(|pattern| FilterReason::Excluded(pattern.text.clone()))
+    }
+
+    /// Why an item should be left out.
+    pub fn check(&self, path: &Path) -> Option<FilterReason> {
+        self.check_module(path).or_else(|| {
+            if self.include.is_empty() || self.is_included(path) {
+                None
+            } else {
+                Some(FilterReason::NotIncluded)
+            }
+        })
+    }
+
+    /// Check if `path` or one of its modules matches an include pattern.
+    fn is_included(&self, path: &Path) -> bool {
+        let segments = segments(path);
+        (1..=segments.len()).any(|length| self.include.iter().any(|pattern| pattern.matches(&segments[..length])))
+    }
+
+    /// Removes the filtered modules and items of `library`, returning them.
+    pub fn apply_library(&self, library: &mut Library) -> Vec<Filtered> {
+        self.apply_module(&mut library.root_module)
+    }
+
+    /// Removes the filtered modules and items within the root module `module`, returning them.
+    pub fn apply_module(&self, module: &mut Module) -> Vec<Filtered> {
+        let mut filtered = Vec::new();
+        let path = Path::from(module.identifier.clone());
+        self.filter_module(module, &path, &mut filtered);
+        filtered
+    }
+
+    fn filter_module(&self, module: &mut Module, path: &Path, filtered: &mut Vec<Filtered>) {
+        self.retain(&mut module.objects, path, |object| &object.identifier, filtered);
+        self.retain(&mut module.functions, path, |function| &function.identifier, filtered);
+        self.retain(&mut module.types, path, |type_| &type_.identifier, filtered);
+        self.retain(&mut module.interfaces, path, |interface| &interface.identifier, filtered);
+        for interface in &mut module.interfaces {
+            // Members follow their interface, so only exclusions apply to them.
+            let path = path.clone().join(interface.identifier.clone());
+            self.retain_excluded(&mut interface.objects, &path, |object| &object.identifier, filtered);
+            self.retain_excluded(&mut interface.functions, &path, |function| &function.identifier, filtered);
+            self.retain_excluded(&mut interface.methods, &path, |method| &method.identifier, filtered);
+        }
+        let modules = std::mem::take(&mut module.modules);
+        for mut child in modules {
+            let path = path.clone().join(child.identifier.clone());
+            if let Some(reason) = self.check_module(&path) {
+                filtered.push(Filtered { path, reason });
+                continue;
+            }
+            self.filter_module(&mut child, &path, filtered);
+            if !self.include.is_empty() && !self.is_included(&path) && child.is_empty() {
+                filtered.push(Filtered { path, reason: FilterReason::NotIncluded });
+                continue;
+            }
+            module.modules.push(child);
+        }
+    }
+
+    fn retain<T>(&self, items: &mut Vec<T>, parent: &Path, identifier: impl Fn(&T) -> &Identifier, filtered: &mut Vec<Filtered>) {
+        items.retain(|item| {
+            let path = parent.clone().join(identifier(item).clone());
+            match self.check(&path) {
+                Some(reason) => {
+                    filtered.push(Filtered { path, reason });
+                    false
+                },
+                None => true
+            }
+        });
+    }
+
+    fn retain_excluded<T>(&self, items: &mut Vec<T>, parent: &Path, identifier: impl Fn(&T) -> &Identifier, filtered: &mut Vec<Filtered>) {
+        items.retain(|item| {
+            let path = parent.clone().join(identifier(item).clone());
+            match self.check_module(&path) {
+                Some(reason) => {
+                    filtered.push(Filtered { path, reason });
+                    false
+                },
+                None => true
+            }
+        });
+    }
+}
+
+fn segments(path: &Path) -> Vec<&str> {
+    path.segments.iter().map(|segment| segment.identifier.name.as_str()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ligen_ir::{Function, TypeDefinition};
+
+    fn module(identifier: &str, modules: Vec<Module>) -> Module {
+        let functions = vec![Function { identifier: "function".into(), ..Default::default() }];
+        let types = vec![TypeDefinition { identifier: "Type".into(), ..Default::default() }];
+        Module { identifier: identifier.into(), functions, types, modules, ..Default::default() }
+    }
+
+    fn library() -> Library {
+        let internal = module("internal", vec![module("detail", vec![])]);
+        let api = module("api", vec![module("tests", vec![])]);
+        let root_module = module("package", vec![api, internal, module("_vendor", vec![])]);
+        Library { identifier: "package".into(), root_module, ..Default::default() }
+    }
+
+    #[test]
+    fn patterns() {
+        let filter = PathFilter::new(vec![], vec!["tests", "internal.*", "package::**::Type", "_v?ndor"]);
+        assert_eq!(filter.check(&"package::api::tests".into()), Some(FilterReason::Excluded("tests".into())));
+        assert_eq!(filter.check(&"package::internal::detail".into()), Some(FilterReason::Excluded("internal.*".into())));
+        assert_eq!(filter.check(&"package::internal".into()), None);
+        assert_eq!(filter.check(&"package::api::Type".into()), Some(FilterReason::Excluded("package::**::Type".into())));
+        assert_eq!(filter.check(&"package::_vendor".into()), Some(FilterReason::Excluded("_v?ndor".into())));
+        assert_eq!(filter.check(&"package::api::function".into()), None);
+    }
+
+    #[test]
+    fn filtered_library() {
+        let mut library = library();
+        let filtered = PathFilter::new(vec!["api"], vec!["tests"]).apply_library(&mut library);
+        assert_eq!(filtered.iter().map(|filtered| filtered.to_string()).collect::<Vec<_>>(), vec![
+            "package::function: not included by any pattern",
+            "package::Type: not included by any pattern",
+            "package::api::tests: excluded by `tests`",
+            "package::internal::function: not included by any pattern",
+            "package::internal::Type: not included by any pattern",
+            "package::internal::detail::function: not included by any pattern",
+            "package::internal::detail::Type: not included by any pattern",
+            "package::internal::detail: not included by any pattern",
+            "package::internal: not included by any pattern",
+            "package::_vendor::function: not included by any pattern",
+            "package::_vendor::Type: not included by any pattern",
+            "package::_vendor: not included by any pattern"
+        ]);
+        assert_eq!(library.root_module.modules.len(), 1);
+        assert_eq!(library.root_module.modules[0].functions.len(), 1);
+    }
+}
@@ -2,7 +2,16 @@ pub mod prelude;
 pub mod assert;
 pub mod utils;
 mod validator;
+mod directive;
+mod filter;
+mod cache;
+mod plugin;
+pub mod parallel;
 mod parser;
 
 pub use parser::*;
-pub use validator::*;
\ No newline at end of file
+pub use validator::*;
+pub use directive::*;
+pub use filter::*;
+pub use cache::*;
+pub use plugin::*;
\ No newline at end of file
@@ -0,0 +1,41 @@
+//! Parsing files on a pool of worker threads, sized by `ligen::workers`.
+
+use rayon::prelude::*;
+use crate::prelude::*;
+use crate::ParserConfig;
+
+/// Runs `run` on a pool with the configured number of workers, so the [`map`] calls within it
+/// share the pool. If it's already running on a pool, `run` is called directly.
+pub fn with_workers<R: Send>(config: &ParserConfig, run: impl FnOnce() -> R + Send) -> Result<R> {
+    if rayon::current_thread_index().is_some() {
+        return Ok(run());
+    }
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(config.get_workers())
+        .build()
+        .map_err(|e| Error::Message(format!("Failed to create the worker pool: {}", e)))?;
+    Ok(pool.install(run))
+}
+
+/// Maps `items` on the workers. The results are in the order of `items`, regardless of which
+/// worker finishes first.
+pub fn map<T: Send, R: Send>(items: Vec<T>, f: impl Fn(T) -> R + Sync + Send) -> Vec<R> {
+    items.into_par_iter().map(f).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ordered_results() -> Result<()> {
+        let mut config = ParserConfig::default();
+        config.set_workers(4);
+        let results = with_workers(&config, || map((0..100u64).collect(), |value| {
+            std::thread::sleep(std::time::Duration::from_micros(100 - value));
+            value * 2
+        }))?;
+        assert_eq!(results, (0..100).map(|value| value * 2).collect::<Vec<_>>());
+        Ok(())
+    }
+}
@@ -0,0 +1,310 @@
+//! Discovery and layering of the parser configuration.
+//!
+//! Values are layered from the lowest to the highest precedence:
+//! 1. The parser's defaults.
+//! 2. The first configuration file found in the input's directory or its ancestors: `ligen.toml`,
+//!    the `[tool.ligen]` table of `pyproject.toml` or the `[package.metadata.ligen]` table of
+//!    `Cargo.toml`. `ligen.toml` holds the whole configuration, e.g. a `[ligen]` table, while the
+//!    tables of the other files hold the `ligen` group.
+//! 3. `LIGEN_...` environment variables overriding known keys, e.g. `LIGEN_ONLY_PARSE_SYMBOLS`
+//!    for `ligen::only-parse-symbols` or `LIGEN_PYTHON__CLASS_VARIABLES_AS_PROPERTIES` for
+//!    `ligen::python::class_variables_as_properties`. Keys are known if they are set by the layers
+//!    below or declared by the parser's schema.
+//! 4. `key=value` overrides, e.g. `ligen::only-parse-symbols=true`, as given by `--set`.
+//!
+//! Values are given as text, e.g. `true`, `10`, `name` or `[tests, internal.*]` for lists.
+
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::path::PathBuf;
+use ligen_ir::{Literal, Path};
+use crate::prelude::*;
+use super::{ConfigSchema, ParserConfig, ParserConfigSet};
+
+/// Where an effective configuration value comes from.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConfigSource {
+    /// The parser's defaults.
+    Default,
+    /// A configuration file.
+    File(PathBuf),
+    /// An environment variable.
+    Environment(String),
+    /// A `key=value` override.
+    Override
+}
+
+impl Display for ConfigSource {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Default => write!(f, "default"),
+            Self::File(path) => write!(f, "file {}", path.display()),
+            Self::Environment(name) => write!(f, "environment variable {}", name),
+            Self::Override => write!(f, "override")
+        }
+    }
+}
+
+/// A configuration and the source of each of its values.
+#[derive(Debug, Clone, Default)]
+pub struct LoadedConfig {
+    pub config: ParserConfig,
+    sources: BTreeMap<String, ConfigSource>
+}
+
+impl LoadedConfig {
+    /// Gets the source of the value at the given path.
+    pub fn source<P: Into<Path>>(&self, path: P) -> Option<&ConfigSource> {
+        self.sources.get(&path.into().to_string())
+    }
+
+    fn set(&mut self, path: Path, value: Literal, source: ConfigSource) {
+        self.sources.insert(path.to_string(), source);
+        self.config.set(path, value);
+    }
+
+    fn layer(&mut self, config: ParserConfig, source: ConfigSource) {
+        for (path, value) in config.iter() {
+            self.set(path, value, source.clone());
+        }
+    }
+}
+
+impl Display for LoadedConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let values = self
+            .config
+            .iter()
+            .map(|(path, value)| (path.to_string(), value))
+            .collect::<BTreeMap<_, _>>();
+        for (path, value) in values {
+            let source = self.sources.get(&path).unwrap_or(&ConfigSource::Default);
+            writeln!(f, "{} = {} ({})", path, value, source)?;
+        }
+        Ok(())
+    }
+}
+
+/// Loads the parser configuration from its layers.
+#[derive(Debug, Default, Clone)]
+pub struct ConfigLoader {
+    schema: ConfigSchema,
+    file: Option<PathBuf>,
+    environment: Vec<(String, String)>,
+    overrides: Vec<String>
+}
+
+impl ConfigLoader {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Knows the keys declared by `schema`, e.g. the parser's schema.
+    pub fn with_schema(mut self, schema: ConfigSchema) -> Self {
+        self.schema = schema;
+        self
+    }
+
+    /// Uses the first configuration file found in `path`'s directory or its ancestors.
+    pub fn discover(mut self, path: impl AsRef<std::path::Path>) -> Self {
+        self.file = Self::find_file(path);
+        self
+    }
+
+    /// Uses the configuration file at `path`.
+    pub fn with_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.file = Some(path.into());
+        self
+    }
+
+    /// Uses the `LIGEN_...` variables of `environment`, e.g. `std::env::vars()`.
+    pub fn with_environment(mut self, environment: impl IntoIterator<Item = (String, String)>) -> Self {
+        self.environment = environment
+            .into_iter()
+            .filter(|(name, _)| name.starts_with("LIGEN_"))
+            .collect();
+        self
+    }
+
+    /// Overrides a value with a `key=value` pair.
+    pub fn with_override(mut self, key_value: impl Into<String>) -> Self {
+        self.overrides.push(key_value.into());
+        self
+    }
+
+    /// Finds the configuration file in `path`'s directory or its ancestors. Files without a ligen
+    /// configuration are skipped, but invalid ones are found, so loading them fails.
+    pub fn find_file(path: impl AsRef<std::path::Path>) -> Option<PathBuf> {
+        let path = path.as_ref();
+        let directory = if path.is_file() { path.parent()? } else { path };
+        directory
+            .ancestors()
+            .find_map(|directory| {
+                ["ligen.toml", "pyproject.toml", "Cargo.toml"]
+                    .into_iter()
+                    .map(|name| directory.join(name))
+                    .find(|path| !matches!(read_file(path), Ok(None)))
+            })
+    }
+
+    /// Layers the configuration on top of `defaults`.
+    pub fn load(&self, defaults: ParserConfig) -> Result<LoadedConfig> {
+        let mut loaded = LoadedConfig::default();
+        loaded.layer(defaults, ConfigSource::Default);
+        if let Some(path) = &self.file {
+            let config = read_file(path)?
+                .ok_or_else(|| Error::Message(format!("{} has no ligen configuration.", path.display())))?;
+            loaded.layer(config, ConfigSource::File(path.clone()));
+        }
+        // Options with a `*` segment stand for many keys, so they have no variable.
+        let options = self
+            .schema
+            .options
+            .iter()
+            .filter(|option| !option.path.segments.iter().any(|segment| segment.identifier == "*"))
+            .map(|option| option.path.clone());
+        let keys = loaded
+            .config
+            .iter()
+            .map(|(path, _)| path)
+            .chain(options)
+            .map(|path| (environment_name(&path), path))
+            .collect::<BTreeMap<_, _>>();
+        for (name, value) in &self.environment {
+            if let Some(path) = keys.get(name) {
+                loaded.set(path.clone(), parse_value(value), ConfigSource::Environment(name.clone()));
+            }
+        }
+        for key_value in &self.overrides {
+            let (key, value) = key_value
+                .split_once('=')
+                .ok_or_else(|| Error::Message(format!("Expected `key=value`, found `{}`.", key_value)))?;
+            let key = key.trim();
+            let path = if key.contains("::") { Path::from(key) } else { Path::from_string_with_separator(key, ".") };
+            loaded.set(path, parse_value(value.trim()), ConfigSource::Override);
+        }
+        Ok(loaded)
+    }
+}
+
+/// Reads the configuration of a `ligen.toml`, `pyproject.toml` or `Cargo.toml` file, if it has one.
+fn read_file(path: &std::path::Path) -> Result<Option<ParserConfig>> {
+    if !path.is_file() {
+        return Ok(None);
+    }
+    let contents = std::fs::read_to_string(path)?;
+    let table = contents
+        .parse::<toml::Table>()
+        .map_err(|e| Error::Message(format!("Failed to parse {}: {}", path.display(), e)))?;
+    let section: &[&str] = match path.file_name().and_then(|name| name.to_str()) {
+        Some("pyproject.toml") => &["tool", "ligen"],
+        Some("Cargo.toml") => &["package", "metadata", "ligen"],
+        _ => &[]
+    };
+    let table = if section.is_empty() {
+        table
+    } else {
+        let mut value = &toml::Value::Table(table);
+        for key in section {
+            match value.get(key) {
+                Some(inner) => value = inner,
+                None => return Ok(None)
+            }
+        }
+        let mut table = toml::Table::new();
+        table.insert("ligen".into(), value.clone());
+        table
+    };
+    toml::Value::Table(table)
+        .try_into::<ParserConfig>()
+        .map(Some)
+        .map_err(|e| Error::Message(format!("Invalid configuration in {}: {}", path.display(), e)))
+}
+
+/// Name of the environment variable overriding `path`.
+fn environment_name(path: &Path) -> String {
+    let mut segments = path
+        .segments
+        .iter()
+        .map(|segment| segment.identifier.name.replace('-', "_").to_uppercase())
+        .collect::<Vec<_>>();
+    if segments.first().is_some_and(|segment| segment == "LIGEN") {
+        segments.remove(0);
+    }
+    format!("LIGEN_{}", segments.join("__"))
+}
+
+/// Parses a value given as text, e.g. `true`, `10`, `name` or `[a, b]`.
+fn parse_value(value: &str) -> Literal {
+    if let Some(values) = value.strip_prefix('[').and_then(|value| value.strip_suffix(']')) {
+        let values = values
+            .split(',')
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(parse_value)
+            .collect();
+        Literal::Vector(values)
+    } else if let Ok(boolean) = value.parse::<bool>() {
+        Literal::Boolean(boolean)
+    } else if let Ok(integer) = value.parse::<i64>() {
+        Literal::Integer(integer)
+    } else if let Ok(float) = value.parse::<f64>() {
+        Literal::Float(float)
+    } else {
+        let unquoted = value
+            .strip_prefix('"')
+            .and_then(|value| value.strip_suffix('"'))
+            .unwrap_or(value);
+        Literal::String(unquoted.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ParserConfigGet;
+
+    #[test]
+    fn layered_config() -> Result<()> {
+        let directory = std::env::temp_dir().join("ligen-config-loader-test");
+        let _ = std::fs::remove_dir_all(&directory);
+        let package = directory.join("package");
+        std::fs::create_dir_all(&package)?;
+        let pyproject = directory.join("pyproject.toml");
+        std::fs::write(&pyproject, "[tool.ligen]\nonly-parse-symbols = true\n\n[tool.ligen.python]\nclass_variables_as_properties = false\n")?;
+        std::fs::write(directory.join("Cargo.toml"), "[package]\nname = \"package\"\n")?;
+
+        let mut defaults = ParserConfig::default();
+        defaults.set("ligen::python::class_variables_as_properties", true);
+        defaults.set("ligen::default-name", "library");
+        let loaded = ConfigLoader::new()
+            .with_schema(ConfigSchema::common())
+            .discover(&package)
+            .with_environment(vec![
+                ("LIGEN_PYTHON__CLASS_VARIABLES_AS_PROPERTIES".to_string(), "true".to_string()),
+                ("LIGEN_WORKERS".to_string(), "2".to_string()),
+                ("LIGEN_UNKNOWN".to_string(), "1".to_string())
+            ])
+            .with_override("ligen.default-name=bindings")
+            .with_override("ligen::exclude=[tests, \"internal.*\"]")
+            .load(defaults)?;
+        assert_eq!(loaded.config.get("ligen::only-parse-symbols"), Some(&true.into()));
+        assert_eq!(loaded.source("ligen::only-parse-symbols"), Some(&ConfigSource::File(pyproject)));
+        assert_eq!(loaded.config.get("ligen::python::class_variables_as_properties"), Some(&true.into()));
+        assert_eq!(loaded.source("ligen::python::class_variables_as_properties"), Some(&ConfigSource::Environment("LIGEN_PYTHON__CLASS_VARIABLES_AS_PROPERTIES".into())));
+        assert_eq!(loaded.config.get("ligen::default-name"), Some(&"bindings".into()));
+        assert_eq!(loaded.source("ligen::default-name"), Some(&ConfigSource::Override));
+        assert_eq!(loaded.config.get("ligen::unknown"), None);
+        assert_eq!(loaded.config.get_workers(), 2);
+        assert_eq!(loaded.source("ligen::workers"), Some(&ConfigSource::Environment("LIGEN_WORKERS".into())));
+        assert_eq!(loaded.config.get_exclude(), vec!["tests", "internal.*"]);
+        assert!(ConfigLoader::new().with_override("ligen::only-parse-symbols").load(Default::default()).is_err());
+
+        // An invalid file isn't skipped for the valid ones above it.
+        std::fs::write(package.join("ligen.toml"), "[ligen\n")?;
+        assert_eq!(ConfigLoader::find_file(&package), Some(package.join("ligen.toml")));
+        assert!(ConfigLoader::new().discover(&package).load(Default::default()).is_err());
+        std::fs::remove_dir_all(&directory)?;
+        Ok(())
+    }
+}
@@ -1,5 +1,9 @@
 mod group;
+mod loader;
+mod schema;
 pub use group::*;
+pub use loader::*;
+pub use schema::*;
 
 use crate::prelude::*;
 
@@ -37,6 +41,53 @@ impl ParserConfig {
             .cloned()
             .unwrap_or(false)
     }
+
+    /// Sets the number of worker threads parsing files, or 0 to use one per CPU.
+    pub fn set_workers(&mut self, workers: u64) {
+        self.set("ligen::workers", workers);
+    }
+
+    /// Number of worker threads parsing files, or 0 to use one per CPU.
+    pub fn get_workers(&self) -> usize {
+        match self.get("ligen::workers") {
+            Some(Literal::Integer(workers)) => (*workers).max(0) as usize,
+            Some(Literal::UnsignedInteger(workers)) => *workers as usize,
+            _ => 0
+        }
+    }
+
+    /// Sets the patterns of the modules and items to parse. Everything is parsed if there are none.
+    pub fn set_include<S: Into<String>>(&mut self, patterns: Vec<S>) {
+        self.set("ligen::include", Literal::Vector(patterns.into_iter().map(|pattern| Literal::String(pattern.into())).collect()));
+    }
+
+    /// Patterns of the modules and items to parse.
+    pub fn get_include(&self) -> Vec<String> {
+        self.get_strings("ligen::include")
+    }
+
+    /// Sets the patterns of the modules and items to leave out.
+    pub fn set_exclude<S: Into<String>>(&mut self, patterns: Vec<S>) {
+        self.set("ligen::exclude", Literal::Vector(patterns.into_iter().map(|pattern| Literal::String(pattern.into())).collect()));
+    }
+
+    /// Patterns of the modules and items to leave out.
+    pub fn get_exclude(&self) -> Vec<String> {
+        self.get_strings("ligen::exclude")
+    }
+
+    fn get_strings(&self, path: &str) -> Vec<String> {
+        match self.get(path) {
+            Some(Literal::Vector(values) | Literal::Tuple(values)) => values
+                .iter()
+                .filter_map(|value| match value {
+                    Literal::String(value) => Some(value.clone()),
+                    _ => None
+                })
+                .collect(),
+            _ => Default::default()
+        }
+    }
 }
 
 pub trait ParserConfigGet {
@@ -0,0 +1,200 @@
+//! Declaration of the options a parser understands.
+
+use std::fmt::{Display, Formatter};
+use ligen_ir::{Literal, Path};
+use crate::{Diagnostic, DiagnosticKind, Severity};
+use super::{ParserConfig, ParserConfigSet};
+
+/// Type of an option's value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OptionKind {
+    Boolean,
+    Integer,
+    Float,
+    String,
+    /// A list of strings.
+    Strings
+}
+
+impl OptionKind {
+    /// Check if `literal` is a value of this kind.
+    pub fn accepts(&self, literal: &Literal) -> bool {
+        match (self, literal) {
+            (Self::Boolean, Literal::Boolean(_)) => true,
+            (Self::Integer, Literal::Integer(_) | Literal::UnsignedInteger(_)) => true,
+            (Self::Float, Literal::Float(_) | Literal::Integer(_) | Literal::UnsignedInteger(_)) => true,
+            (Self::String, Literal::String(_)) => true,
+            (Self::Strings, Literal::Vector(values) | Literal::Tuple(values)) => values.iter().all(|value| matches!(value, Literal::String(_))),
+            _ => false
+        }
+    }
+}
+
+impl Display for OptionKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Boolean => write!(f, "boolean"),
+            Self::Integer => write!(f, "integer"),
+            Self::Float => write!(f, "float"),
+            Self::String => write!(f, "string"),
+            Self::Strings => write!(f, "list of strings")
+        }
+    }
+}
+
+/// A configuration option. A `*` segment in its path matches any segment, e.g.
+/// `ligen::python::types::*`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigOption {
+    pub path: Path,
+    pub kind: OptionKind,
+    pub description: String,
+    pub default: Option<Literal>,
+    /// Values the option is restricted to. Empty if any value of its kind is allowed.
+    pub allowed_values: Vec<Literal>
+}
+
+impl ConfigOption {
+    pub fn new(path: impl Into<Path>, kind: OptionKind, description: impl Into<String>) -> Self {
+        let path = path.into();
+        let description = description.into();
+        let default = None;
+        let allowed_values = Default::default();
+        Self { path, kind, description, default, allowed_values }
+    }
+
+    pub fn with_default(mut self, default: impl Into<Literal>) -> Self {
+        self.default = Some(default.into());
+        self
+    }
+
+    pub fn with_allowed_values<L: Into<Literal>>(mut self, values: Vec<L>) -> Self {
+        self.allowed_values = values.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Check if the option is the one at `path`.
+    pub fn matches(&self, path: &Path) -> bool {
+        self.path.segments.len() == path.segments.len()
+            && self
+                .path
+                .segments
+                .iter()
+                .zip(&path.segments)
+                .all(|(option, segment)| option.identifier == "*" || option.identifier == segment.identifier)
+    }
+}
+
+/// The options a parser understands.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ConfigSchema {
+    pub options: Vec<ConfigOption>
+}
+
+impl ConfigSchema {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Options understood by every parser.
+    pub fn common() -> Self {
+        Self::new()
+            .with_option(
+                ConfigOption::new("ligen::only-parse-symbols", OptionKind::Boolean, "Parses only the symbols, skipping their definitions.")
+                    .with_default(false)
+            )
+            .with_option(ConfigOption::new("ligen::workers", OptionKind::Integer, "Number of worker threads parsing files. One per CPU if it's 0 or unset."))
+            .with_option(ConfigOption::new("ligen::include", OptionKind::Strings, "Patterns of the modules and items to parse, e.g. `package.api`. Everything is parsed if there are none."))
+            .with_option(ConfigOption::new("ligen::exclude", OptionKind::Strings, "Patterns of the modules and items to leave out, e.g. `tests` or `internal.*`."))
+    }
+
+    pub fn with_option(mut self, option: ConfigOption) -> Self {
+        self.options.push(option);
+        self
+    }
+
+    /// Gets the option at `path`.
+    pub fn get<P: Into<Path>>(&self, path: P) -> Option<&ConfigOption> {
+        let path = path.into();
+        self.options.iter().find(|option| option.matches(&path))
+    }
+
+    /// A configuration with the default value of every option.
+    pub fn defaults(&self) -> ParserConfig {
+        let mut config = ParserConfig::new();
+        for option in &self.options {
+            if let Some(default) = &option.default {
+                config.set(option.path.clone(), default.clone());
+            }
+        }
+        config
+    }
+
+    /// Checks `config` against the options. Unknown keys are warnings, as other parsers may
+    /// understand them, while values of the wrong kind or not allowed are errors.
+    pub fn validate(&self, config: &ParserConfig) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+        for (path, value) in config.iter() {
+            match self.get(path.clone()) {
+                Some(option) => if !option.kind.accepts(&value) {
+                    let message = format!("expected a {}, found `{}`", option.kind, value);
+                    diagnostics.push(Diagnostic::new(Severity::Error, DiagnosticKind::InvalidOption, path, message));
+                } else if !option.allowed_values.is_empty() && !option.allowed_values.contains(&value) {
+                    let allowed = option.allowed_values.iter().map(|value| format!("`{}`", value)).collect::<Vec<_>>().join(", ");
+                    let message = format!("`{}` isn't one of {}", value, allowed);
+                    diagnostics.push(Diagnostic::new(Severity::Error, DiagnosticKind::InvalidOption, path, message));
+                },
+                None => {
+                    let message = match self.suggestion(&path) {
+                        Some(option) => format!("unknown option, did you mean `{}`?", option.path),
+                        None => "unknown option".to_string()
+                    };
+                    diagnostics.push(Diagnostic::new(Severity::Warning, DiagnosticKind::UnknownOption, path, message));
+                }
+            }
+        }
+        diagnostics
+    }
+
+    /// An option whose path only differs from `path` in case or in `-` and `_`.
+    fn suggestion(&self, path: &Path) -> Option<&ConfigOption> {
+        let normalize = |path: &Path| path.to_string().replace('-', "_").to_lowercase();
+        let path = normalize(path);
+        self.options.iter().find(|option| normalize(&option.path) == path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn schema() -> ConfigSchema {
+        ConfigSchema::common()
+            .with_option(ConfigOption::new("ligen::format", OptionKind::String, "Output format.").with_default("json").with_allowed_values(vec!["json", "yaml"]))
+            .with_option(ConfigOption::new("ligen::types::*", OptionKind::String, "Type mappings."))
+    }
+
+    #[test]
+    fn defaults() {
+        let config = schema().defaults();
+        assert_eq!(config.iter().count(), 2);
+        assert!(schema().validate(&config).is_empty());
+    }
+
+    #[test]
+    fn validation() {
+        let mut config = ParserConfig::new();
+        config.set("ligen::only_parse_symbols", true);
+        config.set("ligen::format", "xml");
+        config.set("ligen::types::int", 1);
+        config.set("ligen::types::str", "String");
+        let mut diagnostics = schema().validate(&config);
+        diagnostics.sort_by_key(|diagnostic| diagnostic.path.to_string());
+        let messages = diagnostics.iter().map(|diagnostic| (diagnostic.severity, diagnostic.message.as_str())).collect::<Vec<_>>();
+        assert_eq!(messages, vec![
+            (Severity::Error, "`xml` isn't one of `json`, `yaml`"),
+            (Severity::Warning, "unknown option, did you mean `ligen::only-parse-symbols`?"),
+            (Severity::Error, "expected a string, found `1`")
+        ]);
+    }
+}
@@ -4,6 +4,7 @@ pub mod config;
 pub use config::*;
 
 use ligen_common::Result;
+use crate::Filtered;
 
 pub trait Parser<Input> {
     type Output;
@@ -14,4 +15,12 @@ pub trait Parser<Input> {
     fn config(&self) -> ParserConfig {
         Default::default()
     }
+    /// Options the parser understands.
+    fn schema(&self) -> ConfigSchema {
+        ConfigSchema::common()
+    }
+    /// Modules and items left out by the include and exclude patterns so far.
+    fn filtered(&self) -> Vec<Filtered> {
+        Default::default()
+    }
 }
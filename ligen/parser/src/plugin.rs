@@ -0,0 +1,173 @@
+//! Parsers listed by name, so tools can offer every parser available to them.
+//!
+//! Each parser crate provides a [`ParserPlugin`], e.g. `ligen_python_parser::plugin()`, and tools
+//! register the ones they link with in [`ParserPlugins`]. Third-party crates can provide their own
+//! the same way.
+
+use std::fmt::{Debug, Formatter};
+use std::path::Path;
+use std::sync::Arc;
+use ligen_ir::{Library, Registry};
+use crate::prelude::*;
+use crate::{ConfigSchema, Filtered, Parser, ParserConfig};
+
+/// A parser of the libraries at a path and their dependencies.
+pub type DynParser = Box<dyn for<'a> Parser<&'a Path, Output = Registry>>;
+
+/// A parser and what it's about.
+#[derive(Clone)]
+pub struct ParserPlugin {
+    pub name: String,
+    pub description: String,
+    /// Inputs it parses, e.g. "Python package directory".
+    pub inputs: Vec<String>,
+    /// The parser's default configuration.
+    pub config: ParserConfig,
+    /// Options the parser understands.
+    pub schema: ConfigSchema,
+    create: Arc<dyn Fn() -> DynParser + Send + Sync>
+}
+
+impl ParserPlugin {
+    /// A plugin creating its parser with `create`.
+    pub fn new<P>(name: impl Into<String>, description: impl Into<String>, create: impl Fn() -> P + Send + Sync + 'static) -> Self
+    where P: for<'a> Parser<&'a Path, Output = Registry> + 'static
+    {
+        let parser = create();
+        let config = parser.config();
+        let schema = parser.schema();
+        let name = name.into();
+        let description = description.into();
+        let inputs = Default::default();
+        let create = Arc::new(move || Box::new(create()) as DynParser);
+        Self { name, description, inputs, config, schema, create }
+    }
+
+    /// A plugin of a parser of a single library, without its dependencies.
+    pub fn from_library_parser<P>(name: impl Into<String>, description: impl Into<String>, create: impl Fn() -> P + Send + Sync + 'static) -> Self
+    where P: for<'a> Parser<&'a Path, Output = Library> + 'static
+    {
+        Self::new(name, description, move || LibraryRegistryParser(create()))
+    }
+
+    pub fn with_input(mut self, input: impl Into<String>) -> Self {
+        self.inputs.push(input.into());
+        self
+    }
+
+    /// Creates the parser.
+    pub fn create(&self) -> DynParser {
+        (self.create)()
+    }
+}
+
+impl Debug for ParserPlugin {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ParserPlugin")
+            .field("name", &self.name)
+            .field("description", &self.description)
+            .field("inputs", &self.inputs)
+            .finish()
+    }
+}
+
+/// Parses a registry with the single library parsed by `P`.
+struct LibraryRegistryParser<P>(P);
+
+impl<'a, P: Parser<&'a Path, Output = Library>> Parser<&'a Path> for LibraryRegistryParser<P> {
+    type Output = Registry;
+    fn parse(&self, input: &'a Path, config: &ParserConfig) -> Result<Self::Output> {
+        let libraries = vec![self.0.parse(input, config)?];
+        Ok(Registry { libraries })
+    }
+    fn name(&self) -> &str {
+        self.0.name()
+    }
+    fn config(&self) -> ParserConfig {
+        self.0.config()
+    }
+    fn schema(&self) -> ConfigSchema {
+        self.0.schema()
+    }
+    fn filtered(&self) -> Vec<Filtered> {
+        self.0.filtered()
+    }
+}
+
+/// Parser plugins by name.
+#[derive(Debug, Clone, Default)]
+pub struct ParserPlugins {
+    plugins: Vec<ParserPlugin>
+}
+
+impl ParserPlugins {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Registers `plugin`, replacing the one with the same name.
+    pub fn register(&mut self, plugin: ParserPlugin) {
+        self.plugins.retain(|registered| !registered.name.eq_ignore_ascii_case(&plugin.name));
+        self.plugins.push(plugin);
+    }
+
+    pub fn with(mut self, plugin: ParserPlugin) -> Self {
+        self.register(plugin);
+        self
+    }
+
+    /// Gets the plugin named `name`, ignoring case.
+    pub fn get(&self, name: &str) -> Option<&ParserPlugin> {
+        self.plugins.iter().find(|plugin| plugin.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Creates the parser named `name`, ignoring case.
+    pub fn create(&self, name: &str) -> Result<DynParser> {
+        self.get(name)
+            .map(ParserPlugin::create)
+            .ok_or_else(|| Error::Message(format!("Parser \"{}\" not found. Expected one of: {}.", name, self.names().join(", "))))
+    }
+
+    pub fn names(&self) -> Vec<&str> {
+        self.plugins.iter().map(|plugin| plugin.name.as_str()).collect()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &ParserPlugin> {
+        self.plugins.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Default)]
+    struct LibraryParser;
+
+    impl Parser<&Path> for LibraryParser {
+        type Output = Library;
+        fn parse(&self, input: &Path, _config: &ParserConfig) -> Result<Self::Output> {
+            let identifier = input.display().to_string().as_str().into();
+            Ok(Library { identifier, ..Default::default() })
+        }
+        fn name(&self) -> &str {
+            "Library"
+        }
+    }
+
+    #[test]
+    fn plugins() -> Result<()> {
+        let plugins = ParserPlugins::new()
+            .with(ParserPlugin::from_library_parser("library", "Libraries.", LibraryParser::default).with_input("Library directory"))
+            .with(ParserPlugin::from_library_parser("Library", "Libraries, again.", LibraryParser::default));
+        assert_eq!(plugins.names(), vec!["Library"]);
+        assert_eq!(plugins.get("LIBRARY").map(|plugin| plugin.description.as_str()), Some("Libraries, again."));
+        let parser = plugins.create("library")?;
+        assert_eq!(parser.name(), "Library");
+        let registry = parser.parse(Path::new("name"), &Default::default())?;
+        assert_eq!(registry.libraries.len(), 1);
+        assert_eq!(registry.libraries[0].identifier, "name");
+        assert!(plugins.create("unknown").is_err());
+        Ok(())
+    }
+}
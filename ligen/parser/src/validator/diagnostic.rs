@@ -0,0 +1,69 @@
+use ligen_ir::Path;
+use crate::prelude::*;
+
+/// How serious a diagnostic is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Display)]
+pub enum Severity {
+    /// The IR is usable, but something might be missing, e.g. a type from an unparsed dependency.
+    #[display(fmt = "warning")]
+    Warning,
+    /// The IR is inconsistent.
+    #[display(fmt = "error")]
+    Error
+}
+
+/// What a diagnostic is about.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display)]
+pub enum DiagnosticKind {
+    /// A type reference that resolves to nothing.
+    #[display(fmt = "unresolved type")]
+    UnresolvedType,
+    /// An identifier defined more than once in the same module or interface.
+    #[display(fmt = "duplicate identifier")]
+    DuplicateIdentifier,
+    /// A literal incompatible with its declared type.
+    #[display(fmt = "incompatible literal")]
+    IncompatibleLiteral,
+    /// An empty or otherwise invalid identifier.
+    #[display(fmt = "invalid identifier")]
+    InvalidIdentifier,
+    /// An import that points nowhere.
+    #[display(fmt = "unresolved import")]
+    UnresolvedImport,
+    /// An unknown or malformed `ligen(...)` directive.
+    #[display(fmt = "invalid directive")]
+    InvalidDirective,
+    /// A configuration key no option is declared for.
+    #[display(fmt = "unknown option")]
+    UnknownOption,
+    /// A configuration value of the wrong kind or not allowed.
+    #[display(fmt = "invalid option")]
+    InvalidOption
+}
+
+/// A problem found while validating the IR or the configuration.
+#[derive(Debug, Clone, PartialEq, Display)]
+#[display(fmt = "{} [{}] {}: {}", severity, kind, path, message)]
+pub struct Diagnostic {
+    /// Diagnostic severity.
+    pub severity: Severity,
+    /// Diagnostic kind.
+    pub kind: DiagnosticKind,
+    /// Absolute path of the item or configuration key where the problem was found.
+    pub path: Path,
+    /// Human readable description.
+    pub message: String
+}
+
+impl Diagnostic {
+    /// Creates a new diagnostic.
+    pub fn new(severity: Severity, kind: DiagnosticKind, path: Path, message: impl Into<String>) -> Self {
+        let message = message.into();
+        Self { severity, kind, path, message }
+    }
+
+    /// Check if the diagnostic is an error.
+    pub fn is_error(&self) -> bool {
+        self.severity == Severity::Error
+    }
+}
@@ -0,0 +1,396 @@
+use std::collections::HashSet;
+use ligen_ir::{Attributes, Function, Identifier, Import, Interface, KindDefinition, Library, Literal, Method, Module, Object, Parameter, Path, Registry, Resolution, Type, TypeDefinition};
+use crate::prelude::*;
+use crate::{Directive, ParserConfig, Validator};
+use super::{Diagnostic, DiagnosticKind, Severity};
+
+/// Validates whole libraries, reporting dangling references, duplicates, incompatible literals
+/// and invalid identifiers.
+#[derive(Default)]
+pub struct LibraryValidator {}
+
+impl LibraryValidator {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Checks `library` on its own, so references to other libraries are unresolved.
+    pub fn check_library(&self, library: &Library) -> Vec<Diagnostic> {
+        Checker::new(Context::Library(library), library).check()
+    }
+
+    /// Checks every library in `registry`, resolving references across libraries.
+    pub fn check_registry(&self, registry: &Registry) -> Vec<Diagnostic> {
+        registry
+            .libraries
+            .iter()
+            .flat_map(|library| Checker::new(Context::Registry(registry), library).check())
+            .collect()
+    }
+}
+
+impl Validator for LibraryValidator {
+    type Input = Library;
+    fn validate(&self, library: &mut Library, _config: &ParserConfig) -> Result<()> {
+        let errors = self
+            .check_library(library)
+            .into_iter()
+            .filter(Diagnostic::is_error)
+            .map(|diagnostic| diagnostic.to_string())
+            .collect::<Vec<_>>();
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::Message(errors.join("\n")))
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Context<'a> {
+    Library(&'a Library),
+    Registry(&'a Registry)
+}
+
+struct Checker<'a> {
+    context: Context<'a>,
+    library: &'a Library,
+    diagnostics: Vec<Diagnostic>
+}
+
+impl<'a> Checker<'a> {
+    fn new(context: Context<'a>, library: &'a Library) -> Self {
+        let diagnostics = Default::default();
+        Self { context, library, diagnostics }
+    }
+
+    fn check(mut self) -> Vec<Diagnostic> {
+        let library = self.library;
+        let root_module = &library.root_module;
+        // The root module is named after the library, which follows packaging rules instead.
+        self.check_module(root_module, Path::from(root_module.identifier.clone()));
+        self.diagnostics
+    }
+
+    fn resolve(&self, path: &Path, module_path: &Path) -> Option<Resolution<'a>> {
+        match self.context {
+            Context::Library(library) => library.resolve(path, module_path),
+            Context::Registry(registry) => registry.resolve(&self.library.identifier, path, module_path)
+        }
+    }
+
+    fn report(&mut self, severity: Severity, kind: DiagnosticKind, path: &Path, message: impl Into<String>) {
+        self.diagnostics.push(Diagnostic::new(severity, kind, path.clone(), message));
+    }
+
+    fn check_module(&mut self, module: &Module, path: Path) {
+        self.check_directives(&module.attributes, &path);
+        self.check_duplicates(&path, "module", module.modules.iter().map(|module| (&module.identifier, &module.attributes)));
+        self.check_duplicates(&path, "type", module.types.iter().map(|type_| (&type_.identifier, &type_.attributes)));
+        self.check_duplicates(&path, "function", module.functions.iter().map(|function| (&function.identifier, &function.attributes)));
+        self.check_duplicates(&path, "object", module.objects.iter().map(|object| (&object.identifier, &object.attributes)));
+        for import in &module.imports {
+            self.check_import(import, &path);
+        }
+        for type_ in &module.types {
+            self.check_type_definition(type_, &path);
+        }
+        for function in &module.functions {
+            self.check_function(function, &path, &path.clone().join(function.identifier.clone()));
+        }
+        for object in &module.objects {
+            self.check_object(object, &path, &path.clone().join(object.identifier.clone()));
+        }
+        // Types may be implemented across several blocks, so interfaces sharing an identifier aren't duplicates.
+        for interface in &module.interfaces {
+            self.check_interface(interface, &path);
+        }
+        for sub_module in &module.modules {
+            let sub_module_path = path.clone().join(sub_module.identifier.clone());
+            self.check_identifier(&sub_module.identifier, &sub_module_path);
+            self.check_module(sub_module, sub_module_path);
+        }
+    }
+
+    fn check_import(&mut self, import: &Import, module_path: &Path) {
+        self.check_directives(&import.attributes, module_path);
+        if let Some(renaming) = &import.renaming {
+            self.check_identifier(renaming, &module_path.clone().join(renaming.clone()));
+        }
+        if import.path.is_empty() {
+            self.report(Severity::Error, DiagnosticKind::UnresolvedImport, module_path, "empty import path");
+            return;
+        }
+        let is_glob = import.path.last().identifier.name == "*";
+        let target = if is_glob { import.path.clone().without_last() } else { import.path.clone() };
+        // Imports resolving to themselves or to other imports couldn't be followed.
+        let resolved = self
+            .resolve(&target, module_path)
+            .map(|resolution| if is_glob { resolution.item.is_module() } else { !resolution.item.is_import() })
+            .unwrap_or_default();
+        if !resolved {
+            self.report(Severity::Warning, DiagnosticKind::UnresolvedImport, module_path, format!("import `{}` points nowhere", import.path));
+        }
+    }
+
+    fn check_type_definition(&mut self, type_definition: &TypeDefinition, module_path: &Path) {
+        let path = module_path.clone().join(type_definition.identifier.clone());
+        self.check_identifier(&type_definition.identifier, &path);
+        self.check_directives(&type_definition.attributes, &path);
+        let generics = type_definition
+            .generics
+            .types
+            .iter()
+            .filter(|generic| !generic.path.is_empty())
+            .map(|generic| generic.path.last().identifier.clone())
+            .collect::<Vec<_>>();
+        for interface in &type_definition.interfaces {
+            self.check_type(&Type::from(interface.clone()), &generics, module_path, &path);
+        }
+        match &type_definition.definition {
+            KindDefinition::Structure(structure) => {
+                let identifiers = structure.fields.iter().filter_map(|field| field.identifier.as_ref().map(|identifier| (identifier, &field.attributes)));
+                self.check_duplicates(&path, "field", identifiers);
+                for field in &structure.fields {
+                    let field_path = field.identifier.as_ref().map_or_else(|| path.clone(), |identifier| path.clone().join(identifier.clone()));
+                    if let Some(identifier) = &field.identifier {
+                        self.check_identifier(identifier, &field_path);
+                    }
+                    self.check_directives(&field.attributes, &field_path);
+                    self.check_type(&field.type_, &generics, module_path, &path);
+                }
+            },
+            KindDefinition::Enumeration(enumeration) => {
+                self.check_duplicates(&path, "variant", enumeration.variants.iter().map(|variant| (&variant.identifier, &variant.attributes)));
+                for variant in &enumeration.variants {
+                    let variant_path = path.clone().join(variant.identifier.clone());
+                    self.check_identifier(&variant.identifier, &variant_path);
+                    self.check_directives(&variant.attributes, &variant_path);
+                }
+            },
+            KindDefinition::TypeAlias(type_alias) => self.check_type(&type_alias.type_, &generics, module_path, &path)
+        }
+    }
+
+    fn check_interface(&mut self, interface: &Interface, module_path: &Path) {
+        let path = module_path.clone().join(interface.identifier.clone());
+        self.check_identifier(&interface.identifier, &path);
+        self.check_directives(&interface.attributes, &path);
+        self.check_duplicates(&path, "function", interface.functions.iter().map(|function| (&function.identifier, &function.attributes)));
+        self.check_duplicates(&path, "method", interface.methods.iter().map(|method| (&method.identifier, &method.attributes)));
+        self.check_duplicates(&path, "object", interface.objects.iter().map(|object| (&object.identifier, &object.attributes)));
+        for parent in &interface.interfaces {
+            self.check_type(&Type::from(parent.clone()), &[], module_path, &path);
+        }
+        for function in &interface.functions {
+            self.check_function(function, module_path, &path.clone().join(function.identifier.clone()));
+        }
+        for method in &interface.methods {
+            self.check_method(method, module_path, &path.clone().join(method.identifier.clone()));
+        }
+        for object in &interface.objects {
+            self.check_object(object, module_path, &path.clone().join(object.identifier.clone()));
+        }
+    }
+
+    fn check_function(&mut self, function: &Function, module_path: &Path, path: &Path) {
+        self.check_identifier(&function.identifier, path);
+        self.check_directives(&function.attributes, path);
+        self.check_signature(&function.inputs, &function.output, module_path, path);
+        for overload in &function.overloads {
+            self.check_signature(&overload.inputs, &overload.output, module_path, path);
+        }
+    }
+
+    fn check_method(&mut self, method: &Method, module_path: &Path, path: &Path) {
+        self.check_identifier(&method.identifier, path);
+        self.check_directives(&method.attributes, path);
+        self.check_signature(&method.inputs, &method.output, module_path, path);
+        for overload in &method.overloads {
+            self.check_signature(&overload.inputs, &overload.output, module_path, path);
+        }
+    }
+
+    fn check_signature(&mut self, inputs: &[Parameter], output: &Option<Type>, module_path: &Path, path: &Path) {
+        self.check_duplicates(path, "parameter", inputs.iter().map(|parameter| (&parameter.identifier, &parameter.attributes)));
+        for parameter in inputs {
+            let parameter_path = path.clone().join(parameter.identifier.clone());
+            self.check_identifier(&parameter.identifier, &parameter_path);
+            self.check_type(&parameter.type_, &[], module_path, &parameter_path);
+            if let Some(default_value) = &parameter.default_value {
+                self.check_literal(default_value, &parameter.type_, &parameter_path);
+            }
+        }
+        if let Some(output) = output {
+            self.check_type(output, &[], module_path, path);
+        }
+    }
+
+    fn check_object(&mut self, object: &Object, module_path: &Path, path: &Path) {
+        self.check_identifier(&object.identifier, path);
+        self.check_directives(&object.attributes, path);
+        self.check_type(&object.type_, &[], module_path, path);
+        self.check_literal(&object.literal, &object.type_, path);
+    }
+
+    /// Checks that `type_` and its generic arguments resolve, ignoring builtin types and the
+    /// `generics` parameters of the enclosing definition.
+    fn check_type(&mut self, type_: &Type, generics: &[Identifier], module_path: &Path, path: &Path) {
+        for segment in &type_.path.segments {
+            for generic in &segment.generics.types {
+                self.check_type(generic, generics, module_path, path);
+            }
+        }
+        let is_builtin = match type_.path.segments.as_slice() {
+            [] => true,
+            [segment] => type_.is_builtin() || generics.contains(&segment.identifier),
+            _ => false
+        };
+        if !is_builtin && self.resolve(&type_.path, module_path).is_none() {
+            self.report(Severity::Warning, DiagnosticKind::UnresolvedType, path, format!("type `{}` resolves to nothing", type_));
+        }
+    }
+
+    fn check_literal(&mut self, literal: &Literal, type_: &Type, path: &Path) {
+        if matches!(literal, Literal::None | Literal::Unknown(_)) || type_.path.is_empty() {
+            return;
+        }
+        // Optional values are checked against the wrapped type.
+        let segment = type_.path.last();
+        let type_ = if segment.identifier == Identifier::option() {
+            match segment.generics.types.first() {
+                Some(type_) => type_,
+                None => return
+            }
+        } else {
+            type_
+        };
+        if type_.path.is_empty() || *type_ == Type::opaque() {
+            return;
+        }
+        if !literal.is_compatible_with(type_) {
+            self.report(Severity::Error, DiagnosticKind::IncompatibleLiteral, path, format!("literal `{}` is incompatible with type `{}`", literal, type_));
+        }
+    }
+
+    fn check_identifier(&mut self, identifier: &Identifier, path: &Path) {
+        if !is_valid_identifier(identifier) {
+            self.report(Severity::Error, DiagnosticKind::InvalidIdentifier, path, format!("`{}` isn't a valid identifier", identifier));
+        }
+    }
+
+    fn check_directives(&mut self, attributes: &Attributes, path: &Path) {
+        for directive in Directive::parse_all(attributes) {
+            if let Err(Error::Message(message)) = directive {
+                self.report(Severity::Error, DiagnosticKind::InvalidDirective, path, message);
+            }
+        }
+    }
+
+    fn check_duplicates<'i>(&mut self, path: &Path, kind: &str, items: impl IntoIterator<Item = (&'i Identifier, &'i Attributes)>) {
+        let mut defined = HashSet::new();
+        for (identifier, attributes) in items {
+            if !is_conditional(attributes) && !defined.insert(identifier) {
+                self.report(Severity::Error, DiagnosticKind::DuplicateIdentifier, &path.clone().join(identifier.clone()), format!("{} `{}` is defined more than once", kind, identifier));
+            }
+        }
+    }
+}
+
+/// Checks for a letter or underscore followed by letters, digits or underscores. Rust raw identifiers are accepted.
+fn is_valid_identifier(identifier: &Identifier) -> bool {
+    let name = identifier.name.strip_prefix("r#").unwrap_or(&identifier.name);
+    let mut characters = name.chars();
+    matches!(characters.next(), Some(character) if character.is_alphabetic() || character == '_')
+        && characters.all(|character| character.is_alphanumeric() || character == '_')
+}
+
+/// Definitions only compiled under a condition, such as `#[cfg(...)]` items or the branches of an
+/// unknown Python condition, may legitimately share identifiers.
+fn is_conditional(attributes: &Attributes) -> bool {
+    attributes.get_group("cfg").is_some() || attributes.get_literal_from_path("python::condition").is_some()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ligen_ir::macro_attributes::{Group, Named};
+
+    fn library() -> Library {
+        let module = Module {
+            identifier: "models".into(),
+            types: vec![TypeDefinition { identifier: "User".into(), ..Default::default() }],
+            functions: vec![
+                Function {
+                    identifier: "create".into(),
+                    inputs: vec![Parameter { identifier: "name".into(), type_: Type::string(), ..Default::default() }],
+                    output: Some("User".into()),
+                    ..Default::default()
+                }
+            ],
+            imports: vec![Import { path: "self::User".into(), renaming: Some("Account".into()), ..Default::default() }],
+            ..Default::default()
+        };
+        Library {
+            identifier: "library".into(),
+            root_module: Module { identifier: "library".into(), modules: vec![module], ..Default::default() },
+            ..Default::default()
+        }
+    }
+
+    fn kinds(library: &Library) -> Vec<DiagnosticKind> {
+        LibraryValidator::new().check_library(library).into_iter().map(|diagnostic| diagnostic.kind).collect()
+    }
+
+    #[test]
+    fn valid_library() {
+        let mut library = library();
+        assert!(kinds(&library).is_empty());
+        assert!(LibraryValidator::new().validate(&mut library, &Default::default()).is_ok());
+    }
+
+    #[test]
+    fn dangling_references() {
+        let mut library = library();
+        let module = &mut library.root_module.modules[0];
+        module.functions[0].output = Some("Missing".into());
+        module.imports.push(Import { path: "numpy::ndarray".into(), ..Default::default() });
+        assert_eq!(kinds(&library), vec![DiagnosticKind::UnresolvedImport, DiagnosticKind::UnresolvedType]);
+        // Unresolved references are only warnings.
+        assert!(LibraryValidator::new().validate(&mut library, &Default::default()).is_ok());
+    }
+
+    #[test]
+    fn duplicates_and_identifiers() {
+        let mut library = library();
+        let module = &mut library.root_module.modules[0];
+        module.types.push(TypeDefinition { identifier: "User".into(), ..Default::default() });
+        module.objects.push(Object { identifier: "2fast".into(), type_: Type::i32(), literal: Literal::Integer(2), ..Default::default() });
+        let diagnostics = LibraryValidator::new().check_library(&library);
+        assert_eq!(diagnostics.iter().map(|diagnostic| diagnostic.kind).collect::<Vec<_>>(), vec![DiagnosticKind::DuplicateIdentifier, DiagnosticKind::InvalidIdentifier]);
+        assert_eq!(diagnostics[0].path, Path::from("library::models::User"));
+        assert!(LibraryValidator::new().validate(&mut library, &Default::default()).is_err());
+    }
+
+    #[test]
+    fn incompatible_literals() {
+        let mut library = library();
+        let module = &mut library.root_module.modules[0];
+        module.objects.push(Object { identifier: "COUNT".into(), type_: Type::i32(), literal: "ten".into(), ..Default::default() });
+        module.objects.push(Object { identifier: "LIMIT".into(), type_: Type::option(Type::i32()), literal: Literal::Integer(10), ..Default::default() });
+        module.functions[0].inputs[0].default_value = Some(true.into());
+        assert_eq!(kinds(&library), vec![DiagnosticKind::IncompatibleLiteral, DiagnosticKind::IncompatibleLiteral]);
+    }
+
+    #[test]
+    fn invalid_directives() {
+        let mut library = library();
+        let module = &mut library.root_module.modules[0];
+        let directives = vec![Group::from("ignore").into(), Group::from("ignor").into(), Named::new("rename", 1).into()];
+        module.functions[0].attributes = Group::new("ligen", Attributes { attributes: directives }).into();
+        let diagnostics = LibraryValidator::new().check_library(&library);
+        assert_eq!(diagnostics.iter().map(|diagnostic| diagnostic.kind).collect::<Vec<_>>(), vec![DiagnosticKind::InvalidDirective; 2]);
+        assert_eq!(diagnostics[0].message, "unknown directive `ignor`");
+        assert_eq!(diagnostics[0].path, Path::from("library::models::create"));
+    }
+}
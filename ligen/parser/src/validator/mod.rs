@@ -1,6 +1,12 @@
+pub mod diagnostic;
+pub mod library;
+
+pub use diagnostic::*;
+pub use library::*;
+
 use crate::{prelude::*, ParserConfig};
 
 pub trait Validator {
     type Input;
     fn validate(&self, input: &mut Self::Input, config: &ParserConfig) -> Result<()>;
-}
\ No newline at end of file
+}
@@ -1,50 +1,65 @@
-use ligen_ir::{Import, Module, Library};
-use crate::transformers::Transform;
-use crate::visitors::{ImportVisitor, ModuleVisitor, LibraryVisitor};
+use ligen_ir::{Import, Path, Type};
+use crate::transformers::{walk_type, Scope, Transformer};
 
 // FIXME: Move this to ligen-rust.
+/// Replaces the `crate` alias with the root module identifier.
 pub struct ReplaceCrateAlias;
 
-// FIXME: This logic is duplicated fro other transformers. This could be somehow generalized.
-
-impl Transform<Library, Library> for ReplaceCrateAlias {
-    fn transform(&self, data: &Library) -> Library {
-        let visitor = LibraryVisitor::from(data.clone());
-        <Self as Transform::<LibraryVisitor, Library>>::transform(self, &visitor)
+impl ReplaceCrateAlias {
+    fn replace(scope: &Scope, path: &mut Path) {
+        if let Some(first) = path.segments.first_mut() {
+            if first.identifier.name == "crate" {
+                first.identifier = scope.library.root_module.identifier.clone();
+            }
+        }
     }
 }
 
-impl Transform<LibraryVisitor, Library> for ReplaceCrateAlias {
-    fn transform(&self, data: &LibraryVisitor) -> Library {
-        let mut library = data.current.clone();
-        let visitor = ModuleVisitor::from(&data.child(data.current.root_module.clone()));
-        library.root_module = <Self as Transform::<ModuleVisitor, Module>>::transform(self, &visitor);
-        library
+impl Transformer for ReplaceCrateAlias {
+    fn transform_import(&self, scope: &Scope, import: &mut Import) {
+        Self::replace(scope, &mut import.path);
     }
-}
 
-impl Transform<ModuleVisitor, Module> for ReplaceCrateAlias {
-    fn transform(&self, data: &ModuleVisitor) -> Module {
-        let mut module = data.current.clone();
-        for (index, import) in data.imports.iter().enumerate() {
-            let visitor = data.child(import.clone());
-            module.imports[index] = <Self as Transform::<ImportVisitor, Import>>::transform(self, &visitor);
-        }
-        for (index, child_module) in data.modules.iter().enumerate() {
-            let visitor = ModuleVisitor::from(&data.child(child_module.clone()));
-            module.modules[index] = <Self as Transform::<ModuleVisitor, Module>>::transform(self, &visitor);
-        }
-        module
+    fn transform_type(&self, scope: &Scope, type_: &mut Type) {
+        Self::replace(scope, &mut type_.path);
+        walk_type(self, scope, type_);
+    }
+
+    fn transform_path(&self, scope: &Scope, path: &mut Path) {
+        Self::replace(scope, path);
     }
 }
 
-impl Transform<ImportVisitor, Import> for ReplaceCrateAlias {
-    fn transform(&self, data: &ImportVisitor) -> Import {
-        let mut import = data.current.clone();
-        let first = import.path.first_mut();
-        if *first == "crate".into() {
-            *first = data.parent_library().root_module.identifier.clone().into();
-        }
-        import
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ligen_ir::{Field, Interface, Library, Module, Structure, TypeDefinition};
+    use crate::transformers::Transformable;
+
+    #[test]
+    fn crate_alias() {
+        let field = Field { type_: Type::from(Path::from("crate::Other")), ..Default::default() };
+        let structure = TypeDefinition {
+            identifier: "Structure".into(),
+            interfaces: vec!["crate::Trait".into()],
+            definition: Structure { fields: vec![field] }.into(),
+            ..Default::default()
+        };
+        let interface = Interface { identifier: "Structure".into(), interfaces: vec!["crate::Trait".into()], ..Default::default() };
+        let root_module = Module {
+            identifier: "library".into(),
+            imports: vec![Import { path: "crate::Other".into(), ..Default::default() }],
+            types: vec![structure],
+            interfaces: vec![interface],
+            ..Default::default()
+        };
+        let library = Library { identifier: "library".into(), root_module, ..Default::default() }.transform(ReplaceCrateAlias);
+        assert_eq!(library.root_module.imports[0].path, Path::from("library::Other"));
+        let ligen_ir::KindDefinition::Structure(structure) = &library.root_module.types[0].definition else {
+            panic!("Not a structure.");
+        };
+        assert_eq!(structure.fields[0].type_.path, Path::from("library::Other"));
+        assert_eq!(library.root_module.types[0].interfaces, vec![Path::from("library::Trait")]);
+        assert_eq!(library.root_module.interfaces[0].interfaces, vec![Path::from("library::Trait")]);
     }
 }
@@ -1,10 +1,91 @@
 use ligen_ir::Library;
 
+pub mod transformer;
 pub mod path;
 pub mod alias;
+pub mod public_api;
+
+pub use transformer::*;
 
 pub trait Transform<In: Sized, Out: Sized> {
     fn transform(&self, data: &In) -> Out;
+
+    /// Name used when logging the transform.
+    fn name(&self) -> String {
+        let name = std::any::type_name::<Self>();
+        name
+            .rsplit("::")
+            .next()
+            .unwrap_or(name)
+            .to_string()
+    }
+}
+
+/// A transform in a `Transforms` sequence.
+struct Stage<T> {
+    order: i32,
+    transform: Box<dyn Transform<T, T>>
+}
+
+/// Sequence of transforms, applied by ascending order and then in the order they were added.
+pub struct Transforms<T> {
+    stages: Vec<Stage<T>>
+}
+
+impl<T> Default for Transforms<T> {
+    fn default() -> Self {
+        let stages = Default::default();
+        Self { stages }
+    }
+}
+
+impl<T> Transforms<T> {
+    /// Creates an empty sequence.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds `transform` with the default order, 0.
+    pub fn add(self, transform: impl Transform<T, T> + 'static) -> Self {
+        self.add_with_order(0, transform)
+    }
+
+    /// Adds `transform` with `order`. Lower orders are applied first.
+    pub fn add_with_order(mut self, order: i32, transform: impl Transform<T, T> + 'static) -> Self {
+        let transform = Box::new(transform);
+        let index = self.stages.partition_point(|stage| stage.order <= order);
+        self.stages.insert(index, Stage { order, transform });
+        self
+    }
+
+    /// Adds the transforms of `other`, keeping their orders.
+    pub fn extend(mut self, other: Transforms<T>) -> Self {
+        for stage in other.stages {
+            let index = self.stages.partition_point(|current| current.order <= stage.order);
+            self.stages.insert(index, stage);
+        }
+        self
+    }
+
+    /// Names of the transforms, in the order they are applied.
+    pub fn names(&self) -> Vec<String> {
+        self
+            .stages
+            .iter()
+            .map(|stage| stage.transform.name())
+            .collect()
+    }
+}
+
+impl<T: Clone> Transform<T, T> for Transforms<T> {
+    fn transform(&self, data: &T) -> T {
+        let mut data = data.clone();
+        for stage in &self.stages {
+            log::debug!("Applying transform {} (order {}).", stage.transform.name(), stage.order);
+            data = stage.transform.transform(&data);
+        }
+        data
+    }
 }
 
 pub trait Transformable: Sized + Clone {
@@ -12,13 +93,37 @@ pub trait Transformable: Sized + Clone {
         transform.transform(self)
     }
 
-    fn transforms(&self, transforms: &[&dyn Transform<Self, Self>]) -> Self {
-        let mut data = self.clone();
-        for transform in transforms {
-            data = transform.transform(&data);
-        }
-        data
+    fn transforms(&self, transforms: &Transforms<Self>) -> Self {
+        transforms.transform(self)
     }
 }
 
-impl Transformable for Library {}
\ No newline at end of file
+impl Transformable for Library {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Append(&'static str);
+
+    impl Transform<String, String> for Append {
+        fn transform(&self, data: &String) -> String {
+            format!("{}{}", data, self.0)
+        }
+    }
+
+    impl Transformable for String {}
+
+    #[test]
+    fn ordering() {
+        let transforms = Transforms::new()
+            .add(Append("b"))
+            .add_with_order(-1, Append("a"))
+            .add_with_order(1, Append("d"))
+            .add(Append("c"));
+        assert_eq!("".to_string().transforms(&transforms), "abcd");
+        let transforms = transforms.extend(Transforms::new().add_with_order(-1, Append("0")));
+        assert_eq!("".to_string().transforms(&transforms), "a0bcd");
+        assert_eq!(transforms.names(), vec!["Append"; 5]);
+    }
+}
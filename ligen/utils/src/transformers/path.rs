@@ -1,73 +1,238 @@
-use ligen_ir::{Function, Import, Module, Library, Type};
-use crate::transformers::Transform;
-use crate::visitors::{FunctionVisitor, ImportVisitor, ModuleVisitor, LibraryVisitor};
+use std::cell::RefCell;
+use ligen_ir::{Identifier, Import, Interface, Item, Path, Resolution, Type, TypeDefinition};
+use crate::transformers::{walk_interface, walk_type, walk_type_definition, Scope, Transformer};
 
-pub struct RelativePathToAbsolutePath;
+/// Rewrites every import, type and interface path as an absolute path starting with the root
+/// module identifier. References to items outside of the library, either through imports that
+/// can't be followed or not found at all, are prefixed with `Identifier::external()` instead.
+/// Builtin types and generic parameters are kept as they are.
+#[derive(Default)]
+pub struct RelativePathToAbsolutePath {
+    /// Generic parameters of the type definition or interface being transformed.
+    generics: RefCell<Vec<Identifier>>
+}
 
-impl Transform<Library, Library> for RelativePathToAbsolutePath {
-    fn transform(&self, data: &Library) -> Library {
-        let data = data.clone();
-        // TODO: We need to review this process.
-        // data.root_module.guarantee_absolute_paths();
-        let visitor = LibraryVisitor::from(data);
-        <Self as Transform<LibraryVisitor, Library>>::transform(self, &visitor)
+impl RelativePathToAbsolutePath {
+    /// Absolute path of `path`, as written in the module of `scope`.
+    pub fn absolute_path(scope: &Scope, path: &Path) -> Path {
+        if path.is_empty() || Self::is_external(path) {
+            return path.clone();
+        }
+        let absolute_path = match scope.library.resolve(path, &scope.module_path) {
+            // The import may be in another module, so its path is relative to that module.
+            Some(Resolution { item: Item::Import(import), path: import_path, .. }) => Self::external(&Self::normalize(&import.path, &import_path.without_last())),
+            Some(resolution) => resolution.path,
+            None => Self::external(&Self::normalize(path, &scope.module_path))
+        };
+        Self::with_generics(absolute_path, path)
     }
-}
 
-impl Transform<LibraryVisitor, Library> for RelativePathToAbsolutePath {
-    fn transform(&self, data: &LibraryVisitor) -> Library {
-        let mut library = data.current.clone();
-        let visitor = ModuleVisitor::from(&data.child(data.current.root_module.clone()));
-        library.root_module = <Self as Transform::<ModuleVisitor, Module>>::transform(self, &visitor);
-        library
+    /// Returns `true` if `path` is marked as defined outside of the library.
+    pub fn is_external(path: &Path) -> bool {
+        path
+            .segments
+            .first()
+            .map_or(false, |segment| segment.identifier == Identifier::external())
     }
-}
 
-impl Transform<ModuleVisitor, Module> for RelativePathToAbsolutePath {
-    fn transform(&self, data: &ModuleVisitor) -> Module {
-        let mut module = data.current.clone();
-        for (index, import) in data.current.imports.iter().enumerate() {
-            let visitor = data.child(import.clone());
-            module.imports[index] = <Self as Transform::<ImportVisitor, Import>>::transform(self, &visitor);
-        }
-        for (index, function) in data.current.functions.iter().enumerate() {
-            let visitor = data.child(function.clone());
-            module.functions[index] = <Self as Transform::<FunctionVisitor, Function>>::transform(self, &visitor);
+    fn external(path: &Path) -> Path {
+        Path::from(Identifier::external()).join(path.clone())
+    }
+
+    /// Replaces the leading `self`, `super` and `root` segments of `path`, as written in the module
+    /// at `module_path`, with the module they refer to.
+    fn normalize(path: &Path, module_path: &Path) -> Path {
+        let mut base: Option<Path> = None;
+        let mut segments = path.segments.iter().peekable();
+        while let Some(segment) = segments.peek() {
+            let current = base.clone().unwrap_or_else(|| module_path.clone());
+            base = if segment.identifier == Identifier::self_() {
+                Some(current)
+            } else if segment.identifier == Identifier::super_() {
+                Some(current.without_last())
+            } else if segment.identifier == Identifier::root() || segment.identifier.name == "crate" {
+                module_path.segments.first().cloned().map(Path::from)
+            } else {
+                break;
+            };
+            segments.next();
         }
-        for (index, child_module) in data.current.modules.iter().enumerate() {
-            let visitor = ModuleVisitor::from(&data.child(child_module.clone()));
-            module.modules[index] = <Self as Transform::<ModuleVisitor, Module>>::transform(self, &visitor);
+        match base {
+            Some(base) => base.join(Path { segments: segments.cloned().collect() }),
+            None => path.clone()
         }
-        module
     }
-}
 
-impl Transform<ImportVisitor, Import> for RelativePathToAbsolutePath {
-    fn transform(&self, data: &ImportVisitor) -> Import {
-        let mut import = data.current.clone();
-        if let Some(absolute_path) = data.find_absolute_path() {
-            import.path = absolute_path;
+    /// Keeps the generic arguments written in the last segment.
+    fn with_generics(mut absolute_path: Path, path: &Path) -> Path {
+        if let (Some(last), Some(written)) = (absolute_path.segments.last_mut(), path.segments.last()) {
+            last.generics = written.generics.clone();
         }
-        import
+        absolute_path
+    }
+
+    /// Sets the generic parameters in scope while running `transform`.
+    fn with_scoped_generics(&self, generics: Vec<Identifier>, transform: impl FnOnce()) {
+        let previous = self.generics.replace(generics);
+        transform();
+        self.generics.replace(previous);
+    }
+
+    fn generic_parameters(type_definition: &TypeDefinition) -> Vec<Identifier> {
+        type_definition
+            .generics
+            .types
+            .iter()
+            .filter_map(|type_| match type_.path.segments.as_slice() {
+                [segment] => Some(segment.identifier.clone()),
+                _ => None
+            })
+            .collect()
     }
 }
 
-impl Transform<FunctionVisitor, Function> for RelativePathToAbsolutePath {
-    fn transform(&self, data: &FunctionVisitor) -> Function {
-        let mut function = data.current.clone();
-        if let Some(output) = function.output.as_mut() {
-            type_to_absolute_path(&data.parent, output);
-        }
-        for input in &mut function.inputs {
-            type_to_absolute_path(&data.parent, &mut input.type_);
+impl Transformer for RelativePathToAbsolutePath {
+    fn transform_import(&self, scope: &Scope, import: &mut Import) {
+        let is_glob = !import.path.is_empty() && import.path.last().identifier.name == "*";
+        import.path = if is_glob {
+            let base = import.path.clone().without_last();
+            Self::absolute_path(scope, &base).join(Identifier::from("*"))
+        } else {
+            Self::absolute_path(scope, &import.path)
+        };
+    }
+
+    fn transform_type_definition(&self, scope: &Scope, type_definition: &mut TypeDefinition) {
+        let generics = Self::generic_parameters(type_definition);
+        self.with_scoped_generics(generics, || walk_type_definition(self, scope, type_definition));
+    }
+
+    fn transform_interface(&self, scope: &Scope, interface: &mut Interface) {
+        // Interfaces use the generic parameters of the type they are implemented for.
+        let generics = scope
+            .module()
+            .and_then(|module| module.types.iter().find(|type_| type_.identifier == interface.identifier))
+            .map(Self::generic_parameters)
+            .unwrap_or_default();
+        self.with_scoped_generics(generics, || walk_interface(self, scope, interface));
+    }
+
+    fn transform_type(&self, scope: &Scope, type_: &mut Type) {
+        let is_generic = match type_.path.segments.as_slice() {
+            [segment] => self.generics.borrow().contains(&segment.identifier),
+            _ => false
+        };
+        if !type_.is_builtin() && !is_generic {
+            type_.path = Self::absolute_path(scope, &type_.path);
         }
-        function
+        walk_type(self, scope, type_);
+    }
+
+    fn transform_path(&self, scope: &Scope, path: &mut Path) {
+        *path = Self::absolute_path(scope, path);
     }
 }
 
-fn type_to_absolute_path(module_visitor: &ModuleVisitor, type_: &mut Type) {
-    let path = &mut type_.path;
-    if let Some(absolute_path) = module_visitor.find_absolute_path(path) {
-        *path = absolute_path
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ligen_ir::{Field, Function, Library, Method, Module, Parameter, Structure, TypeAlias};
+    use crate::transformers::Transformable;
+
+    fn library() -> Library {
+        let user = TypeDefinition {
+            identifier: "User".into(),
+            generics: Type::from(Path::from("T")).into(),
+            interfaces: vec!["Display".into()],
+            definition: Structure { fields: vec![Field { identifier: Some("data".into()), type_: Type::from(Path::from("T")), ..Default::default() }] }.into(),
+            ..Default::default()
+        };
+        let models = Module {
+            identifier: "models".into(),
+            imports: vec![
+                Import { path: "numpy::ndarray".into(), ..Default::default() },
+                Import { path: "super::missing::Session".into(), ..Default::default() }
+            ],
+            types: vec![user],
+            ..Default::default()
+        };
+        let api = Module {
+            identifier: "api".into(),
+            imports: vec![
+                Import { path: "super::models::User".into(), renaming: Some("Account".into()), ..Default::default() },
+                Import { path: "super::models::*".into(), ..Default::default() },
+                Import { path: "numpy::ndarray".into(), ..Default::default() }
+            ],
+            functions: vec![Function {
+                identifier: "login".into(),
+                inputs: vec![
+                    Parameter { identifier: "account".into(), type_: Type::from(Path::from("Account")), ..Default::default() },
+                    Parameter { identifier: "data".into(), type_: Type::from(Path::from("ndarray")), ..Default::default() }
+                ],
+                output: Some(Type::vector(Type::from(Path::from("User")))),
+                ..Default::default()
+            }],
+            types: vec![TypeDefinition { identifier: "Users".into(), definition: TypeAlias { type_: Type::vector(Type::from(Path::from("Unknown"))) }.into(), ..Default::default() }],
+            ..Default::default()
+        };
+        let root_module = Module {
+            identifier: "library".into(),
+            interfaces: vec![Interface {
+                identifier: "Session".into(),
+                methods: vec![Method { identifier: "user".into(), output: Some(Type::from(Path::from("models::User"))), ..Default::default() }],
+                ..Default::default()
+            }],
+            modules: vec![models, api],
+            ..Default::default()
+        };
+        Library { identifier: "library".into(), root_module, ..Default::default() }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn absolute_paths() {
+        let library = library().transform(RelativePathToAbsolutePath::default());
+        let models = &library.root_module.modules[0];
+        let api = &library.root_module.modules[1];
+        let imports = api.imports.iter().map(|import| import.path.to_string()).collect::<Vec<_>>();
+        assert_eq!(imports, vec!["library::models::User", "library::models::*", "external::numpy::ndarray"]);
+        let inputs = api.functions[0].inputs.iter().map(|input| input.type_.to_string()).collect::<Vec<_>>();
+        assert_eq!(inputs, vec!["library::models::User", "external::numpy::ndarray"]);
+        assert_eq!(api.functions[0].output, Some(Type::vector(Type::from(Path::from("library::models::User")))));
+        assert_eq!(library.root_module.interfaces[0].methods[0].output, Some(Type::from(Path::from("library::models::User"))));
+        let ligen_ir::KindDefinition::TypeAlias(alias) = &api.types[0].definition else {
+            panic!("Not a type alias.");
+        };
+        assert_eq!(alias.type_, Type::vector(Type::from(Path::from("external::Unknown"))));
+        assert_eq!(models.types[0].interfaces, vec![Path::from("external::Display")]);
+        let ligen_ir::KindDefinition::Structure(structure) = &models.types[0].definition else {
+            panic!("Not a structure.");
+        };
+        assert_eq!(structure.fields[0].type_, Type::from(Path::from("T")));
+    }
+
+    #[test]
+    fn chained_imports() {
+        let mut library = library();
+        library.root_module.modules[1].imports.push(Import { path: "super::models::Session".into(), ..Default::default() });
+        library.root_module.modules[1].functions.push(Function {
+            identifier: "session".into(),
+            inputs: vec![Parameter { identifier: "data".into(), type_: Type::from(Path::from("super::models::ndarray")), ..Default::default() }],
+            output: Some(Type::from(Path::from("Session"))),
+            ..Default::default()
+        });
+        let library = library.transform(RelativePathToAbsolutePath::default());
+        let models = &library.root_module.modules[0];
+        let api = &library.root_module.modules[1];
+        let imports = models.imports.iter().map(|import| import.path.to_string()).collect::<Vec<_>>();
+        assert_eq!(imports, vec!["external::numpy::ndarray", "external::library::missing::Session"]);
+        assert_eq!(api.imports[3].path, Path::from("external::library::missing::Session"));
+        assert_eq!(api.functions[1].inputs[0].type_, Type::from(Path::from("external::numpy::ndarray")));
+        assert_eq!(api.functions[1].output, Some(Type::from(Path::from("external::library::missing::Session"))));
+    }
+
+    #[test]
+    fn idempotent() {
+        let library = library().transform(RelativePathToAbsolutePath::default());
+        assert_eq!(library.transform(RelativePathToAbsolutePath::default()), library);
+    }
+}
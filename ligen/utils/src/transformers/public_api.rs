@@ -0,0 +1,575 @@
+use std::collections::{HashMap, HashSet};
+use ligen_ir::{Attributes, Function, Identifier, Import, Interface, Item, KindDefinition, Library, Literal, Method, Module, Path, Resolution, Type, TypeDefinition, Visibility};
+use crate::transformers::Transformer;
+
+/// Keeps only the API users of a library can reach: the public members of public modules, the
+/// items re-exported with `pub use` or listed in `__all__`, and the types referenced by their
+/// signatures, transitively. Private, `#[ligen(ignore)]` and unreachable items are pruned.
+///
+/// Private imports are pruned too, so relative paths should be made absolute with
+/// `RelativePathToAbsolutePath` first.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct PublicApi {
+    /// Moves the re-exported items into the modules re-exporting them, replacing the imports.
+    pub flatten_reexports: bool
+}
+
+impl PublicApi {
+    /// Keeps the re-exports as imports.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Moves the re-exported items into the modules re-exporting them.
+    pub fn flattened() -> Self {
+        let flatten_reexports = true;
+        Self { flatten_reexports }
+    }
+}
+
+impl Transformer for PublicApi {
+    fn transform_library(&self, library: &mut Library) {
+        let root_path = Path::from(library.root_module.identifier.clone());
+        let mut surface = Surface::new(library, self.flatten_reexports);
+        surface.export_module(&root_path);
+        let root_module = surface.prune_module(&library.root_module, &root_path);
+        library.root_module = root_module;
+    }
+}
+
+/// A member or module re-exported by an import.
+struct Reexport {
+    /// Index of the import in the re-exporting module.
+    import: usize,
+    /// Name users see the item by.
+    name: Identifier,
+    /// Absolute path of the item.
+    target: Path
+}
+
+/// The reachable items of a library.
+struct Surface<'a> {
+    library: &'a Library,
+    flatten_reexports: bool,
+    /// Modules whose public members were exported.
+    exported: HashSet<Path>,
+    /// Modules to keep, with the ancestors of every kept item.
+    modules: HashSet<Path>,
+    /// Members kept where they are defined.
+    members: HashSet<Path>,
+    /// Members whose signatures were visited.
+    visited: HashSet<Path>,
+    /// Re-exports by module.
+    reexports: HashMap<Path, Vec<Reexport>>
+}
+
+impl<'a> Surface<'a> {
+    fn new(library: &'a Library, flatten_reexports: bool) -> Self {
+        let exported = Default::default();
+        let modules = Default::default();
+        let members = Default::default();
+        let visited = Default::default();
+        let reexports = Default::default();
+        Self { library, flatten_reexports, exported, modules, members, visited, reexports }
+    }
+
+    /// Exports the public members, re-exports and sub-modules of the module at `module_path`.
+    fn export_module(&mut self, module_path: &Path) {
+        if !self.exported.insert(module_path.clone()) {
+            return;
+        }
+        let library = self.library;
+        let Some(module) = library.find_module(module_path) else {
+            return;
+        };
+        self.keep_module(module_path);
+        let all = all_names(module);
+        for identifier in exported_members(module, &all) {
+            self.keep(&module_path.clone().join(identifier));
+        }
+        for (index, import) in module.imports.iter().enumerate() {
+            self.reexport(module_path, index, import, &all);
+        }
+        for sub_module in &module.modules {
+            if is_public(sub_module.visibility, &sub_module.attributes) {
+                self.export_module(&module_path.clone().join(sub_module.identifier.clone()));
+            }
+        }
+    }
+
+    fn reexport(&mut self, module_path: &Path, index: usize, import: &Import, all: &Option<Vec<String>>) {
+        let library = self.library;
+        if import.attributes.has_ignore_attribute() {
+            return;
+        }
+        if is_glob(import) {
+            let base = import.path.clone().without_last();
+            let Some(Resolution { path, item: Item::Module(target), .. }) = library.resolve(&base, module_path) else {
+                return;
+            };
+            for identifier in exported_members(target, &all_names(target)) {
+                let is_listed = match all {
+                    Some(all) => all.contains(&identifier.name),
+                    None => import.visibility == Visibility::Public
+                };
+                if is_listed {
+                    let target = path.clone().join(identifier.clone());
+                    self.add_reexport(module_path, Reexport { import: index, name: identifier, target });
+                }
+            }
+        } else {
+            let name = import_name(import);
+            if !is_exported(all, &name, import.visibility, &import.attributes) {
+                return;
+            }
+            match library.resolve(&import.path, module_path) {
+                // Modules are exported as a whole.
+                Some(Resolution { path, item: Item::Module(_), .. }) => {
+                    self.export_module(&path);
+                    let reexport = Reexport { import: index, name, target: path };
+                    self.reexports.entry(module_path.clone()).or_default().push(reexport);
+                },
+                // Items outside of the library aren't part of its API.
+                Some(Resolution { item: Item::Import(_), .. }) | None => (),
+                Some(resolution) => self.add_reexport(module_path, Reexport { import: index, name, target: resolution.path })
+            }
+        }
+    }
+
+    fn add_reexport(&mut self, module_path: &Path, reexport: Reexport) {
+        // Only module members can be re-exported, e.g. not the functions of an interface.
+        if self.library.find_module(&reexport.target.clone().without_last()).is_none() {
+            return;
+        }
+        if self.flatten_reexports {
+            self.reach(&reexport.target);
+        } else {
+            self.keep(&reexport.target);
+        }
+        self.reexports.entry(module_path.clone()).or_default().push(reexport);
+    }
+
+    /// Keeps the members at `path` where they are defined.
+    fn keep(&mut self, path: &Path) {
+        if self.members.insert(path.clone()) {
+            self.keep_module(&path.clone().without_last());
+            self.reach(path);
+        }
+    }
+
+    /// Keeps the module at `module_path` and its ancestors.
+    fn keep_module(&mut self, module_path: &Path) {
+        let mut module_path = module_path.clone();
+        while !module_path.is_empty() && self.modules.insert(module_path.clone()) {
+            module_path = module_path.without_last();
+        }
+    }
+
+    /// Keeps the types and interfaces referenced by the signatures of the members at `path`.
+    fn reach(&mut self, path: &Path) {
+        if !self.visited.insert(path.clone()) {
+            return;
+        }
+        let library = self.library;
+        let module_path = path.clone().without_last();
+        let Some(module) = library.find_module(&module_path) else {
+            return;
+        };
+        let identifier = &path.last().identifier;
+        let mut paths = Vec::new();
+        for type_definition in module.types.iter().filter(|type_| type_.identifier == *identifier) {
+            type_definition_paths(type_definition, &mut paths);
+        }
+        for function in module.functions.iter().filter(|function| function.identifier == *identifier) {
+            function_paths(function, &mut paths);
+        }
+        for object in module.objects.iter().filter(|object| object.identifier == *identifier) {
+            type_paths(&object.type_, &mut paths);
+        }
+        for interface in module.interfaces.iter().filter(|interface| interface.identifier == *identifier) {
+            interface_paths(interface, &mut paths);
+        }
+        for path in paths {
+            if let Some(resolution) = library.resolve(&path, &module_path) {
+                if matches!(resolution.item, Item::TypeDefinition(_) | Item::Interface(_)) {
+                    self.keep(&resolution.path);
+                }
+            }
+        }
+    }
+
+    fn is_kept(&self, module_path: &Path, identifier: &Identifier, attributes: &Attributes) -> bool {
+        !attributes.has_ignore_attribute() && self.members.contains(&module_path.clone().join(identifier.clone()))
+    }
+
+    fn prune_module(&self, module: &Module, module_path: &Path) -> Module {
+        let reexports = self
+            .reexports
+            .get(module_path)
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        let imports = if self.flatten_reexports {
+            Default::default()
+        } else {
+            module
+                .imports
+                .iter()
+                .enumerate()
+                .filter(|(index, _)| reexports.iter().any(|reexport| reexport.import == *index))
+                .map(|(_, import)| import.clone())
+                .collect()
+        };
+        let objects = module
+            .objects
+            .iter()
+            .filter(|object| self.is_kept(module_path, &object.identifier, &object.attributes))
+            .cloned()
+            .collect();
+        let functions = module
+            .functions
+            .iter()
+            .filter(|function| self.is_kept(module_path, &function.identifier, &function.attributes))
+            .map(prune_function)
+            .collect();
+        let types = module
+            .types
+            .iter()
+            .filter(|type_| self.is_kept(module_path, &type_.identifier, &type_.attributes))
+            .map(prune_type_definition)
+            .collect();
+        let interfaces = module
+            .interfaces
+            .iter()
+            .filter(|interface| self.is_kept(module_path, &interface.identifier, &interface.attributes))
+            .map(prune_interface)
+            .collect();
+        let modules = module
+            .modules
+            .iter()
+            .filter_map(|sub_module| {
+                let sub_module_path = module_path.clone().join(sub_module.identifier.clone());
+                self.modules.contains(&sub_module_path).then(|| self.prune_module(sub_module, &sub_module_path))
+            })
+            .collect();
+        let attributes = module.attributes.clone();
+        let visibility = module.visibility;
+        let identifier = module.identifier.clone();
+        let mut module = Module { attributes, visibility, identifier, imports, objects, functions, types, interfaces, modules };
+        if self.flatten_reexports {
+            for reexport in reexports {
+                self.flatten(&mut module, module_path, reexport);
+            }
+        }
+        module
+    }
+
+    /// Copies the items re-exported by `reexport` into `module`, under their re-exported name.
+    fn flatten(&self, module: &mut Module, module_path: &Path, reexport: &Reexport) {
+        let target_module_path = reexport.target.clone().without_last();
+        // Re-exports of the module's own members or of its ancestors have nothing to move.
+        if target_module_path == *module_path || module_path.segments.starts_with(&reexport.target.segments) {
+            return;
+        }
+        let Some(target_module) = self.library.find_module(&target_module_path) else {
+            return;
+        };
+        let identifier = &reexport.target.last().identifier;
+        let name = &reexport.name;
+        if let Some(sub_module) = target_module.modules.iter().find(|sub_module| sub_module.identifier == *identifier) {
+            if !module.modules.iter().any(|sub_module| sub_module.identifier == *name) {
+                let mut sub_module = self.prune_module(sub_module, &reexport.target);
+                sub_module.identifier = name.clone();
+                module.modules.push(sub_module);
+            }
+            return;
+        }
+        let is_copied = |candidate: &Identifier, attributes: &Attributes| candidate == identifier && !attributes.has_ignore_attribute();
+        if !module.types.iter().any(|type_| type_.identifier == *name) {
+            for type_ in target_module.types.iter().filter(|type_| is_copied(&type_.identifier, &type_.attributes)) {
+                let mut type_ = prune_type_definition(type_);
+                type_.identifier = name.clone();
+                module.types.push(type_);
+            }
+        }
+        if !module.interfaces.iter().any(|interface| interface.identifier == *name) {
+            for interface in target_module.interfaces.iter().filter(|interface| is_copied(&interface.identifier, &interface.attributes)) {
+                let mut interface = prune_interface(interface);
+                interface.identifier = name.clone();
+                module.interfaces.push(interface);
+            }
+        }
+        if !module.functions.iter().any(|function| function.identifier == *name) {
+            for function in target_module.functions.iter().filter(|function| is_copied(&function.identifier, &function.attributes)) {
+                let mut function = prune_function(function);
+                function.identifier = name.clone();
+                module.functions.push(function);
+            }
+        }
+        if !module.objects.iter().any(|object| object.identifier == *name) {
+            for object in target_module.objects.iter().filter(|object| is_copied(&object.identifier, &object.attributes)) {
+                let mut object = object.clone();
+                object.identifier = name.clone();
+                module.objects.push(object);
+            }
+        }
+    }
+}
+
+fn is_public(visibility: Visibility, attributes: &Attributes) -> bool {
+    visibility == Visibility::Public && !attributes.has_ignore_attribute()
+}
+
+/// Members listed in `all` are exported whatever their visibility.
+fn is_exported(all: &Option<Vec<String>>, identifier: &Identifier, visibility: Visibility, attributes: &Attributes) -> bool {
+    match all {
+        Some(all) => all.contains(&identifier.name) && !attributes.has_ignore_attribute(),
+        None => is_public(visibility, attributes)
+    }
+}
+
+/// Names listed in the `__all__` object of `module`, if any.
+fn all_names(module: &Module) -> Option<Vec<String>> {
+    let object = module.objects.iter().find(|object| object.identifier.name == "__all__")?;
+    let names = match &object.literal {
+        Literal::Vector(names) | Literal::Tuple(names) => names,
+        _ => return None
+    };
+    let names = names
+        .iter()
+        .filter_map(Literal::as_string)
+        .cloned()
+        .collect();
+    Some(names)
+}
+
+/// Identifiers of the exported types, functions, objects and interfaces of `module`.
+fn exported_members(module: &Module, all: &Option<Vec<String>>) -> Vec<Identifier> {
+    let types = module
+        .types
+        .iter()
+        .filter(|type_| is_exported(all, &type_.identifier, type_.visibility, &type_.attributes))
+        .map(|type_| type_.identifier.clone());
+    let functions = module
+        .functions
+        .iter()
+        .filter(|function| is_exported(all, &function.identifier, function.visibility, &function.attributes))
+        .map(|function| function.identifier.clone());
+    let objects = module
+        .objects
+        .iter()
+        .filter(|object| is_exported(all, &object.identifier, Visibility::Public, &object.attributes))
+        .map(|object| object.identifier.clone());
+    let interfaces = module
+        .interfaces
+        .iter()
+        .filter(|interface| is_exported(all, &interface.identifier, interface.visibility, &interface.attributes))
+        .map(|interface| interface.identifier.clone());
+    types.chain(functions).chain(objects).chain(interfaces).collect()
+}
+
+fn is_glob(import: &Import) -> bool {
+    !import.path.is_empty() && import.path.last().identifier.name == "*"
+}
+
+/// The name an import brings into scope.
+fn import_name(import: &Import) -> Identifier {
+    import
+        .renaming
+        .clone()
+        .unwrap_or_else(|| import.path.last().identifier.clone())
+}
+
+fn type_paths(type_: &Type, paths: &mut Vec<Path>) {
+    if !type_.is_builtin() {
+        paths.push(type_.path.clone());
+    }
+    for segment in &type_.path.segments {
+        for generic in &segment.generics.types {
+            type_paths(generic, paths);
+        }
+    }
+}
+
+fn function_paths(function: &Function, paths: &mut Vec<Path>) {
+    for input in &function.inputs {
+        type_paths(&input.type_, paths);
+    }
+    if let Some(output) = &function.output {
+        type_paths(output, paths);
+    }
+    for overload in &function.overloads {
+        function_paths(overload, paths);
+    }
+}
+
+fn method_paths(method: &Method, paths: &mut Vec<Path>) {
+    for input in &method.inputs {
+        type_paths(&input.type_, paths);
+    }
+    if let Some(output) = &method.output {
+        type_paths(output, paths);
+    }
+    for overload in &method.overloads {
+        method_paths(overload, paths);
+    }
+}
+
+fn type_definition_paths(type_definition: &TypeDefinition, paths: &mut Vec<Path>) {
+    if type_definition.attributes.has_ignore_attribute() {
+        return;
+    }
+    paths.extend(type_definition.interfaces.iter().cloned());
+    match &type_definition.definition {
+        KindDefinition::Structure(structure) => {
+            for field in structure.fields.iter().filter(|field| is_public(field.visibility, &field.attributes)) {
+                type_paths(&field.type_, paths);
+            }
+        },
+        KindDefinition::Enumeration(_) => (),
+        KindDefinition::TypeAlias(type_alias) => type_paths(&type_alias.type_, paths)
+    }
+}
+
+fn interface_paths(interface: &Interface, paths: &mut Vec<Path>) {
+    if interface.attributes.has_ignore_attribute() {
+        return;
+    }
+    paths.extend(interface.interfaces.iter().cloned());
+    for object in interface.objects.iter().filter(|object| !object.attributes.has_ignore_attribute()) {
+        type_paths(&object.type_, paths);
+    }
+    for function in interface.functions.iter().filter(|function| is_public(function.visibility, &function.attributes)) {
+        function_paths(function, paths);
+    }
+    for method in interface.methods.iter().filter(|method| is_public(method.visibility, &method.attributes)) {
+        method_paths(method, paths);
+    }
+}
+
+fn prune_function(function: &Function) -> Function {
+    let mut function = function.clone();
+    function.overloads.retain(|overload| !overload.attributes.has_ignore_attribute());
+    function
+}
+
+fn prune_type_definition(type_definition: &TypeDefinition) -> TypeDefinition {
+    let mut type_definition = type_definition.clone();
+    match &mut type_definition.definition {
+        KindDefinition::Structure(structure) => structure.fields.retain(|field| is_public(field.visibility, &field.attributes)),
+        KindDefinition::Enumeration(enumeration) => enumeration.variants.retain(|variant| !variant.attributes.has_ignore_attribute()),
+        KindDefinition::TypeAlias(_) => ()
+    }
+    type_definition
+}
+
+fn prune_interface(interface: &Interface) -> Interface {
+    let mut interface = interface.clone();
+    interface.objects.retain(|object| !object.attributes.has_ignore_attribute());
+    interface.functions.retain(|function| is_public(function.visibility, &function.attributes));
+    interface.methods.retain(|method| is_public(method.visibility, &method.attributes));
+    interface
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ligen_ir::{Attribute, Enumeration, Field, Object, Parameter, Structure, Variant};
+    use ligen_ir::macro_attributes::Group;
+    use crate::transformers::Transformable;
+
+    fn ignored() -> Attributes {
+        let attributes = vec![Attribute::Group(Group::new("ligen", Group::from("ignore")))];
+        Attributes { attributes }
+    }
+
+    fn library() -> Library {
+        let user = TypeDefinition {
+            identifier: "User".into(),
+            definition: Structure {
+                fields: vec![
+                    Field { identifier: Some("name".into()), type_: Type::string(), ..Default::default() },
+                    Field { identifier: Some("role".into()), type_: Type::from(Path::from("Role")), ..Default::default() },
+                    Field { identifier: Some("secret".into()), visibility: Visibility::Private, type_: Type::string(), ..Default::default() }
+                ]
+            }.into(),
+            ..Default::default()
+        };
+        let role = TypeDefinition {
+            identifier: "Role".into(),
+            visibility: Visibility::Private,
+            definition: Enumeration { variants: vec![Variant { identifier: "Admin".into(), ..Default::default() }] }.into(),
+            ..Default::default()
+        };
+        let unused = TypeDefinition { identifier: "Unused".into(), visibility: Visibility::Private, ..Default::default() };
+        let hidden = TypeDefinition { identifier: "Hidden".into(), attributes: ignored(), ..Default::default() };
+        let models = Module { identifier: "models".into(), visibility: Visibility::Private, types: vec![user, role, unused, hidden], ..Default::default() };
+        let session = TypeDefinition { identifier: "Session".into(), ..Default::default() };
+        let api = Module { identifier: "api".into(), types: vec![session], ..Default::default() };
+        let all = Object {
+            identifier: "__all__".into(),
+            literal: Literal::Vector(vec![Literal::String("exported".into())]),
+            ..Default::default()
+        };
+        let python = Module {
+            identifier: "python".into(),
+            objects: vec![all],
+            functions: vec![
+                Function { identifier: "exported".into(), ..Default::default() },
+                Function { identifier: "unlisted".into(), ..Default::default() }
+            ],
+            ..Default::default()
+        };
+        let root_module = Module {
+            identifier: "library".into(),
+            imports: vec![
+                Import { path: "models::User".into(), ..Default::default() },
+                Import { path: "models::Unused".into(), visibility: Visibility::Private, ..Default::default() }
+            ],
+            functions: vec![
+                Function {
+                    identifier: "login".into(),
+                    inputs: vec![Parameter { identifier: "name".into(), type_: Type::string(), ..Default::default() }],
+                    output: Some(Type::from(Path::from("api::Session"))),
+                    ..Default::default()
+                },
+                Function { identifier: "helper".into(), visibility: Visibility::Private, ..Default::default() },
+                Function { identifier: "ignored".into(), attributes: ignored(), ..Default::default() }
+            ],
+            modules: vec![models, api, python],
+            ..Default::default()
+        };
+        Library { identifier: "library".into(), root_module, ..Default::default() }
+    }
+
+    fn identifiers<'a>(identifiers: impl Iterator<Item = &'a Identifier>) -> Vec<String> {
+        identifiers.map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn reachable_items() {
+        let library = library().transform(PublicApi::new());
+        let root_module = &library.root_module;
+        let imports = root_module.imports.iter().map(|import| import.path.to_string()).collect::<Vec<_>>();
+        assert_eq!(imports, vec!["models::User"]);
+        assert_eq!(identifiers(root_module.functions.iter().map(|function| &function.identifier)), vec!["login"]);
+        assert_eq!(identifiers(root_module.modules.iter().map(|module| &module.identifier)), vec!["models", "api", "python"]);
+        let models = &root_module.modules[0];
+        assert_eq!(identifiers(models.types.iter().map(|type_| &type_.identifier)), vec!["User", "Role"]);
+        let KindDefinition::Structure(structure) = &models.types[0].definition else {
+            panic!("Not a structure.");
+        };
+        assert_eq!(structure.fields.len(), 2);
+        assert_eq!(identifiers(root_module.modules[1].types.iter().map(|type_| &type_.identifier)), vec!["Session"]);
+        assert_eq!(identifiers(root_module.modules[2].functions.iter().map(|function| &function.identifier)), vec!["exported"]);
+    }
+
+    #[test]
+    fn flattened_reexports() {
+        let library = library().transform(PublicApi::flattened());
+        let root_module = &library.root_module;
+        assert!(root_module.imports.is_empty());
+        assert_eq!(identifiers(root_module.types.iter().map(|type_| &type_.identifier)), vec!["User"]);
+        // `Role` is still referenced where it's defined by the re-exported `User`.
+        assert_eq!(identifiers(root_module.modules[0].types.iter().map(|type_| &type_.identifier)), vec!["Role"]);
+    }
+}
@@ -0,0 +1,256 @@
+//! In-place transformer with per-node hooks.
+
+use ligen_ir::{Field, Function, Identifier, Import, Interface, KindDefinition, Library, Method, Module, Object, Parameter, Path, Type, TypeDefinition, Variant};
+use crate::transformers::Transform;
+
+/// Where the node being transformed is.
+#[derive(Debug, Clone)]
+pub struct Scope<'a> {
+    /// The library as it was before the transformation, for lookups.
+    pub library: &'a Library,
+    /// Absolute path of the module containing the node, starting with the root module identifier.
+    pub module_path: Path
+}
+
+impl<'a> Scope<'a> {
+    /// Scope of the root module of `library`.
+    pub fn new(library: &'a Library) -> Self {
+        let module_path = library.root_module.identifier.clone().into();
+        Self { library, module_path }
+    }
+
+    /// The module containing the node, as it was before the transformation.
+    pub fn module(&self) -> Option<&'a Module> {
+        self.library.find_module(&self.module_path)
+    }
+
+    /// Scope of the sub-module `identifier`.
+    pub fn enter(&self, identifier: &Identifier) -> Self {
+        let library = self.library;
+        let module_path = self.module_path.clone().join(identifier.clone());
+        Self { library, module_path }
+    }
+}
+
+/// Transforms a library in place. Every hook recurses into the node's children by default, so
+/// implementors only override the hooks of the nodes they change, calling the matching `walk_*`
+/// function to keep recursing.
+pub trait Transformer {
+    fn transform_library(&self, library: &mut Library) {
+        walk_library(self, library)
+    }
+
+    fn transform_module(&self, scope: &Scope, module: &mut Module) {
+        walk_module(self, scope, module)
+    }
+
+    fn transform_import(&self, _scope: &Scope, _import: &mut Import) {}
+
+    fn transform_object(&self, scope: &Scope, object: &mut Object) {
+        walk_object(self, scope, object)
+    }
+
+    fn transform_function(&self, scope: &Scope, function: &mut Function) {
+        walk_function(self, scope, function)
+    }
+
+    fn transform_method(&self, scope: &Scope, method: &mut Method) {
+        walk_method(self, scope, method)
+    }
+
+    fn transform_parameter(&self, scope: &Scope, parameter: &mut Parameter) {
+        walk_parameter(self, scope, parameter)
+    }
+
+    fn transform_type_definition(&self, scope: &Scope, type_definition: &mut TypeDefinition) {
+        walk_type_definition(self, scope, type_definition)
+    }
+
+    fn transform_field(&self, scope: &Scope, field: &mut Field) {
+        walk_field(self, scope, field)
+    }
+
+    fn transform_variant(&self, _scope: &Scope, _variant: &mut Variant) {}
+
+    fn transform_interface(&self, scope: &Scope, interface: &mut Interface) {
+        walk_interface(self, scope, interface)
+    }
+
+    fn transform_type(&self, scope: &Scope, type_: &mut Type) {
+        walk_type(self, scope, type_)
+    }
+
+    /// Paths referring to interfaces, such as `TypeDefinition::interfaces`.
+    fn transform_path(&self, _scope: &Scope, _path: &mut Path) {}
+}
+
+impl<T: Transformer> Transform<Library, Library> for T {
+    fn transform(&self, data: &Library) -> Library {
+        let mut library = data.clone();
+        self.transform_library(&mut library);
+        library
+    }
+}
+
+/// Transforms the root module, looking names up in a snapshot of the library.
+pub fn walk_library<T: Transformer + ?Sized>(transformer: &T, library: &mut Library) {
+    let snapshot = library.clone();
+    let scope = Scope::new(&snapshot);
+    transformer.transform_module(&scope, &mut library.root_module);
+}
+
+/// Transforms the module members, then its sub-modules.
+pub fn walk_module<T: Transformer + ?Sized>(transformer: &T, scope: &Scope, module: &mut Module) {
+    for import in &mut module.imports {
+        transformer.transform_import(scope, import);
+    }
+    for object in &mut module.objects {
+        transformer.transform_object(scope, object);
+    }
+    for function in &mut module.functions {
+        transformer.transform_function(scope, function);
+    }
+    for type_definition in &mut module.types {
+        transformer.transform_type_definition(scope, type_definition);
+    }
+    for interface in &mut module.interfaces {
+        transformer.transform_interface(scope, interface);
+    }
+    for sub_module in &mut module.modules {
+        let scope = scope.enter(&sub_module.identifier);
+        transformer.transform_module(&scope, sub_module);
+    }
+}
+
+/// Transforms the object type.
+pub fn walk_object<T: Transformer + ?Sized>(transformer: &T, scope: &Scope, object: &mut Object) {
+    transformer.transform_type(scope, &mut object.type_);
+}
+
+/// Transforms the parameters, the output and the overloads.
+pub fn walk_function<T: Transformer + ?Sized>(transformer: &T, scope: &Scope, function: &mut Function) {
+    for parameter in &mut function.inputs {
+        transformer.transform_parameter(scope, parameter);
+    }
+    if let Some(output) = &mut function.output {
+        transformer.transform_type(scope, output);
+    }
+    for overload in &mut function.overloads {
+        transformer.transform_function(scope, overload);
+    }
+}
+
+/// Transforms the parameters, the output and the overloads.
+pub fn walk_method<T: Transformer + ?Sized>(transformer: &T, scope: &Scope, method: &mut Method) {
+    for parameter in &mut method.inputs {
+        transformer.transform_parameter(scope, parameter);
+    }
+    if let Some(output) = &mut method.output {
+        transformer.transform_type(scope, output);
+    }
+    for overload in &mut method.overloads {
+        transformer.transform_method(scope, overload);
+    }
+}
+
+/// Transforms the parameter type.
+pub fn walk_parameter<T: Transformer + ?Sized>(transformer: &T, scope: &Scope, parameter: &mut Parameter) {
+    transformer.transform_type(scope, &mut parameter.type_);
+}
+
+/// Transforms the implemented interfaces and the fields, the variants or the aliased type.
+pub fn walk_type_definition<T: Transformer + ?Sized>(transformer: &T, scope: &Scope, type_definition: &mut TypeDefinition) {
+    for path in &mut type_definition.interfaces {
+        transformer.transform_path(scope, path);
+    }
+    match &mut type_definition.definition {
+        KindDefinition::Structure(structure) => {
+            for field in &mut structure.fields {
+                transformer.transform_field(scope, field);
+            }
+        },
+        KindDefinition::Enumeration(enumeration) => {
+            for variant in &mut enumeration.variants {
+                transformer.transform_variant(scope, variant);
+            }
+        },
+        KindDefinition::TypeAlias(type_alias) => transformer.transform_type(scope, &mut type_alias.type_)
+    }
+}
+
+/// Transforms the field type.
+pub fn walk_field<T: Transformer + ?Sized>(transformer: &T, scope: &Scope, field: &mut Field) {
+    transformer.transform_type(scope, &mut field.type_);
+}
+
+/// Transforms the extended interfaces, the objects, functions and methods.
+pub fn walk_interface<T: Transformer + ?Sized>(transformer: &T, scope: &Scope, interface: &mut Interface) {
+    for path in &mut interface.interfaces {
+        transformer.transform_path(scope, path);
+    }
+    for object in &mut interface.objects {
+        transformer.transform_object(scope, object);
+    }
+    for function in &mut interface.functions {
+        transformer.transform_function(scope, function);
+    }
+    for method in &mut interface.methods {
+        transformer.transform_method(scope, method);
+    }
+}
+
+/// Transforms the generic arguments.
+pub fn walk_type<T: Transformer + ?Sized>(transformer: &T, scope: &Scope, type_: &mut Type) {
+    for segment in &mut type_.path.segments {
+        for generic in &mut segment.generics.types {
+            transformer.transform_type(scope, generic);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ligen_ir::Structure;
+
+    /// Counts the visited nodes.
+    #[derive(Default)]
+    struct Counter {
+        types: std::cell::Cell<usize>,
+        modules: std::cell::RefCell<Vec<String>>
+    }
+
+    impl Transformer for Counter {
+        fn transform_module(&self, scope: &Scope, module: &mut Module) {
+            self.modules.borrow_mut().push(scope.module_path.to_string());
+            walk_module(self, scope, module)
+        }
+
+        fn transform_type(&self, scope: &Scope, type_: &mut Type) {
+            self.types.set(self.types.get() + 1);
+            walk_type(self, scope, type_)
+        }
+    }
+
+    #[test]
+    fn default_recursion() {
+        let structure = TypeDefinition {
+            identifier: "Structure".into(),
+            definition: Structure { fields: vec![Field { type_: Type::vector(Type::i32()), ..Default::default() }] }.into(),
+            ..Default::default()
+        };
+        let interface = Interface {
+            identifier: "Structure".into(),
+            methods: vec![Method { output: Some(Type::boolean()), ..Default::default() }],
+            ..Default::default()
+        };
+        let sub_module = Module { identifier: "sub".into(), types: vec![structure], interfaces: vec![interface], ..Default::default() };
+        let root_module = Module { identifier: "library".into(), modules: vec![sub_module], ..Default::default() };
+        let library = Library { identifier: "library".into(), root_module, ..Default::default() };
+        let counter = Counter::default();
+        let transformed = counter.transform(&library);
+        assert_eq!(transformed, library);
+        assert_eq!(counter.types.get(), 3);
+        assert_eq!(*counter.modules.borrow(), vec!["library", "library::sub"]);
+    }
+}
@@ -1,37 +1,175 @@
 use std::path::PathBuf;
 
 use clap::Parser as Clap;
-use ligen_ir::{prelude::*, Registry};
-use ligen_parser::Parser;
-use ligen_python_parser::PythonParser;
-use ligen_rust_parser::parser::RustParser;
+use ligen_ir::{prelude::*, Format, Library, Registry};
+use ligen_ir::symbols::Symbols;
+use ligen_generator::GeneratorPlugins;
+use ligen_parser::{ConfigLoader, LibraryValidator, ParseCache, ParserPlugins};
 
 #[derive(Clap, Debug)]
+#[command(subcommand_negates_reqs = true)]
 pub struct Args {
-    #[arg(short, long)]
-    parser: String,
+    #[command(subcommand)]
+    command: Option<Command>,
 
-    #[arg(short, long)]
-    generator: String,
+    #[arg(short, long, required = true)]
+    parser: Option<String>,
 
-    #[arg(short, long)]
-    input: PathBuf,
+    #[arg(short, long, required = true)]
+    generator: Option<String>,
 
-    #[arg(short, long)]
-    output: PathBuf
+    #[arg(short, long, required = true)]
+    input: Option<PathBuf>,
+
+    #[arg(short, long, required = true)]
+    output: Option<PathBuf>,
+
+    /// Validate the parsed IR, failing on errors.
+    #[arg(long)]
+    validate: bool,
+
+    /// Save the IR of the parsed library to this file.
+    #[arg(long)]
+    save_ir: Option<PathBuf>,
+
+    /// Format of IR files, e.g. json, yaml, ron, msgpack or cbor. Defaults to the file extension.
+    #[arg(long)]
+    ir_format: Option<Format>,
+
+    /// Print the symbols fuzzily matching this query.
+    #[arg(long)]
+    find: Option<String>,
+
+    /// Override a configuration value, e.g. `--set ligen::only-parse-symbols=true` or
+    /// `--set ligen::exclude=[tests]`.
+    #[arg(long = "set", value_name = "KEY=VALUE")]
+    set: Vec<String>,
+
+    /// Print the effective configuration and where each value comes from.
+    #[arg(long)]
+    print_config: bool,
+
+    /// Print the modules and items left out by `ligen::include` and `ligen::exclude`.
+    #[arg(long)]
+    print_filtered: bool,
+
+    /// Parse every file again instead of reusing the modules cached from earlier runs.
+    #[arg(long)]
+    no_cache: bool,
+
+    /// Directory of the parse cache. Defaults to `$LIGEN_CACHE_DIR`, `$XDG_CACHE_HOME/ligen` or
+    /// `~/.cache/ligen`.
+    #[arg(long, global = true)]
+    cache_dir: Option<PathBuf>
+}
+
+#[derive(clap::Subcommand, Debug)]
+enum Command {
+    /// Remove the parse cache.
+    CleanCache,
+    /// List the available parsers.
+    Parsers,
+    /// List the available generators.
+    Generators
+}
+
+/// The parsers available to `--parser`, besides "ir".
+fn parser_plugins(cache: Option<ParseCache>) -> ParserPlugins {
+    match cache {
+        Some(cache) => ParserPlugins::new()
+            .with(ligen_python_parser::cached_plugin(cache.clone()))
+            .with(ligen_cargo::parser::library::cached_plugin(cache)),
+        None => ParserPlugins::new()
+            .with(ligen_python_parser::plugin())
+            .with(ligen_cargo::parser::library::plugin())
+    }
+}
+
+/// The generators available to `--generator`.
+fn generator_plugins() -> GeneratorPlugins {
+    GeneratorPlugins::new()
+        .with(ligen_rust_pyo3_importer::plugin())
 }
 
 fn main() -> Result<()> {
     let args = Args::parse();
-    let parser: Box<dyn Parser<&std::path::Path, Output = Registry>> = if args.parser.to_lowercase() == "python" {
-        Box::new(PythonParser::default())
-    } else if args.parser.to_lowercase() == "rust" {
-        Box::new(RustParser::default())
+    let cache_directory = args.cache_dir.clone().unwrap_or_else(ParseCache::default_directory);
+    let cache = (!args.no_cache).then(|| ParseCache::new(&cache_directory));
+    let parsers = parser_plugins(cache);
+    let generators = generator_plugins();
+    match args.command {
+        Some(Command::CleanCache) => return ParseCache::clean(&cache_directory),
+        Some(Command::Parsers) => {
+            for plugin in parsers.iter() {
+                println!("{}: {} Inputs: {}.", plugin.name, plugin.description, plugin.inputs.join(", "));
+            }
+            return Ok(());
+        },
+        Some(Command::Generators) => {
+            for plugin in generators.iter() {
+                println!("{}: {}", plugin.name, plugin.description);
+            }
+            return Ok(());
+        },
+        None => {}
+    }
+    // These are required without a command.
+    let parser_name = args.parser.clone().unwrap_or_default().to_lowercase();
+    let generator_name = args.generator.clone().unwrap_or_default();
+    let input = args.input.clone().unwrap_or_default();
+    // Fails early on unknown generators.
+    generators.create(&generator_name)?;
+    // The "ir" parser reads a library saved with `--save-ir`, migrating older files.
+    let registry = if parser_name == "ir" {
+        let format = args.ir_format.unwrap_or_else(|| Format::from_path(&input));
+        let library = Library::load_as(&input, format)?;
+        Registry { libraries: vec![library] }
     } else {
-        panic!("Parser not found.");
+        let parser = parsers.create(&parser_name)?;
+        let loader = args
+            .set
+            .iter()
+            .fold(ConfigLoader::new().with_schema(parser.schema()).discover(&input).with_environment(std::env::vars()), |loader, key_value| loader.with_override(key_value));
+        let loaded = loader.load(parser.config())?;
+        if args.print_config {
+            eprint!("{}", loaded);
+        }
+        let diagnostics = parser.schema().validate(&loaded.config);
+        for diagnostic in &diagnostics {
+            eprintln!("{}", diagnostic);
+        }
+        let errors = diagnostics.iter().filter(|diagnostic| diagnostic.is_error()).count();
+        if errors > 0 {
+            return Err(Error::Message(format!("Invalid configuration with {} error(s).", errors)));
+        }
+        let registry = parser.parse(input.as_path(), &loaded.config)?;
+        if args.print_filtered {
+            for filtered in parser.filtered() {
+                eprintln!("{}", filtered);
+            }
+        }
+        registry
     };
-    let config = parser.config();
-    let _registry = parser.parse(args.input.as_path(), &config)?;
+    if args.validate {
+        let diagnostics = LibraryValidator::new().check_registry(&registry);
+        for diagnostic in &diagnostics {
+            eprintln!("{}", diagnostic);
+        }
+        let errors = diagnostics.iter().filter(|diagnostic| diagnostic.is_error()).count();
+        if errors > 0 {
+            return Err(Error::Message(format!("Validation failed with {} error(s).", errors)));
+        }
+    }
+    if let Some(query) = &args.find {
+        for symbol in Symbols::from_registry(&registry).search(query) {
+            println!("{} {} {} ({})", symbol.visibility, symbol.kind, symbol.path, symbol.library);
+        }
+    }
+    if let Some(path) = &args.save_ir {
+        // Dependencies are parsed first, so the parsed library is the last one.
+        let library = registry.libraries.last().ok_or("No library was parsed.")?;
+        library.save_as(path, args.ir_format.unwrap_or_else(|| Format::from_path(path)))?;
+    }
     // for library in registry.libraries.iter() {
     //     LibraryGenerator::default().generate(&library, PathBuf::from(&args.output).as_path())?;
     // }
@@ -1,25 +1,27 @@
-use ligen_cargo::parser::library;
+use ligen_generator::{DynGenerator, GeneratorPlugin};
 
 use crate::prelude::*;
 use crate::gui::ui::editor::{widget::Widget, settings::Settings};
 
 pub struct Generator {
-    generator: Box<dyn ligen_generator::Generator<Input = ligen_ir::Library>>,
+    generator: DynGenerator,
+    name: String,
     result: String
 }
 
 impl Generator {
-    pub fn new<T: ligen_generator::Generator<Input = ligen_ir::Library> + 'static>(generator: T) -> Self {
-        let generator = Box::new(generator);
+    pub fn new(plugin: &GeneratorPlugin) -> Self {
+        let generator = plugin.create();
+        let name = plugin.name.clone();
         let result = Default::default();
-        Self { generator, result }
+        Self { generator, name, result }
     }
 }
 
 impl Widget for Generator {
     type Input = ligen_ir::Library;
     fn show(&mut self, settings: &Settings, ui: &mut egui::Ui, input: &mut Self::Input) {
-        ui.label("Generator");
+        ui.label(&self.name);
         if ui.button("Generate").clicked() {
             let entry = rfd::FileDialog::new()
             .pick_folder();
@@ -1,6 +1,6 @@
 pub mod generator;
 pub use generator::*;
-use ligen_rust_pyo3_importer::LibraryGenerator;
+use ligen_generator::GeneratorPlugins;
 
 use crate::gui::ui::panes::{Pane, PaneManager};
 
@@ -12,10 +12,9 @@ pub struct Generators {
 
 impl Default for Generators {
     fn default() -> Self {
-        let generators = vec![
-            Generator::new(LibraryGenerator::default())
-        ];
-        Self { generators }
+        let plugins = GeneratorPlugins::new()
+            .with(ligen_rust_pyo3_importer::plugin());
+        Self::from_plugins(&plugins)
     }
 }
 
@@ -23,6 +22,12 @@ impl Generators {
     pub fn new() -> Self {
         Default::default()
     }
+
+    /// A generator for each of the `plugins`.
+    pub fn from_plugins(plugins: &GeneratorPlugins) -> Self {
+        let generators = plugins.iter().map(Generator::new).collect();
+        Self { generators }
+    }
 }
 
 impl Widget for Generators {
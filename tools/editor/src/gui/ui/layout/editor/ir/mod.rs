@@ -26,7 +26,9 @@ pub use library::*;
 pub use module::*;
 pub use literal::*;
 pub use interface::*;
+use ligen_ir::Format;
 use ligen_ir::symbols::Symbols;
+use ligen_parser::{Diagnostic, LibraryValidator};
 use crate::gui::ui::List;
 
 use crate::gui::ui::panes::{Pane, PaneManager};
@@ -41,7 +43,8 @@ pub struct Editor {
     filter: String,
     settings: Settings,
     symbols: Symbols,
-    generators: Generators
+    generators: Generators,
+    diagnostics: Vec<Diagnostic>
 }
 
 impl Editor {
@@ -50,7 +53,8 @@ impl Editor {
         let symbols = Symbols::new(&library);
         let display_settings = Default::default();
         let generators = Default::default();
-        Self { library, symbols, filter, settings: display_settings, generators }
+        let diagnostics = Default::default();
+        Self { library, symbols, filter, settings: display_settings, generators, diagnostics }
     }
 }
 
@@ -65,7 +69,7 @@ impl Pane for Editor {
             ui.menu_button("File", |ui| {
                 if ui.button("Save").clicked() {
                     let file = rfd::FileDialog::new()
-                        .add_filter("ligen-ir", &["lir"])
+                        .add_filter("ligen-ir", &Format::all_extensions())
                         .save_file();
                     if let Some(file) = file {
                         self
@@ -76,6 +80,12 @@ impl Pane for Editor {
                     ui.close_menu();
                 }
             });
+            ui.menu_button("Validate", |ui| {
+                if ui.button("Validate library").clicked() {
+                    self.diagnostics = LibraryValidator::new().check_library(&self.library);
+                    ui.close_menu();
+                }
+            });
         });
         ui.separator();
         self.settings.show(ui);
@@ -84,12 +94,26 @@ impl Pane for Editor {
         ui.separator();
         self.generators.show(&self.settings, ui, &mut self.library);
         ui.separator();
+        if !self.diagnostics.is_empty() {
+            List::new("Diagnostics").show(ui, self.diagnostics.iter(), |ui, diagnostic| {
+                let color = if diagnostic.is_error() {
+                    egui::Color32::from_rgb(255, 0, 0)
+                } else {
+                    egui::Color32::from_rgb(255, 200, 0)
+                };
+                ui.colored_label(color, diagnostic.to_string());
+            });
+            ui.separator();
+        }
         ui.horizontal(|ui| {
             ui.label("Filter");
             ui.text_edit_singleline(&mut self.filter);
+            if ui.button("Refresh").clicked() {
+                self.symbols.update_library(&self.library);
+            }
         });
-        List::new("Symbols").show(ui, self.symbols.symbols.iter_mut().filter(|symbol| symbol.to_string().contains(self.filter.as_str())), |ui, symbol| {
-            ui.label(symbol.to_string());
+        List::new("Symbols").show(ui, self.symbols.search(&self.filter), |ui, symbol| {
+            ui.label(format!("{} ({})", symbol, symbol.visibility));
         });
         UiResponse::None
     }
@@ -1,4 +1,4 @@
-use ligen_ir::Library;
+use ligen_ir::{Format, Library};
 use ligen_python_parser::{PythonParser, PythonParserConfig};
 
 use crate::prelude::*;
@@ -15,11 +15,18 @@ impl MenuButton for EditorMenuButton {
     fn show_button(&self, ui: &mut egui::Ui, panes: &mut Panes) {
         if ui.button("Open").clicked() {
             let file = rfd::FileDialog::new()
-                .add_filter("ligen-ir", &["lir"])
+                .add_filter("ligen-ir", &Format::all_extensions())
                 .pick_file();
             if let Some(file) = file {
-                if let Ok(library) = ligen_ir::Library::load(file) {
-                    panes.new_pane(Box::new(Editor::new(library)));
+                match ligen_ir::Library::load(file) {
+                    Ok(library) => panes.new_pane(Box::new(Editor::new(library))),
+                    Err(error) => {
+                        rfd::MessageDialog::new()
+                            .set_level(rfd::MessageLevel::Error)
+                            .set_title("Failed to open library")
+                            .set_description(&format!("{:?}", error))
+                            .show();
+                    }
                 }
             }
             ui.close_menu();
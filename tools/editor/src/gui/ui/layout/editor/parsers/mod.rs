@@ -1,7 +1,6 @@
 use crate::{prelude::*, gui::ui::panes::Pane};
-use ligen_cargo::parser::library::LibraryParser;
-use ligen_parser::ParserConfigSet;
-use ligen_python_parser::{PythonParserConfig, PythonParser};
+use ligen_parser::{ParserConfigSet, ParserPlugins};
+use ligen_python_parser::PythonParserConfig;
 
 pub mod parser;
 pub use parser::*;
@@ -16,11 +15,10 @@ pub struct Parsers {
 
 impl Default for Parsers {
     fn default() -> Self {
-        let parsers = vec![
-            // Parser::new(PythonParser::default()),
-            Parser::new(LibraryParser::default()) // TODO: This is the Rust parser. We need to rename it and also make it parse from the project directory. It's currently using the Cargo.toml file.
-        ];
-        Self { parsers }
+        let plugins = ParserPlugins::new()
+            .with(ligen_python_parser::plugin())
+            .with(ligen_cargo::parser::library::plugin());
+        Self::from_plugins(&plugins)
     }
 }
 
@@ -28,6 +26,12 @@ impl Parsers {
     pub fn new() -> Self {
         Default::default()
     }
+
+    /// A parser for each of the `plugins`.
+    pub fn from_plugins(plugins: &ParserPlugins) -> Self {
+        let parsers = plugins.iter().map(Parser::new).collect();
+        Self { parsers }
+    }
 }
 
 impl Pane for Parsers {
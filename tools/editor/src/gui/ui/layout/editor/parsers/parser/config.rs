@@ -1,14 +1,71 @@
 use crate::{prelude::*, gui::ui::editor::ir::{Path, Literal}};
-use ligen_parser::ParserConfigSet;
+use egui::ComboBox;
+use ligen_parser::{ConfigOption, ConfigSchema, OptionKind, ParserConfigGet, ParserConfigSet};
 
 use crate::gui::ui::editor::{widget::Widget, settings::Settings};
 
+/// Renders a form with the options declared by the parser's schema, followed by the other keys.
 #[derive(Default)]
-pub struct ParserConfig {}
+pub struct ParserConfig {
+    schema: ConfigSchema
+}
 
 impl ParserConfig {
-    pub fn new() -> Self {
-        Default::default()
+    pub fn new(schema: ConfigSchema) -> Self {
+        Self { schema }
+    }
+
+    fn show_option(&self, ui: &mut egui::Ui, option: &ConfigOption, literal: &mut ligen_ir::Literal) {
+        if !option.kind.accepts(literal) {
+            *literal = option.default.clone().unwrap_or_else(|| match option.kind {
+                OptionKind::Boolean => false.into(),
+                OptionKind::Integer => 0i64.into(),
+                OptionKind::Float => 0.0f64.into(),
+                OptionKind::String => String::new().into(),
+                OptionKind::Strings => ligen_ir::Literal::Vector(Default::default())
+            });
+        }
+        if !option.allowed_values.is_empty() {
+            ComboBox::new(option.path.to_string(), "")
+                .selected_text(literal.to_string())
+                .show_ui(ui, |ui| {
+                    for value in &option.allowed_values {
+                        ui.selectable_value(literal, value.clone(), value.to_string());
+                    }
+                });
+            return;
+        }
+        match literal {
+            ligen_ir::Literal::Boolean(value) => {
+                ui.checkbox(value, "");
+            },
+            ligen_ir::Literal::Integer(value) => {
+                ui.add(egui::DragValue::new(value));
+            },
+            ligen_ir::Literal::UnsignedInteger(value) => {
+                ui.add(egui::DragValue::new(value));
+            },
+            ligen_ir::Literal::Float(value) => {
+                ui.add(egui::DragValue::new(value));
+            },
+            ligen_ir::Literal::String(value) => {
+                ui.text_edit_singleline(value);
+            },
+            ligen_ir::Literal::Vector(values) | ligen_ir::Literal::Tuple(values) => {
+                let mut text = values.iter().map(|value| value.to_string()).collect::<Vec<_>>().join(", ");
+                if ui.text_edit_singleline(&mut text).changed() {
+                    *values = text
+                        .split(',')
+                        .map(str::trim)
+                        .filter(|value| !value.is_empty())
+                        .map(|value| ligen_ir::Literal::String(value.into()))
+                        .collect();
+                }
+            },
+            literal => {
+                ui.label(literal.to_string());
+            }
+        }
     }
 }
 
@@ -16,8 +73,33 @@ impl Widget for ParserConfig {
     type Input = ligen_parser::ParserConfig;
     fn show(&mut self, settings: &Settings, ui: &mut egui::Ui, input: &mut Self::Input) {
         ui.label("Configuration");
-        for (index, (mut path, mut literal)) in input.iter().enumerate() {
-            ui.push_id(index, |ui| {
+        // Options with a `*` segment stand for many keys, so they are edited as other keys.
+        let options = self
+            .schema
+            .options
+            .iter()
+            .filter(|option| !option.path.segments.iter().any(|segment| segment.identifier == "*"))
+            .collect::<Vec<_>>();
+        for (index, option) in options.iter().enumerate() {
+            ui.push_id(("option", index), |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(option.path.to_string()).on_hover_text(&option.description);
+                    let mut literal = input
+                        .get(option.path.clone())
+                        .or(option.default.as_ref())
+                        .cloned()
+                        .unwrap_or(ligen_ir::Literal::None);
+                    self.show_option(ui, option, &mut literal);
+                    input.set(option.path.clone(), literal);
+                });
+            });
+        }
+        let others = input
+            .iter()
+            .filter(|(path, _)| !options.iter().any(|option| option.path == *path))
+            .collect::<Vec<_>>();
+        for (index, (mut path, mut literal)) in others.into_iter().enumerate() {
+            ui.push_id(("other", index), |ui| {
                 ui.horizontal(|ui| {
                     Path::default().show(settings, ui, &mut path);
                     Literal::default().show(settings, ui, &mut literal);
@@ -26,4 +108,4 @@ impl Widget for ParserConfig {
             });
         }
     }
-}
\ No newline at end of file
+}
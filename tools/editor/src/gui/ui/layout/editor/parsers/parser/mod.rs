@@ -3,24 +3,25 @@ pub use config::*;
 use ligen_gui_runtime::egui::{CollapsingHeader, Color32};
 
 use crate::{prelude::*, gui::ui::{editor::{widget::Widget, settings::Settings, ir::Editor}, panes::PaneManager}};
-use std::path::Path;
-use ligen_parser::{self, Parser as ParserTrait, ParserConfigSet, ParserConfig, ParserConfigGet};
-use ligen_python_parser::{PythonParser, PythonParserConfig};
+use ligen_parser::{self, DynParser, Parser as ParserTrait, ParserConfigSet, ParserConfig, ParserConfigGet, ParserPlugin};
+use ligen_python_parser::PythonParserConfig;
 
 pub struct Parser {
-    parser: Box<dyn for<'a> ligen_parser::Parser<&'a Path, Output = ligen_ir::Library>>,
+    parser: DynParser,
+    description: String,
     config: ParserConfig,
+    config_widget: config::ParserConfig,
     result: String
 }
 
 impl Parser {
-    pub fn new<T>(parser: T) -> Self
-    where T: for<'a> ligen_parser::Parser<&'a Path, Output = ligen_ir::Library> + 'static
-    {
-        let config = parser.config();
-        let parser = Box::new(parser);
+    pub fn new(plugin: &ParserPlugin) -> Self {
+        let parser = plugin.create();
+        let description = plugin.description.clone();
+        let config = plugin.config.clone();
+        let config_widget = config::ParserConfig::new(plugin.schema.clone());
         let result = Default::default();
-        Self { parser, config, result }
+        Self { parser, description, config, config_widget, result }
     }
 }
 
@@ -30,14 +31,19 @@ impl Widget for Parser {
         CollapsingHeader::new(self.parser.name())
             .default_open(false)
             .show(ui, |ui| {
-                config::ParserConfig::new().show(settings, ui, &mut self.config);
+                ui.label(&self.description);
+                self.config_widget.show(settings, ui, &mut self.config);
                 if ui.button("Parse").clicked() {
                     let entry = rfd::FileDialog::new()
                         .pick_folder();
                     if let Some(entry) = entry {
                         stacker::grow(1024 * 1024 * 10, || {
-                            match self.parser.parse(entry.as_path(), &self.config) {
-                                Ok(library) => pane_manager.new_pane(Box::new(Editor::new(library))),
+                            // Dependencies are parsed first, so the parsed library is the last one.
+                            match self.parser.parse(entry.as_path(), &self.config).map(|registry| registry.libraries.into_iter().last()) {
+                                Ok(Some(library)) => pane_manager.new_pane(Box::new(Editor::new(library))),
+                                Ok(None) => {
+                                    self.result = "No library was parsed.".to_string();
+                                },
                                 Err(error) => {
                                     self.result = format!("{:?}", error);
                                 }